@@ -0,0 +1,63 @@
+# Times converting large variables into the core and large response data back out. Run
+# it against builds with and without the `orjson` cargo feature (with orjson installed)
+# to compare the fast path with the recursive converter.
+import argparse
+import json
+import time
+from dataclasses import dataclass
+
+import uvloop
+
+import grommet as gm
+
+
+@gm.type
+@dataclass
+class Query:
+    @gm.field
+    def echo(self, values: list[str]) -> list[str]:
+        return values
+
+
+async def _run_case(*, schema: gm.Schema, size: int, rounds: int) -> list[float]:
+    values = [f"value {i}" for i in range(size)]
+    elapsed: list[float] = []
+    for _ in range(rounds):
+        start = time.perf_counter()
+        result = await schema.execute(
+            "query ($values: [String!]!) { echo(values: $values) }", {"values": values}
+        )
+        elapsed.append(time.perf_counter() - start)
+        assert not result.errors
+    return elapsed
+
+
+async def _run_sizes(*, sizes: list[int], rounds: int) -> dict[str, float]:
+    schema = gm.Schema(query=Query)
+    out: dict[str, float] = {}
+    for size in sizes:
+        samples = sorted(await _run_case(schema=schema, size=size, rounds=rounds))
+        out[str(size)] = samples[len(samples) // 2]
+    return out
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--sizes", type=int, nargs="+", default=[100, 1_000, 10_000, 100_000]
+    )
+    parser.add_argument("--rounds", type=int, default=5)
+    parser.add_argument("--json", action="store_true")
+    args = parser.parse_args()
+
+    results = uvloop.run(_run_sizes(sizes=args.sizes, rounds=args.rounds))
+    if args.json:
+        print(json.dumps(results, sort_keys=True))
+        return
+
+    for size, median in results.items():
+        print(f"{size} values: median={median:.4f}s")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,13 +1,21 @@
-from .decorators import field, input, interface, subscription, type
-from .metadata import Context, Field, Hidden, Union
+from ._core import Complete, Event, GlobalID, Info
+from .decorators import extend, field, input, interface, subscription, type
+from .metadata import ID, Context, Field, Hidden, Lazy, Union
 from .schema import Schema
 
 __all__ = [
+    "Complete",
     "Context",
+    "Event",
     "Field",
+    "GlobalID",
     "Hidden",
+    "ID",
+    "Info",
+    "Lazy",
     "Schema",
     "Union",
+    "extend",
     "field",
     "input",
     "interface",
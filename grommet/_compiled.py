@@ -6,12 +6,22 @@ if TYPE_CHECKING:
     from collections.abc import Callable
     from typing import Any, Literal
 
-    from .metadata import TypeMeta, TypeSpec
+    from .metadata import LazyRef, TypeMeta, TypeSpec
 
 META_ATTR = "__grommet_meta__"
 REFS_ATTR = "__grommet_refs__"
 COMPILED_RESOLVER_ATTR = "__grommet_compiled_resolver__"
 COMPILED_TYPE_ATTR = "__grommet_compiled_type__"
+EXTENSIONS_ATTR = "__grommet_extensions__"
+
+
+@dataclass(frozen=True, slots=True)
+class CompiledConstraints:
+    min_length: int | None = None
+    max_length: int | None = None
+    minimum: float | None = None
+    maximum: float | None = None
+    pattern: str | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -20,6 +30,10 @@ class CompiledArg:
     type_spec: "TypeSpec"
     has_default: bool = False
     default: object | None = None
+    constraints: CompiledConstraints | None = None
+    validate: "Callable[[Any], object] | None" = None
+    deprecation: str | None = None
+    default_literal: str | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -32,7 +46,16 @@ class CompiledResolverField:
     type_spec: "TypeSpec"
     description: str | None
     args: tuple[CompiledArg, ...]
-    refs: frozenset["pytype"]
+    refs: frozenset["pytype | LazyRef"]
+    requires_role: str | None = None
+    batch: bool = False
+    needs_info: bool = False
+    on_error: "Literal['stop', 'skip', 'emit']" = "stop"
+    retries: int = 0
+    backoff_ms: int = 0
+    retry_on: tuple[str, ...] = ()
+    serial: bool = False
+    doc: str | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -43,7 +66,9 @@ class CompiledDataField:
     has_default: bool
     default: object | None
     resolver_func: "Callable[..., Any]"
-    refs: frozenset["pytype"]
+    refs: frozenset["pytype | LazyRef"]
+    requires_role: str | None = None
+    deprecation: str | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -53,7 +78,10 @@ class CompiledInputField:
     description: str | None
     has_default: bool
     default: object | None
-    refs: frozenset["pytype"]
+    refs: frozenset["pytype | LazyRef"]
+    requires_role: str | None = None
+    constraints: CompiledConstraints | None = None
+    deprecation: str | None = None
 
 
 @dataclass(frozen=True, slots=True)
@@ -63,7 +91,7 @@ class CompiledType:
     subscription_fields: tuple[CompiledResolverField, ...] = ()
     input_fields: tuple[CompiledInputField, ...] = ()
     implements: tuple[str, ...] = ()
-    refs: frozenset["pytype"] = frozenset()
+    refs: frozenset["pytype | LazyRef"] = frozenset()
 
 
 @dataclass(frozen=True, slots=True)
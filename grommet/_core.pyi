@@ -1,23 +1,367 @@
-from collections.abc import AsyncIterator
-from typing import Any
+import os
+from collections.abc import (
+    AsyncIterator,
+    Awaitable,
+    Callable,
+    Iterator,
+    Mapping,
+    Sequence,
+)
+from typing import Any, Literal, TypedDict
+
+class GrommetError(Exception):
+    """Base exception for grommet errors."""
+
+class SchemaBuildError(GrommetError, ValueError):
+    """Raised when a schema cannot be built from its types and options."""
+
+class ValidationFailed(GrommetError, ValueError):
+    """Raised when an operation does not parse or validate against the schema."""
+
+class SubscriptionClosed(GrommetError, RuntimeError):
+    """Raised when a subscription stream can no longer deliver events."""
 
 class OperationResult:
     """Result of a GraphQL operation with data, errors, and extensions."""
 
     data: dict[str, Any]
-    errors: list[dict[str, Any]] | None
+    errors: list[dict[str, Any]] | list[GraphQLError] | None
     extensions: dict[str, Any] | None
+    status_hint: int
     def __repr__(self) -> str: ...
     def __getitem__(self, key: str) -> Any: ...
 
+class ExecutionResult:
+    """graphql-core shaped result of schemas built with `execution_result=True`."""
+
+    data: dict[str, Any] | None
+    errors: list[dict[str, Any]] | list[GraphQLError] | None
+    extensions: dict[str, Any] | None
+    status_hint: int
+    @property
+    def ok(self) -> bool: ...
+    @property
+    def formatted(self) -> dict[str, Any]: ...
+    def __iter__(self) -> Iterator[Any]: ...
+    def __repr__(self) -> str: ...
+
+class SubscriptionPayload:
+    """Event of a subscription streamed with `envelope=True`."""
+
+    __match_args__ = ("data", "errors", "is_keepalive", "cursor")
+    data: dict[str, Any] | None
+    errors: list[dict[str, Any]] | list[GraphQLError] | None
+    extensions: dict[str, Any] | None
+    is_keepalive: bool
+    cursor: str | None
+    @staticmethod
+    def keepalive() -> SubscriptionPayload: ...
+    def __repr__(self) -> str: ...
+
+class GraphQLError:
+    """Response error returned in place of a dict with the `error_objects` option."""
+
+    message: str
+    path: tuple[str | int, ...]
+    locations: list[dict[str, int]]
+    extensions: dict[str, Any]
+    original_exception: BaseException | None
+
+class ResponseHandle:
+    """Unconverted operation response with lazily converted accessors."""
+
+    def data(self) -> dict[str, Any] | None: ...
+    def errors(self) -> list[dict[str, Any]] | list[GraphQLError] | None: ...
+    def extensions(self) -> dict[str, Any] | None: ...
+    def status_hint(self) -> int: ...
+    def to_json(self) -> str: ...
+    def to_msgpack(self) -> bytes: ...
+
+class RequestOptions(TypedDict, total=False):
+    """Request-level settings accepted by `Schema.execute` and `Schema.execute_raw`."""
+
+    disable_introspection: bool
+    introspection_only: bool
+    parse_only: bool
+    error_objects: bool
+    extensions: Mapping[str, Any]
+    visibility: Callable[[str, str], bool] | None
+    raw: bool
+    resume_from: str | None
+    coercion_warnings: bool
+    sort_keys: bool
+    read_only: bool
+    operation_name: str | None
+    idempotency_key: str | None
+    max_lifetime_s: float | None
+    idle_timeout_s: float | None
+    labels: Sequence[str] | None
+    envelope: bool
+    chunk_size: int | None
+
 class Schema:
-    def __init__(self, bundle: Any) -> None: ...
+    def __init__(
+        self,
+        bundle: Any,
+        *,
+        default_resolver: Callable[[Any, Info], Any]
+        | Mapping[str, Callable[[Any, Info], Any]]
+        | list[Callable[[Any, Info], Any]]
+        | ResolverRegistry
+        | None = None,
+        serializers: Mapping[str, Callable[[Any], Any]] | None = None,
+        interfaces: Mapping[str, Mapping[str, type]] | None = None,
+        execution_result: bool = False,
+        manage_context: bool = False,
+        typed_ids: bool = False,
+        docstring_descriptions: bool = False,
+        require_mutation_resolvers: bool = False,
+    ) -> None: ...
+    @classmethod
+    def build_async(
+        cls,
+        bundle: Any,
+        *,
+        default_resolver: Callable[[Any, Info], Any]
+        | Mapping[str, Callable[[Any, Info], Any]]
+        | list[Callable[[Any, Info], Any]]
+        | ResolverRegistry
+        | None = None,
+        serializers: Mapping[str, Callable[[Any], Any]] | None = None,
+        interfaces: Mapping[str, Mapping[str, type]] | None = None,
+        execution_result: bool = False,
+        manage_context: bool = False,
+        typed_ids: bool = False,
+        docstring_descriptions: bool = False,
+        require_mutation_resolvers: bool = False,
+    ) -> Awaitable[Schema]: ...
     async def execute(
-        self, query: str, variables: dict[str, Any] | None = None, context: Any = None
-    ) -> OperationResult | SubscriptionStream: ...
+        self,
+        query: str | bytes,
+        variables: dict[str, Any] | None = None,
+        context: Any = None,
+        *,
+        options: RequestOptions | None = None,
+    ) -> (
+        OperationResult | ExecutionResult | ResponseHandle | SubscriptionStream
+    ): ...
+    async def execute_raw(
+        self,
+        query: str | bytes,
+        variables_json: bytes | None = None,
+        context: Any = None,
+        *,
+        options: RequestOptions | None = None,
+    ) -> (
+        OperationResult | ExecutionResult | ResponseHandle | SubscriptionStream
+    ): ...
+    async def execute_mock(
+        self,
+        query: str,
+        variables: dict[str, Any] | None = None,
+        context: Any = None,
+        *,
+        seed: int | None = None,
+        list_length: int = 2,
+    ) -> OperationResult | ExecutionResult: ...
+    async def response_fingerprint(
+        self,
+        query: str,
+        variables: dict[str, Any] | None = None,
+        context: Any = None,
+    ) -> str: ...
+    async def call(
+        self, field_name: str, *, context: Any = None, **args: Any
+    ) -> Any: ...
+    async def execute_document(
+        self,
+        query: str,
+        operations: list[str],
+        variables: dict[str, dict[str, Any]] | None = None,
+        context: Any = None,
+    ) -> dict[str, OperationResult | ExecutionResult]: ...
     def as_sdl(self) -> str: ...
+    def set_sdl_header(
+        self, description: str | None = None, *, comments: str | None = None
+    ) -> None: ...
+    def generate_types(
+        self, path: str | os.PathLike[str], style: str = "dataclass"
+    ) -> None: ...
+    async def export_introspection(
+        self, path: str | os.PathLike[str], minified: bool = False
+    ) -> None: ...
+    def restrict(self, roles: list[str]) -> Schema: ...
+    def reload_type(
+        self, type_def: Any, resolvers: Sequence[Any] | None = None
+    ) -> None: ...
+    def set_request_limits(
+        self,
+        *,
+        max_query_bytes: int | None = None,
+        max_variables: int | None = None,
+        max_variable_bytes: int | None = None,
+        max_response_bytes: int | None = None,
+        max_list_items: int | None = None,
+    ) -> None: ...
+    def set_result_hook(self, hook: Callable[[str, Any], Any] | None) -> None: ...
+    def set_pre_execute(
+        self, hook: Callable[[dict[str, Any], Any], Any] | None
+    ) -> None: ...
+    def set_node_resolvers(
+        self, resolvers: dict[str, Callable[[str, Any], Any]]
+    ) -> None: ...
+    def set_source_lookup(
+        self, order: Sequence[str] = ("attr", "item"), strict: bool = False
+    ) -> None: ...
+    def set_rate_limits(
+        self,
+        rules: Sequence[Mapping[str, Any]] | None,
+        key: Callable[[Any], Any] | None = None,
+    ) -> None: ...
+    def set_remote(
+        self,
+        url: str | None,
+        headers: dict[str, str] | None = None,
+        transport: Callable[[str, str, dict[str, str]], Awaitable[bytes | str]]
+        | None = None,
+        timeout_s: float = 30.0,
+    ) -> None: ...
+    def set_replay_buffer(self, size: int) -> None: ...
+    def set_max_value_depth(self, depth: int) -> None: ...
+    def set_gil_yield_interval(self, items: int) -> None: ...
+    def set_string_policy(
+        self, policy: Literal["keep", "error", "replace"]
+    ) -> None: ...
+    def set_idempotency_ttl(self, ttl_s: float) -> None: ...
+    def set_introspection_trim(
+        self,
+        *,
+        omit_descriptions: bool = False,
+        max_description_length: int | None = None,
+    ) -> None: ...
+    def set_debug(self, enabled: bool = True) -> None: ...
+    def set_coverage(self, enabled: bool = True) -> None: ...
+    def coverage(self) -> dict[str, int]: ...
+    def set_call_capture(self, enabled: bool = True) -> None: ...
+    def captured_calls(self) -> list[dict[str, Any]]: ...
+    def active_subscriptions(self) -> list[dict[str, Any]]: ...
+    def cancel_all(self, label: str | None = None) -> int: ...
+    def types(self) -> list[dict[str, Any]]: ...
+    def fields(self, type_name: str) -> list[dict[str, Any]]: ...
+    def resolver_keys(self) -> list[str]: ...
+    def explain(
+        self, query: str, operation_name: str | None = None
+    ) -> dict[str, Any]: ...
+    async def warm_up(self, operations: list[str]) -> dict[str, list[str]]: ...
+    def set_shadow_schema(
+        self, schema: Schema | None, *, compare: bool = True
+    ) -> None: ...
+    def shadow_stats(self) -> dict[str, int] | None: ...
+    def debug_info(self) -> dict[str, Any]: ...
+    def cache_stats(self) -> dict[str, dict[str, Any]]: ...
+    def clear_caches(self) -> None: ...
+    async def check_operation(
+        self, query: str, variables_types: dict[str, str] | None = None
+    ) -> dict[str, Any]: ...
+
+class SchemaRegistry:
+    """Named schemas that can be replaced atomically while requests run."""
+
+    def __init__(self) -> None: ...
+    def swap(self, name: str, schema: Schema) -> Schema | None: ...
+    def get(self, name: str) -> Schema: ...
+    def remove(self, name: str) -> Schema | None: ...
+    def names(self) -> list[str]: ...
+    def __contains__(self, name: str) -> bool: ...
+    def __len__(self) -> int: ...
+
+class ResolverRegistry:
+    """Default resolvers registered by decorators, keyed by field coordinate."""
+
+    def __init__(self) -> None: ...
+    def register[F: Callable[..., Any]](
+        self, type_name: str, field_name: str
+    ) -> Callable[[F], F]: ...
+    def keys(self) -> list[str]: ...
+    def __contains__(self, key: str) -> bool: ...
+    def __len__(self) -> int: ...
+
+class FieldSelection:
+    """Field of a `QueryBuilder` operation with its arguments and subselections."""
+
+    def select(self, *selections: str | FieldSelection) -> FieldSelection: ...
+    def alias(self, alias: str) -> FieldSelection: ...
+
+class QueryBuilder:
+    """Builds operation strings checked against a schema's fields."""
+
+    def __init__(self, schema: Schema) -> None: ...
+    def field(self, name: str, **args: Any) -> FieldSelection: ...
+    def query(
+        self, *selections: str | FieldSelection, name: str | None = None
+    ) -> str: ...
+    def mutation(
+        self, *selections: str | FieldSelection, name: str | None = None
+    ) -> str: ...
+
+class Event:
+    """Subscription event tagged with a cursor clients can resume from."""
+
+    value: Any
+    cursor: str
+    def __init__(self, value: Any, cursor: str) -> None: ...
+
+class Complete:
+    """Final subscription value; the stream delivers it and then ends."""
+
+    value: Any
+    def __init__(self, value: Any) -> None: ...
+
+class GlobalID:
+    """Relay global ID decoded into its node type and raw id; `str()` encodes it."""
+
+    type: str
+    id: str
+    def __init__(self, type_name: str, id: str) -> None: ...
+
+class Info:
+    """Details of the field being resolved, for resolvers that declare them."""
+
+    field_name: str
+    context: Any
+    root_value: Any
+    path: list[str | int]
+    schema: Any
+    variable_values: dict[str, Any]
+    operation_name: str | None
 
 class SubscriptionStream:
-    def __aiter__(self) -> AsyncIterator[OperationResult]: ...
-    async def __anext__(self) -> OperationResult: ...
+    operation_name: str | None
+    field_name: str | None
+    started_at: float
+    @property
+    def cursor(self) -> str | None: ...
+    def close_reason(self) -> str | None: ...
+    def is_closed(self) -> bool: ...
+    def __aiter__(
+        self,
+    ) -> AsyncIterator[OperationResult | ExecutionResult | SubscriptionPayload]: ...
+    async def __anext__(
+        self,
+    ) -> OperationResult | ExecutionResult | SubscriptionPayload: ...
     async def aclose(self) -> None: ...
+    async def __aenter__(self) -> "SubscriptionStream": ...
+    async def __aexit__(
+        self, exc_type: Any, exc_value: Any, traceback: Any
+    ) -> bool: ...
+
+def compare_responses(
+    a: ResponseHandle | Any,
+    b: ResponseHandle | Any,
+    ignore_paths: list[str] | None = None,
+) -> list[dict[str, Any]]: ...
+def operation_signature(query: str, operation_name: str | None = None) -> str: ...
+def parse_http_get(
+    query_string: str,
+) -> tuple[str, str | None, dict[str, Any] | None]: ...
+def validate_definition(bundle: Any) -> list[dict[str, str]]: ...
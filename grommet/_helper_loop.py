@@ -0,0 +1,30 @@
+import asyncio
+import threading
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from collections.abc import Awaitable
+    from concurrent.futures import Future
+
+_lock = threading.Lock()
+_loop: "asyncio.AbstractEventLoop | None" = None
+
+
+def _helper_loop() -> "asyncio.AbstractEventLoop":
+    global _loop
+    with _lock:
+        if _loop is None:
+            _loop = asyncio.new_event_loop()
+            threading.Thread(
+                target=_loop.run_forever, name="grommet-helper-loop", daemon=True
+            ).start()
+        return _loop
+
+
+async def _await(awaitable: "Awaitable[object]") -> object:
+    return await awaitable
+
+
+def submit(awaitable: "Awaitable[object]") -> "Future[object]":
+    """Schedule a resolver's awaitable on the helper loop."""
+    return asyncio.run_coroutine_threadsafe(_await(awaitable), _helper_loop())
@@ -0,0 +1,28 @@
+import asyncio
+import urllib.request
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from collections.abc import Awaitable, Callable
+
+
+def _post(url: str, body: str, headers: "dict[str, str]", timeout: float) -> bytes:
+    request = urllib.request.Request(
+        url,
+        data=body.encode(),
+        headers={"Content-Type": "application/json", **headers},
+        method="POST",
+    )
+    with urllib.request.urlopen(request, timeout=timeout) as response:
+        return response.read()
+
+
+def urllib_transport(
+    timeout: float,
+) -> "Callable[[str, str, dict[str, str]], Awaitable[bytes]]":
+    """Returns a transport posting GraphQL request bodies with a timeout in seconds."""
+
+    async def post_graphql(url: str, body: str, headers: "dict[str, str]") -> bytes:
+        return await asyncio.to_thread(_post, url, body, headers, timeout)
+
+    return post_graphql
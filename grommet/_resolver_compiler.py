@@ -5,6 +5,15 @@ from noaio import can_syncify, syncify
 
 from ._annotations import get_annotations
 from ._compiled import CompiledArg, CompiledResolverField
+from ._core import Info
+from ._type_compiler import (
+    _DEFAULT_FIELD_META,
+    _compile_constraints,
+    _compile_default_literal,
+    _compile_deprecation,
+    _first_doc_line,
+    _get_annotated_field_meta,
+)
 from .annotations import (
     _type_spec_from_annotation,
     analyze_annotation,
@@ -13,9 +22,16 @@ from .annotations import (
 )
 from .coercion import _arg_coercer, _default_value_for_annotation
 from .errors import (
+    batch_resolver_info_not_supported,
+    batch_resolver_requires_list,
+    batch_resolver_retry_not_supported,
+    batch_resolver_serial_not_supported,
     resolver_context_annotation_requires_annotated,
     resolver_missing_annotation,
     resolver_requires_async,
+    retry_policy_invalid,
+    subscription_emit_requires_nullable,
+    subscription_error_policy_invalid,
 )
 from .metadata import Context
 
@@ -24,6 +40,8 @@ if TYPE_CHECKING:
     from collections.abc import Callable
     from typing import Any, Literal
 
+    from .metadata import LazyRef
+
 
 def _resolver_params(resolver: "Callable[..., Any]") -> list[inspect.Parameter]:
     sig = inspect.signature(resolver)
@@ -39,6 +57,10 @@ def _is_context_annotation(annotation: "Any") -> bool:
     return analyze_annotation(annotation).is_context
 
 
+def _is_info_annotation(annotation: "Any") -> bool:
+    return annotation is Info
+
+
 def _is_bare_context_annotation(annotation: "Any") -> bool:
     return annotation is Context
 
@@ -51,18 +73,24 @@ def _resolver_adapter(
     func: "Callable[..., Any]",
     *,
     context_param_names: tuple[str, ...],
+    info_param_names: tuple[str, ...],
     arg_names: tuple[str, ...],
     coercers: list[tuple[str, "Callable[[Any], Any]"]],
 ) -> "Callable[..., Any]":
     """Adapt a resolver to a stable runtime call shape used by Rust."""
     coercer_map = dict(coercers)
 
-    def _adapter(parent: "Any", context: "Any", kwargs: dict[str, "Any"]) -> "Any":
+    def _adapter(
+        parent: "Any", context: "Any", kwargs: dict[str, "Any"], info: "Any" = None
+    ) -> "Any":
         call_kwargs: dict[str, "Any"] = {}
 
         for name in context_param_names:
             call_kwargs[name] = context
 
+        for name in info_param_names:
+            call_kwargs[name] = info
+
         for name in arg_names:
             if name not in kwargs:
                 continue
@@ -81,8 +109,9 @@ def _resolver_adapter(
 
 def _partition_context_params(
     resolver_name: str, params: list[inspect.Parameter], hints: dict[str, "Any"]
-) -> tuple[list[str], list[inspect.Parameter]]:
+) -> tuple[list[str], list[str], list[inspect.Parameter]]:
     context_param_names: list[str] = []
+    info_param_names: list[str] = []
     graphql_arg_params: list[inspect.Parameter] = []
 
     for param in params:
@@ -99,9 +128,27 @@ def _partition_context_params(
             context_param_names.append(param.name)
             continue
 
+        if _is_info_annotation(annotation):
+            info_param_names.append(param.name)
+            continue
+
         graphql_arg_params.append(param)
 
-    return context_param_names, graphql_arg_params
+    return context_param_names, info_param_names, graphql_arg_params
+
+
+def _argument_validator(
+    validate: "Callable[[Any], object] | None",
+    coercer: "Callable[[Any], Any] | None",
+) -> "Callable[[Any], object] | None":
+    """Run an argument's validate callable against the value its resolver receives."""
+    if validate is None or coercer is None:
+        return validate
+
+    def _validate(value: "Any") -> object:
+        return validate(coercer(value))
+
+    return _validate
 
 
 def _build_arg_info(
@@ -132,12 +179,27 @@ def _build_arg_info(
         if has_default:
             default = _default_value_for_annotation(annotation, param.default)
 
+        field_meta = _get_annotated_field_meta(annotation) or _DEFAULT_FIELD_META
+        default_literal = _compile_default_literal(
+            f"argument '{param.name}'", field_meta, has_default=has_default
+        )
         args.append(
             CompiledArg(
                 name=param.name,
                 type_spec=type_spec,
                 has_default=has_default,
                 default=default,
+                constraints=_compile_constraints(
+                    f"argument '{param.name}'", field_meta, type_spec
+                ),
+                validate=_argument_validator(field_meta.validate, coercer),
+                deprecation=_compile_deprecation(
+                    f"argument '{param.name}'",
+                    field_meta,
+                    type_spec,
+                    has_default=has_default or default_literal is not None,
+                ),
+                default_literal=default_literal,
             )
         )
 
@@ -146,8 +208,8 @@ def _build_arg_info(
 
 def _collect_refs(
     return_ann: "Any", arg_params: list[inspect.Parameter], hints: dict[str, "Any"]
-) -> "frozenset[pytype]":
-    refs: list[pytype] = list(walk_annotation(return_ann))
+) -> "frozenset[pytype | LazyRef]":
+    refs: list[pytype | LazyRef] = list(walk_annotation(return_ann))
     for param in arg_params:
         param_ann = hints.get(param.name, param.annotation)
         if param_ann is not inspect._empty:
@@ -155,24 +217,69 @@ def _collect_refs(
     return frozenset(refs)
 
 
+def _batch_item_annotation(
+    resolver_name: str, field_name: str, return_ann: "Any"
+) -> "Any":
+    """Batch resolvers return one value per parent; the field has the item type."""
+    info = analyze_annotation(return_ann)
+    if not info.is_list or info.optional or info.list_item is None:
+        raise batch_resolver_requires_list(resolver_name, field_name)
+    return info.list_item
+
+
+def _check_retry_policy(
+    resolver: "Callable[..., Any]",
+    field_name: str,
+    retries: int,
+    backoff_ms: int,
+    *,
+    batch: bool,
+) -> None:
+    """Retries re-await a plain async resolver, so they need one to call again."""
+    resolver_name = _resolver_name(resolver)
+    if retries < 0 or backoff_ms < 0:
+        raise retry_policy_invalid(resolver_name, retries, backoff_ms)
+    if batch:
+        raise batch_resolver_retry_not_supported(resolver_name, field_name)
+    if not inspect.iscoroutinefunction(resolver):
+        raise resolver_requires_async(resolver_name, field_name)
+
+
 def compile_resolver_field(
     resolver: "Callable[..., Any]",
     *,
     field_name: str,
     description: str | None,
     kind: "Literal['field', 'subscription']",
+    requires_role: str | None = None,
+    batch: bool = False,
+    on_error: "Literal['stop', 'skip', 'emit']" = "stop",
+    retries: int = 0,
+    backoff_ms: int = 0,
+    retry_on: tuple[str, ...] = (),
+    serial: bool = False,
 ) -> CompiledResolverField:
     """Compile a resolver into an immutable blueprint used for schema registration."""
     resolver_name = _resolver_name(resolver)
 
     if kind == "subscription" and not inspect.isasyncgenfunction(resolver):
         raise resolver_requires_async(resolver_name, field_name)
+    if batch and not inspect.iscoroutinefunction(resolver):
+        raise resolver_requires_async(resolver_name, field_name)
+    if retries or backoff_ms or retry_on:
+        _check_retry_policy(resolver, field_name, retries, backoff_ms, batch=batch)
+    if serial and batch:
+        raise batch_resolver_serial_not_supported(resolver_name, field_name)
+    if serial and not inspect.iscoroutinefunction(resolver):
+        raise resolver_requires_async(resolver_name, field_name)
 
     hints = get_annotations(resolver)
     params = _resolver_params(resolver)
-    context_param_names, graphql_arg_params = _partition_context_params(
-        resolver_name, params[1:], hints
+    context_param_names, info_param_names, graphql_arg_params = (
+        _partition_context_params(resolver_name, params[1:], hints)
     )
+    if batch and info_param_names:
+        raise batch_resolver_info_not_supported(resolver_name, field_name)
 
     arg_names, coercers, args = _build_arg_info(
         resolver_name, graphql_arg_params, hints
@@ -181,7 +288,14 @@ def compile_resolver_field(
     is_async = kind == "subscription" or is_coroutine
     func = resolver
 
-    if kind == "field" and is_coroutine and can_syncify(resolver):
+    if (
+        kind == "field"
+        and is_coroutine
+        and not batch
+        and not retries
+        and not serial
+        and can_syncify(resolver)
+    ):
         func = syncify(resolver)
         is_async = False
 
@@ -189,6 +303,7 @@ def compile_resolver_field(
     func = _resolver_adapter(
         func,
         context_param_names=tuple(context_param_names),
+        info_param_names=tuple(info_param_names),
         arg_names=arg_names_tuple,
         coercers=coercers,
     )
@@ -200,7 +315,13 @@ def compile_resolver_field(
     output_ann = (
         unwrap_async_iterable(return_ann)[0] if kind == "subscription" else return_ann
     )
+    if batch:
+        output_ann = _batch_item_annotation(resolver_name, field_name, return_ann)
     type_spec = _type_spec_from_annotation(output_ann, expect_input=False)
+    if on_error not in ("stop", "skip", "emit"):
+        raise subscription_error_policy_invalid(resolver_name, on_error)
+    if on_error == "emit" and not type_spec.nullable:
+        raise subscription_emit_requires_nullable(resolver_name, field_name)
 
     refs = _collect_refs(return_ann, graphql_arg_params, hints)
 
@@ -214,4 +335,13 @@ def compile_resolver_field(
         description=description,
         args=tuple(args),
         refs=refs,
+        requires_role=requires_role,
+        batch=batch,
+        needs_info=bool(info_param_names),
+        on_error=on_error,
+        retries=retries,
+        backoff_ms=backoff_ms,
+        retry_on=retry_on,
+        serial=serial,
+        doc=_first_doc_line(resolver.__doc__),
     )
@@ -6,8 +6,10 @@ from ._annotations import get_annotations
 from ._compiled import (
     COMPILED_RESOLVER_ATTR,
     COMPILED_TYPE_ATTR,
+    EXTENSIONS_ATTR,
     META_ATTR,
     REFS_ATTR,
+    CompiledConstraints,
     CompiledDataField,
     CompiledInputField,
     CompiledResolverField,
@@ -22,7 +24,19 @@ from .annotations import (
     walk_annotation,
 )
 from .coercion import _input_field_default
-from .errors import GrommetTypeError, input_field_resolver_not_allowed
+from .errors import (
+    GrommetTypeError,
+    constraint_not_applicable,
+    default_literal_conflict,
+    extension_data_fields_not_allowed,
+    extension_resolver_kind_mismatch,
+    extension_target_invalid,
+    input_field_default_literal_not_allowed,
+    input_field_resolver_not_allowed,
+    interface_type_expected,
+    relay_node_requires_id,
+    required_input_deprecated,
+)
 from .metadata import MISSING, Field, TypeKind, TypeMeta
 
 if TYPE_CHECKING:
@@ -30,6 +44,16 @@ if TYPE_CHECKING:
     from collections.abc import Callable, Iterator
     from typing import Any
 
+    from .metadata import LazyRef, TypeSpec
+
+_DEFAULT_FIELD_META = Field()
+_NO_CONSTRAINTS = CompiledConstraints()
+_STRING_SCALARS = frozenset({"String"})
+_NUMERIC_SCALARS = frozenset({"Int", "Float"})
+_RELAY_ID_SCALARS = frozenset({"ID", "Int", "String"})
+# The reason the spec gives `@deprecated` when none is stated.
+_DEFAULT_DEPRECATION_REASON = "No longer supported"
+
 
 def _get_annotated_field_meta(annotation: "Any") -> Field | None:
     info = analyze_annotation(annotation)
@@ -39,6 +63,21 @@ def _get_annotated_field_meta(annotation: "Any") -> Field | None:
     return None
 
 
+def _first_doc_line(doc: str | None) -> str | None:
+    if not doc:
+        return None
+    lines = doc.strip().splitlines()
+    return lines[0].strip() if lines else None
+
+
+def _class_doc(cls: "pytype") -> str | None:
+    doc = vars(cls).get("__doc__")
+    # Dataclasses without a docstring are given their signature as one.
+    if isinstance(doc, str) and doc.startswith(f"{cls.__name__}("):
+        return None
+    return _first_doc_line(doc)
+
+
 def _data_field_resolver(
     field_name: str, *, has_default: bool, default: object | None
 ) -> "Callable[[Any, Any, dict[str, Any]], Any]":
@@ -96,9 +135,70 @@ def _implemented_interfaces(
     return tuple(dict.fromkeys(names)), tuple(dict.fromkeys(refs))
 
 
+def _compile_constraints(
+    target: str, field_meta: Field, type_spec: "TypeSpec"
+) -> CompiledConstraints | None:
+    """Collect declared input constraints, rejecting ones the value type cannot satisfy."""
+    constraints = CompiledConstraints(
+        min_length=field_meta.min_length,
+        max_length=field_meta.max_length,
+        minimum=field_meta.minimum,
+        maximum=field_meta.maximum,
+        pattern=field_meta.pattern,
+    )
+    if constraints == _NO_CONSTRAINTS:
+        return None
+
+    named = type_spec
+    while named.of_type is not None:
+        named = named.of_type
+    is_list = type_spec.kind == "list"
+    is_string = named.name in _STRING_SCALARS
+    is_numeric = named.name in _NUMERIC_SCALARS
+    applicable = {
+        "min_length": is_list or is_string,
+        "max_length": is_list or is_string,
+        "minimum": is_numeric,
+        "maximum": is_numeric,
+        "pattern": is_string,
+    }
+    for constraint, applies in applicable.items():
+        if getattr(constraints, constraint) is not None and not applies:
+            raise constraint_not_applicable(target, constraint, str(named.name))
+    return constraints
+
+
+def _deprecation_reason(field_meta: Field) -> str | None:
+    """Resolve the deprecation reason of a field, if it is deprecated."""
+    if field_meta.deprecated is False:
+        return None
+    if field_meta.deprecated is True:
+        return _DEFAULT_DEPRECATION_REASON
+    return field_meta.deprecated
+
+
+def _compile_deprecation(
+    target: str, field_meta: Field, type_spec: "TypeSpec", *, has_default: bool
+) -> str | None:
+    """Resolve the deprecation reason of an input, which must be optional."""
+    reason = _deprecation_reason(field_meta)
+    if reason is not None and not type_spec.nullable and not has_default:
+        raise required_input_deprecated(target)
+    return reason
+
+
+def _compile_default_literal(
+    target: str, field_meta: Field, *, has_default: bool
+) -> str | None:
+    """Resolve the GraphQL literal an argument defaults to, in place of a Python one."""
+    if field_meta.default_literal is not None and has_default:
+        raise default_literal_conflict(target)
+    return field_meta.default_literal
+
+
 def _iter_visible_dataclass_fields(
     cls: "pytype", hints: dict[str, "Any"]
-) -> "Iterator[tuple[dataclasses.Field[Any], Any, str | None, frozenset[pytype]]]":
+) -> "Iterator[tuple[dataclasses.Field[Any], Any, Field, frozenset[pytype | LazyRef]]]":
     """Yield visible dataclass fields with normalized metadata used by all compile modes."""
     for dc_field in dataclasses.fields(cls):
         annotation = hints.get(dc_field.name, dc_field.type)
@@ -106,13 +206,12 @@ def _iter_visible_dataclass_fields(
             continue
 
         refs = frozenset(walk_annotation(annotation))
-        field_meta = _get_annotated_field_meta(annotation)
-        description = field_meta.description if field_meta else None
-        yield dc_field, annotation, description, refs
+        field_meta = _get_annotated_field_meta(annotation) or _DEFAULT_FIELD_META
+        yield dc_field, annotation, field_meta, refs
 
 
 def _compile_subscription_fields(
-    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, str | None, frozenset[pytype]], ...]",
+    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, Field, frozenset[pytype | LazyRef]], ...]",
     subscription_resolvers: list[CompiledResolverField],
 ) -> tuple[CompiledResolverField, ...]:
     if visible_fields:
@@ -121,10 +220,10 @@ def _compile_subscription_fields(
 
 
 def _compile_input_fields(
-    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, str | None, frozenset[pytype]], ...]",
+    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, Field, frozenset[pytype | LazyRef]], ...]",
 ) -> tuple[CompiledInputField, ...]:
     fields: list[CompiledInputField] = []
-    for dc_field, annotation, desc, field_refs in visible_fields:
+    for dc_field, annotation, field_meta, field_refs in visible_fields:
         force_nullable = (
             dc_field.default is not MISSING or dc_field.default_factory is not MISSING
         )
@@ -133,25 +232,37 @@ def _compile_input_fields(
         )
         default_value = _input_field_default(dc_field, annotation)
         has_default = default_value is not MISSING
+        if field_meta.default_literal is not None:
+            raise input_field_default_literal_not_allowed(dc_field.name)
         fields.append(
             CompiledInputField(
                 name=dc_field.name,
                 type_spec=type_spec,
-                description=desc,
+                description=field_meta.description,
                 has_default=has_default,
                 default=default_value if has_default else None,
                 refs=field_refs,
+                requires_role=field_meta.requires_role,
+                constraints=_compile_constraints(
+                    f"field '{dc_field.name}'", field_meta, type_spec
+                ),
+                deprecation=_compile_deprecation(
+                    f"field '{dc_field.name}'",
+                    field_meta,
+                    type_spec,
+                    has_default=has_default,
+                ),
             )
         )
     return tuple(fields)
 
 
 def _compile_object_fields(
-    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, str | None, frozenset[pytype]], ...]",
+    visible_fields: "tuple[tuple[dataclasses.Field[Any], Any, Field, frozenset[pytype | LazyRef]], ...]",
     field_resolvers: list[CompiledResolverField],
 ) -> tuple[CompiledDataField | CompiledResolverField, ...]:
     fields: list[CompiledDataField | CompiledResolverField] = []
-    for dc_field, annotation, desc, field_refs in visible_fields:
+    for dc_field, annotation, field_meta, field_refs in visible_fields:
         type_spec = _type_spec_from_annotation(
             annotation, expect_input=False, force_nullable=dc_field.default is None
         )
@@ -160,21 +271,41 @@ def _compile_object_fields(
             CompiledDataField(
                 name=dc_field.name,
                 type_spec=type_spec,
-                description=desc,
+                description=field_meta.description,
                 has_default=has_default,
                 default=default,
                 resolver_func=_data_field_resolver(
                     dc_field.name, has_default=has_default, default=default
                 ),
                 refs=field_refs,
+                requires_role=field_meta.requires_role,
+                deprecation=_deprecation_reason(field_meta),
             )
         )
     fields.extend(field_resolvers)
     return tuple(fields)
 
 
+def _has_scalar_id(
+    fields: "tuple[CompiledDataField | CompiledResolverField, ...]",
+) -> bool:
+    return any(
+        field.name == "id"
+        and field.type_spec.kind == "named"
+        and field.type_spec.name in _RELAY_ID_SCALARS
+        and not field.type_spec.nullable
+        for field in fields
+    )
+
+
 def compile_type_definition(
-    cls: "pytype", *, kind: TypeKind, name: str | None, description: str | None
+    cls: "pytype",
+    *,
+    kind: TypeKind,
+    name: str | None,
+    description: str | None,
+    requires_role: str | None = None,
+    relay_node: bool = False,
 ) -> CompiledType:
     """Compile a decorated class into immutable metadata used at schema build time."""
     type_name = name or cls.__name__
@@ -212,7 +343,7 @@ def compile_type_definition(
         if resolver.name not in visible_field_names
     ]
 
-    refs: list[pytype] = []
+    refs: list[pytype | LazyRef] = []
     for _dc_field, _annotation, _desc, field_refs in visible_fields:
         refs.extend(field_refs)
 
@@ -234,6 +365,9 @@ def compile_type_definition(
     else:
         object_fields = _compile_object_fields(visible_fields, field_resolvers)
 
+    if relay_node and not _has_scalar_id(object_fields):
+        raise relay_node_requires_id(type_name)
+
     resolver_ref_sources = (
         tuple(field_resolvers)
         if resolved_kind in {TypeKind.OBJECT, TypeKind.INTERFACE}
@@ -242,7 +376,14 @@ def compile_type_definition(
     for resolver in resolver_ref_sources:
         refs.extend(resolver.refs)
 
-    meta = TypeMeta(kind=resolved_kind, name=type_name, description=description)
+    meta = TypeMeta(
+        kind=resolved_kind,
+        name=type_name,
+        description=description,
+        requires_role=requires_role,
+        relay_node=relay_node,
+        doc=_class_doc(cls),
+    )
     compiled = CompiledType(
         meta=meta,
         object_fields=object_fields,
@@ -257,3 +398,44 @@ def compile_type_definition(
     setattr(cls, COMPILED_TYPE_ATTR, compiled)
 
     return compiled
+
+
+def compile_type_extension(
+    cls: "pytype", *, target: "pytype", implements: "tuple[pytype, ...]"
+) -> CompiledType:
+    """Compile a class contributing resolvers and interfaces to an existing type."""
+    meta = _get_type_meta(target)
+    if meta.kind not in {TypeKind.OBJECT, TypeKind.SUBSCRIPTION}:
+        raise extension_target_invalid(meta.name)
+    if dataclasses.is_dataclass(cls) and dataclasses.fields(cls):
+        raise extension_data_fields_not_allowed(cls.__name__)
+
+    resolvers = _iter_compiled_resolvers(cls)
+    expected_kind = "subscription" if meta.kind is TypeKind.SUBSCRIPTION else "field"
+    if any(resolver.kind != expected_kind for resolver in resolvers):
+        raise extension_resolver_kind_mismatch(
+            cls.__name__, meta.name, f"@grommet.{expected_kind}"
+        )
+
+    refs: list[pytype | LazyRef] = []
+    for resolver in resolvers:
+        refs.extend(resolver.refs)
+    interface_names: list[str] = []
+    for interface in implements:
+        interface_meta = _get_type_meta(interface)
+        if interface_meta.kind is not TypeKind.INTERFACE:
+            raise interface_type_expected(interface_meta.name)
+        interface_names.append(interface_meta.name)
+        refs.append(interface)
+
+    is_subscription = meta.kind is TypeKind.SUBSCRIPTION
+    compiled = CompiledType(
+        meta=meta,
+        object_fields=() if is_subscription else tuple(resolvers),
+        subscription_fields=tuple(resolvers) if is_subscription else (),
+        implements=tuple(interface_names),
+        refs=frozenset(refs),
+    )
+    extensions: tuple[CompiledType, ...] = vars(target).get(EXTENSIONS_ATTR, ())
+    setattr(target, EXTENSIONS_ATTR, (*extensions, compiled))
+    return compiled
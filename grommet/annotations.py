@@ -5,6 +5,7 @@ from typing import (
     TYPE_CHECKING,
     Annotated,
     ClassVar,
+    ForwardRef,
     TypeAliasType,
     Union,
     get_args,
@@ -14,6 +15,7 @@ from typing import (
 from .errors import (
     async_iterable_requires_parameter,
     input_type_expected,
+    lazy_input_not_supported,
     list_type_requires_parameter,
     not_grommet_type,
     output_type_expected,
@@ -21,7 +23,16 @@ from .errors import (
     union_member_must_be_object,
     unsupported_annotation,
 )
-from .metadata import _SCALARS, Context, Hidden, TypeKind, TypeMeta, TypeSpec
+from .metadata import (
+    _SCALARS,
+    Context,
+    Hidden,
+    Lazy,
+    LazyRef,
+    TypeKind,
+    TypeMeta,
+    TypeSpec,
+)
 from .metadata import Union as UnionMetadata
 
 if TYPE_CHECKING:
@@ -99,6 +110,11 @@ def analyze_annotation(annotation: "Any") -> AnnotationInfo:
             metadata = args[1:]
     inner, optional = _split_optional(inner)
     inner = _unwrap_type_alias(inner)
+    if get_origin(inner) is Annotated:
+        args = get_args(inner)
+        if args:
+            inner = _unwrap_type_alias(args[0])
+            metadata = (*metadata, *args[1:])
     origin = get_origin(inner)
     args = get_args(inner)
     is_list = origin is list
@@ -153,18 +169,31 @@ def is_hidden_field(attr_name: str, annotation: "Any") -> bool:
     return info.is_hidden or info.is_classvar
 
 
-def walk_annotation(annotation: "Any") -> "Iterator[pytype]":
+def _lazy_ref(info: AnnotationInfo) -> LazyRef | None:
+    if not isinstance(info.inner, ForwardRef):
+        return None
+    for item in info.metadata:
+        if isinstance(item, Lazy):
+            return LazyRef(name=info.inner.__forward_arg__, module=item.module)
+    return None
+
+
+def walk_annotation(annotation: "Any") -> "Iterator[pytype | LazyRef]":
     """Yields grommet types referenced in an annotation."""
     info = analyze_annotation(annotation)
     if info.is_context:
         return
+    lazy_ref = _lazy_ref(info)
+    if lazy_ref is not None:
+        yield lazy_ref
+        return
     inner = info.async_item if info.is_async_iterable else info.inner
     if inner is None:
         return
     yield from _walk_inner(inner)
 
 
-def _walk_inner(inner: "Any") -> "Iterator[pytype]":
+def _walk_inner(inner: "Any") -> "Iterator[pytype | LazyRef]":
     """Recursively walk an unwrapped inner type."""
     info = analyze_annotation(inner)
     if info.is_context:
@@ -180,7 +209,10 @@ def _walk_inner(inner: "Any") -> "Iterator[pytype]":
             yield from _walk_inner(member)
         return
 
-    if _is_grommet_type(info.inner):
+    lazy_ref = _lazy_ref(info)
+    if lazy_ref is not None:
+        yield lazy_ref
+    elif _is_grommet_type(info.inner):
         yield info.inner
 
 
@@ -211,6 +243,11 @@ def _type_spec_from_annotation(
     )
     if union_spec is not None:
         return union_spec
+    lazy_ref = _lazy_ref(info)
+    if lazy_ref is not None:
+        if expect_input:
+            raise lazy_input_not_supported(lazy_ref.name)
+        return TypeSpec(kind="named", name=lazy_ref.name, nullable=nullable)
     if inner in _SCALARS:
         return TypeSpec(kind="named", name=_SCALARS[inner], nullable=nullable)
     if _is_grommet_type(inner):
@@ -10,6 +10,20 @@ if TYPE_CHECKING:
     from typing import Any
 
 
+def _is_pydantic_model(value: "Any") -> bool:
+    """Whether the value is a Pydantic model instance, of either major version."""
+    return any(
+        base.__name__ == "BaseModel"
+        and base.__module__.partition(".")[0] == "pydantic"
+        for base in type(value).__mro__
+    )
+
+
+def _pydantic_dump(value: "Any") -> "dict[str, Any]":
+    dump = getattr(value, "model_dump", None) or value.dict
+    return dump()
+
+
 def _default_value_for_annotation(annotation: "Any", default: "Any") -> "Any":
     if default is MISSING:
         return default
@@ -27,6 +41,8 @@ def _default_value_for_annotation(annotation: "Any", default: "Any") -> "Any":
     if _is_input_type(inner):
         if isinstance(default, inner):
             return dataclasses.asdict(default)
+        if _is_pydantic_model(default):
+            return _pydantic_dump(default)
         if isinstance(default, dict):
             return default
     return default
@@ -67,4 +83,6 @@ def _coerce_input(value: "Any", cls: type) -> "Any":
         return value
     if isinstance(value, dict):
         return cls(**value)
+    if _is_pydantic_model(value):
+        return cls(**_pydantic_dump(value))
     raise input_mapping_expected(cls.__name__)
@@ -3,7 +3,7 @@ from typing import TYPE_CHECKING, ParamSpec, TypeVar, overload
 
 from ._compiled import COMPILED_RESOLVER_ATTR, REFS_ATTR
 from ._resolver_compiler import compile_resolver_field
-from ._type_compiler import compile_type_definition
+from ._type_compiler import compile_type_definition, compile_type_extension
 from .errors import GrommetTypeError, dataclass_required, decorator_requires_callable
 from .metadata import TypeKind
 
@@ -12,28 +12,51 @@ R = TypeVar("R")
 
 if TYPE_CHECKING:
     from builtins import type as pytype
-    from collections.abc import Callable
-    from typing import Any
+    from collections.abc import Callable, Sequence
+    from typing import Any, Literal
 
 
 def _compile_decorated_type(
-    target: "pytype", *, kind: TypeKind, name: str | None, description: str | None
+    target: "pytype",
+    *,
+    kind: TypeKind,
+    name: str | None,
+    description: str | None,
+    requires_role: str | None,
+    relay_node: bool = False,
 ) -> "pytype":
     if not dataclasses.is_dataclass(target):
         raise dataclass_required(f"@grommet.{kind.value}")
-    compile_type_definition(target, kind=kind, name=name, description=description)
+    compile_type_definition(
+        target,
+        kind=kind,
+        name=name,
+        description=description,
+        requires_role=requires_role,
+        relay_node=relay_node,
+    )
     return target
 
 
 @overload
 def type(
-    cls: "pytype", *, name: str | None = None, description: str | None = None
+    cls: "pytype",
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
+    relay_node: bool = False,
 ) -> "pytype": ...
 
 
 @overload
 def type(
-    cls: None = None, *, name: str | None = None, description: str | None = None
+    cls: None = None,
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
+    relay_node: bool = False,
 ) -> "Callable[[pytype], pytype]": ...
 
 
@@ -42,12 +65,19 @@ def type(
     *,
     name: str | None = None,
     description: str | None = None,
+    requires_role: str | None = None,
+    relay_node: bool = False,
 ) -> "Callable[[pytype], pytype] | pytype":
     """Marks a dataclass as a GraphQL object type."""
 
     def wrap(target: "pytype") -> "pytype":
         return _compile_decorated_type(
-            target, kind=TypeKind.OBJECT, name=name, description=description
+            target,
+            kind=TypeKind.OBJECT,
+            name=name,
+            description=description,
+            requires_role=requires_role,
+            relay_node=relay_node,
         )
 
     if cls is None:
@@ -55,15 +85,35 @@ def type(
     return wrap(cls)
 
 
+def extend(
+    target: "pytype", *, implements: "tuple[pytype, ...]" = ()
+) -> "Callable[[pytype], pytype]":
+    """Adds the decorated class's resolvers and given interfaces to an existing type."""
+
+    def wrap(extension: "pytype") -> "pytype":
+        compile_type_extension(extension, target=target, implements=implements)
+        return extension
+
+    return wrap
+
+
 @overload
 def input(
-    cls: "pytype", *, name: str | None = None, description: str | None = None
+    cls: "pytype",
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
 ) -> "pytype": ...
 
 
 @overload
 def input(
-    cls: None = None, *, name: str | None = None, description: str | None = None
+    cls: None = None,
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
 ) -> "Callable[[pytype], pytype]": ...
 
 
@@ -72,12 +122,17 @@ def input(
     *,
     name: str | None = None,
     description: str | None = None,
+    requires_role: str | None = None,
 ) -> "Callable[[pytype], pytype] | pytype":
     """Marks a dataclass as a GraphQL input type."""
 
     def wrap(target: "pytype") -> "pytype":
         return _compile_decorated_type(
-            target, kind=TypeKind.INPUT, name=name, description=description
+            target,
+            kind=TypeKind.INPUT,
+            name=name,
+            description=description,
+            requires_role=requires_role,
         )
 
     if cls is None:
@@ -87,13 +142,21 @@ def input(
 
 @overload
 def interface(
-    cls: "pytype", *, name: str | None = None, description: str | None = None
+    cls: "pytype",
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
 ) -> "pytype": ...
 
 
 @overload
 def interface(
-    cls: None = None, *, name: str | None = None, description: str | None = None
+    cls: None = None,
+    *,
+    name: str | None = None,
+    description: str | None = None,
+    requires_role: str | None = None,
 ) -> "Callable[[pytype], pytype]": ...
 
 
@@ -102,12 +165,17 @@ def interface(
     *,
     name: str | None = None,
     description: str | None = None,
+    requires_role: str | None = None,
 ) -> "Callable[[pytype], pytype] | pytype":
     """Marks a dataclass as a GraphQL interface type."""
 
     def wrap(target: "pytype") -> "pytype":
         return _compile_decorated_type(
-            target, kind=TypeKind.INTERFACE, name=name, description=description
+            target,
+            kind=TypeKind.INTERFACE,
+            name=name,
+            description=description,
+            requires_role=requires_role,
         )
 
     if cls is None:
@@ -117,13 +185,31 @@ def interface(
 
 @overload
 def field(
-    func: "Callable[P, R]", *, description: str | None = None, name: str | None = None
+    func: "Callable[P, R]",
+    *,
+    description: str | None = None,
+    name: str | None = None,
+    requires_role: str | None = None,
+    batch: bool = False,
+    retries: int = 0,
+    backoff_ms: int = 0,
+    retry_on: "Sequence[str]" = (),
+    serial: bool = False,
 ) -> "Callable[P, R]": ...
 
 
 @overload
 def field(
-    func: None = None, *, description: str | None = None, name: str | None = None
+    func: None = None,
+    *,
+    description: str | None = None,
+    name: str | None = None,
+    requires_role: str | None = None,
+    batch: bool = False,
+    retries: int = 0,
+    backoff_ms: int = 0,
+    retry_on: "Sequence[str]" = (),
+    serial: bool = False,
 ) -> "Callable[[Callable[P, R]], Callable[P, R]]": ...
 
 
@@ -132,6 +218,12 @@ def field(
     *,
     description: str | None = None,
     name: str | None = None,
+    requires_role: str | None = None,
+    batch: bool = False,
+    retries: int = 0,
+    backoff_ms: int = 0,
+    retry_on: "Sequence[str]" = (),
+    serial: bool = False,
 ) -> "Callable[..., Any]":
     """Declares a resolver-backed field on a GraphQL type."""
 
@@ -146,7 +238,16 @@ def field(
 
         field_name = name or target.__name__
         compiled = compile_resolver_field(
-            target, field_name=field_name, description=description, kind="field"
+            target,
+            field_name=field_name,
+            description=description,
+            kind="field",
+            requires_role=requires_role,
+            batch=batch,
+            retries=retries,
+            backoff_ms=backoff_ms,
+            retry_on=tuple(retry_on),
+            serial=serial,
         )
         setattr(target, COMPILED_RESOLVER_ATTR, compiled)
         setattr(target, REFS_ATTR, compiled.refs)
@@ -159,13 +260,23 @@ def field(
 
 @overload
 def subscription(
-    func: "Callable[P, R]", *, description: str | None = None, name: str | None = None
+    func: "Callable[P, R]",
+    *,
+    description: str | None = None,
+    name: str | None = None,
+    requires_role: str | None = None,
+    on_error: "Literal['stop', 'skip', 'emit']" = "stop",
 ) -> "Callable[P, R]": ...
 
 
 @overload
 def subscription(
-    func: None = None, *, description: str | None = None, name: str | None = None
+    func: None = None,
+    *,
+    description: str | None = None,
+    name: str | None = None,
+    requires_role: str | None = None,
+    on_error: "Literal['stop', 'skip', 'emit']" = "stop",
 ) -> "Callable[[Callable[P, R]], Callable[P, R]]": ...
 
 
@@ -174,6 +285,8 @@ def subscription(
     *,
     description: str | None = None,
     name: str | None = None,
+    requires_role: str | None = None,
+    on_error: "Literal['stop', 'skip', 'emit']" = "stop",
 ) -> "Callable[..., Any]":
     """Declares a subscription resolver field on a GraphQL type."""
 
@@ -183,7 +296,12 @@ def subscription(
 
         field_name = name or target.__name__
         compiled = compile_resolver_field(
-            target, field_name=field_name, description=description, kind="subscription"
+            target,
+            field_name=field_name,
+            description=description,
+            kind="subscription",
+            requires_role=requires_role,
+            on_error=on_error,
         )
         setattr(target, COMPILED_RESOLVER_ATTR, compiled)
         setattr(target, REFS_ATTR, compiled.refs)
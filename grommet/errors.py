@@ -1,13 +1,11 @@
 from typing import TYPE_CHECKING
 
+from ._core import GrommetError
+
 if TYPE_CHECKING:
     from typing import Any
 
 
-class GrommetError(Exception):
-    """Base exception for grommet errors."""
-
-
 class GrommetTypeError(TypeError, GrommetError):
     """Raised when grommet encounters an invalid type or annotation."""
 
@@ -43,6 +41,65 @@ def resolver_requires_async(resolver_name: str, field_name: str) -> GrommetTypeE
     )
 
 
+def subscription_error_policy_invalid(
+    resolver_name: str, policy: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Subscription resolver {resolver_name} has on_error={policy!r}; "
+        "expected 'stop', 'skip', or 'emit'."
+    )
+
+
+def subscription_emit_requires_nullable(
+    resolver_name: str, field_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Subscription resolver {resolver_name} for field '{field_name}' must return "
+        "a nullable type to use on_error='emit'."
+    )
+
+
+def batch_resolver_requires_list(
+    resolver_name: str, field_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Batch resolver {resolver_name} for field '{field_name}' must return a list."
+    )
+
+
+def batch_resolver_info_not_supported(
+    resolver_name: str, field_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Batch resolver {resolver_name} for field '{field_name}' cannot take Info."
+    )
+
+
+def batch_resolver_retry_not_supported(
+    resolver_name: str, field_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Batch resolver {resolver_name} for field '{field_name}' cannot retry."
+    )
+
+
+def batch_resolver_serial_not_supported(
+    resolver_name: str, field_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Batch resolver {resolver_name} for field '{field_name}' cannot be serial."
+    )
+
+
+def retry_policy_invalid(
+    resolver_name: str, retries: int, backoff_ms: int
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Resolver {resolver_name} has retries={retries} and "
+        f"backoff_ms={backoff_ms}; both must be non-negative."
+    )
+
+
 def input_type_expected(type_name: str) -> GrommetTypeError:
     return GrommetTypeError(f"{type_name} is not an input type")
 
@@ -92,3 +149,102 @@ def union_definition_conflict(union_name: str) -> GrommetTypeError:
     return GrommetTypeError(
         f"Union '{union_name}' has conflicting definitions across the schema graph."
     )
+
+
+def union_member_not_registered(union_name: str, member_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Union '{union_name}' member '{member_name}' is not a registered object type."
+    )
+
+
+def interface_not_implemented(
+    type_name: str, interface_name: str, problems: "list[str]"
+) -> GrommetTypeError:
+    details = "; ".join(problems)
+    return GrommetTypeError(
+        f"Type '{type_name}' does not correctly implement interface "
+        f"'{interface_name}': {details}."
+    )
+
+
+def constraint_not_applicable(
+    target: str, constraint: str, type_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Constraint '{constraint}' on {target} does not apply to type '{type_name}'."
+    )
+
+
+def required_input_deprecated(target: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Required {target} cannot be deprecated; make it nullable or give it a "
+        "default."
+    )
+
+
+def default_literal_conflict(target: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"{target.capitalize()} has both a default and a default_literal; "
+        "give it only one."
+    )
+
+
+def input_field_default_literal_not_allowed(field_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Input field '{field_name}' cannot have a default_literal; "
+        "give it a dataclass default instead."
+    )
+
+
+def relay_node_requires_id(type_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Relay node type '{type_name}' must declare a non-null scalar 'id' field."
+    )
+
+
+def interface_type_expected(type_name: str) -> GrommetTypeError:
+    return GrommetTypeError(f"{type_name} is not an interface type")
+
+
+def extension_target_invalid(type_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Only object and subscription types can be extended; '{type_name}' is neither."
+    )
+
+
+def extension_data_fields_not_allowed(extension_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Type extension {extension_name} can only declare resolver fields."
+    )
+
+
+def extension_resolver_kind_mismatch(
+    extension_name: str, type_name: str, decorator_name: str
+) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Type extension {extension_name} for '{type_name}' must use "
+        f"{decorator_name} resolvers."
+    )
+
+
+def extension_field_conflict(type_name: str, field_name: str) -> GrommetTypeError:
+    return GrommetTypeError(f"Type '{type_name}' already defines field '{field_name}'.")
+
+
+def mutation_root_not_object(type_name: str, kind: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Mutation root '{type_name}' must be an object type, not {kind} type."
+    )
+
+
+def lazy_input_not_supported(type_name: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Lazy reference '{type_name}' cannot be used as an input type."
+    )
+
+
+def lazy_type_unresolved(type_name: str, module: str) -> GrommetTypeError:
+    return GrommetTypeError(
+        f"Lazy reference '{type_name}' does not resolve to a grommet type named "
+        f"'{type_name}' in module '{module}'."
+    )
@@ -1,5 +1,10 @@
 import dataclasses
 import enum
+from typing import TYPE_CHECKING, NewType
+
+if TYPE_CHECKING:
+    from collections.abc import Callable
+    from typing import Any
 
 MISSING = dataclasses.MISSING
 
@@ -9,12 +14,24 @@ NO_DEFAULT: object = object()
 Context = object()
 Hidden = object()
 
+ID = NewType("ID", str)
+"""The GraphQL `ID` scalar: resolvers receive `ID` arguments as strings."""
+
 
 @dataclasses.dataclass(frozen=True, slots=True)
 class Field:
     """Annotated metadata providing field-level GraphQL configuration."""
 
     description: str | None = None
+    requires_role: str | None = None
+    min_length: int | None = None
+    max_length: int | None = None
+    minimum: float | None = None
+    maximum: float | None = None
+    pattern: str | None = None
+    validate: "Callable[[Any], object] | None" = None
+    deprecated: bool | str = False
+    default_literal: str | None = None
 
 
 @dataclasses.dataclass(frozen=True, slots=True)
@@ -25,6 +42,24 @@ class Union:
     description: str | None = None
 
 
+@dataclasses.dataclass(frozen=True, slots=True)
+class Lazy:
+    """
+    Annotated metadata deferring a string type reference until the schema is built,
+    importing the type from `module` then.
+    """
+
+    module: str
+
+
+@dataclasses.dataclass(frozen=True, slots=True)
+class LazyRef:
+    """Reference to a type that is imported from its module at schema build time."""
+
+    name: str
+    module: str
+
+
 class TypeKind(enum.Enum):
     OBJECT = "object"
     INPUT = "input"
@@ -38,6 +73,10 @@ class TypeMeta:
     kind: TypeKind
     name: str
     description: str | None = None
+    requires_role: str | None = None
+    relay_node: bool = False
+    # First line of the class docstring, the description under `docstring_descriptions`.
+    doc: str | None = None
 
 
 @dataclasses.dataclass(frozen=True, slots=True)
@@ -59,4 +98,9 @@ class ArgPlan:
     default: object = NO_DEFAULT
 
 
-_SCALARS = {str: "String", int: "Int", float: "Float", bool: "Boolean"}
+_SCALARS = {str: "String", int: "Int", float: "Float", bool: "Boolean", ID: "ID"}
+
+
+def scalar_bindings() -> dict[object, str]:
+    """Returns builtin GraphQL scalar names keyed by their bound Python types."""
+    return dict(_SCALARS)
@@ -1,8 +1,10 @@
-from dataclasses import dataclass
-from typing import TYPE_CHECKING
+from dataclasses import dataclass, replace
+from importlib import import_module
+from typing import TYPE_CHECKING, cast
 
 from ._compiled import (
     COMPILED_TYPE_ATTR,
+    EXTENSIONS_ATTR,
     REFS_ATTR,
     CompiledDataField,
     CompiledResolverField,
@@ -10,13 +12,22 @@ from ._compiled import (
     CompiledUnion,
 )
 from .annotations import _get_type_meta, _is_grommet_type
-from .errors import GrommetTypeError, union_definition_conflict
-from .metadata import TypeKind, TypeMeta
+from .errors import (
+    GrommetTypeError,
+    extension_field_conflict,
+    interface_not_implemented,
+    lazy_type_unresolved,
+    mutation_root_not_object,
+    union_definition_conflict,
+    union_member_not_registered,
+)
+from .metadata import LazyRef, TypeKind, TypeMeta
 
 if TYPE_CHECKING:
     from builtins import type as pytype
     from collections.abc import Iterator
 
+    from ._compiled import CompiledArg
     from .metadata import TypeSpec
 
 
@@ -37,6 +48,8 @@ def build_schema_graph(
     subscription: "pytype | None" = None,
 ) -> SchemaBundle:
     """Build schema graph using precompiled class metadata only."""
+    if mutation is not None:
+        _validate_mutation_root(mutation)
     for root in (query, mutation, subscription):
         if root is None:
             continue
@@ -44,10 +57,11 @@ def build_schema_graph(
 
     collected_classes = _walk_and_collect(query, mutation, subscription)
     compiled_types = [_get_compiled_type(cls) for cls in collected_classes]
-    types: list[CompiledType | CompiledUnion] = [
-        *compiled_types,
-        *_collect_compiled_unions(compiled_types),
-    ]
+    compiled_unions = _collect_compiled_unions(compiled_types)
+    types_by_name = {compiled.meta.name: compiled for compiled in compiled_types}
+    _validate_union_members(compiled_unions, types_by_name)
+    _validate_interface_implementations(types_by_name)
+    types: list[CompiledType | CompiledUnion] = [*compiled_types, *compiled_unions]
 
     return SchemaBundle(
         query=_get_type_meta(query).name,
@@ -60,10 +74,43 @@ def build_schema_graph(
 def _get_compiled_type(cls: "pytype") -> CompiledType:
     compiled = getattr(cls, COMPILED_TYPE_ATTR, None)
     if isinstance(compiled, CompiledType):
-        return compiled
+        return _apply_extensions(compiled, _get_extensions(cls))
     raise GrommetTypeError(f"{cls.__name__} is missing compiled grommet type metadata.")
 
 
+def _get_extensions(cls: "pytype") -> tuple[CompiledType, ...]:
+    return cast("tuple[CompiledType, ...]", vars(cls).get(EXTENSIONS_ATTR, ()))
+
+
+def _apply_extensions(
+    compiled: CompiledType, extensions: tuple[CompiledType, ...]
+) -> CompiledType:
+    """Merge fields and interfaces contributed by `@grommet.extend` into a type."""
+    if not extensions:
+        return compiled
+    object_fields = list(compiled.object_fields)
+    subscription_fields = list(compiled.subscription_fields)
+    field_names = {field.name for field in (*object_fields, *subscription_fields)}
+    implements = list(compiled.implements)
+    refs = set(compiled.refs)
+    for extension in extensions:
+        for field in (*extension.object_fields, *extension.subscription_fields):
+            if field.name in field_names:
+                raise extension_field_conflict(compiled.meta.name, field.name)
+            field_names.add(field.name)
+        object_fields.extend(extension.object_fields)
+        subscription_fields.extend(extension.subscription_fields)
+        implements.extend(extension.implements)
+        refs.update(extension.refs)
+    return replace(
+        compiled,
+        object_fields=tuple(object_fields),
+        subscription_fields=tuple(subscription_fields),
+        implements=tuple(dict.fromkeys(implements)),
+        refs=frozenset(refs),
+    )
+
+
 def _walk_and_collect(
     query: "pytype", mutation: "pytype | None", subscription: "pytype | None"
 ) -> list["pytype"]:
@@ -89,8 +136,11 @@ def _walk_and_collect(
                 if implementer not in visited:
                     pending.append(implementer)
 
-        refs: frozenset[pytype] = getattr(cls, REFS_ATTR, frozenset())
-        for ref_cls in sorted(refs, key=_class_sort_key):
+        refs: frozenset[pytype | LazyRef] = getattr(cls, REFS_ATTR, frozenset())
+        for extension in _get_extensions(cls):
+            refs |= extension.refs
+        for ref in sorted(refs, key=_ref_sort_key):
+            ref_cls = _resolve_lazy_ref(ref) if isinstance(ref, LazyRef) else ref
             if ref_cls not in visited:
                 pending.append(ref_cls)
 
@@ -101,6 +151,19 @@ def _class_sort_key(cls: "pytype") -> str:
     return f"{cls.__module__}.{cls.__qualname__}"
 
 
+def _ref_sort_key(ref: "pytype | LazyRef") -> str:
+    if isinstance(ref, LazyRef):
+        return f"{ref.module}.{ref.name}"
+    return _class_sort_key(ref)
+
+
+def _resolve_lazy_ref(ref: LazyRef) -> "pytype":
+    cls = getattr(import_module(ref.module), ref.name, None)
+    if not _is_grommet_type(cls) or _get_type_meta(cls).name != ref.name:
+        raise lazy_type_unresolved(ref.name, ref.module)
+    return cast("pytype", cls)
+
+
 def _iter_interface_implementers(interface_cls: "pytype") -> "Iterator[pytype]":
     pending = sorted(interface_cls.__subclasses__(), key=_class_sort_key, reverse=True)
     seen: set[pytype] = set()
@@ -171,6 +234,106 @@ def _iter_union_type_specs(type_spec: "TypeSpec") -> "Iterator[TypeSpec]":
         yield from _iter_union_type_specs(type_spec.of_type)
 
 
+def _validate_union_members(
+    unions: list[CompiledUnion], types_by_name: dict[str, CompiledType]
+) -> None:
+    for union in unions:
+        for member in union.possible_types:
+            member_type = types_by_name.get(member)
+            if member_type is None or member_type.meta.kind is not TypeKind.OBJECT:
+                raise union_member_not_registered(union.meta.name, member)
+
+
+def _validate_interface_implementations(
+    types_by_name: dict[str, CompiledType],
+) -> None:
+    for compiled_type in types_by_name.values():
+        for interface_name in compiled_type.implements:
+            interface = types_by_name.get(interface_name)
+            if interface is None or interface.meta.kind is not TypeKind.INTERFACE:
+                problems = [f"'{interface_name}' is not a registered interface"]
+            else:
+                problems = list(
+                    _iter_interface_problems(compiled_type, interface, types_by_name)
+                )
+            if problems:
+                raise interface_not_implemented(
+                    compiled_type.meta.name, interface_name, problems
+                )
+
+
+def _iter_interface_problems(
+    compiled_type: CompiledType,
+    interface: CompiledType,
+    types_by_name: dict[str, CompiledType],
+) -> "Iterator[str]":
+    fields = {field.name: field for field in compiled_type.object_fields}
+    for interface_field in interface.object_fields:
+        field = fields.get(interface_field.name)
+        if field is None:
+            yield f"missing field '{interface_field.name}'"
+            continue
+        if not _is_type_spec_subtype(
+            field.type_spec, interface_field.type_spec, types_by_name
+        ):
+            yield (
+                f"field '{field.name}' has type {_render_type_spec(field.type_spec)}, "
+                f"expected {_render_type_spec(interface_field.type_spec)}"
+            )
+        args = {arg.name: arg for arg in _field_args(field)}
+        for interface_arg in _field_args(interface_field):
+            arg = args.get(interface_arg.name)
+            if arg is None:
+                yield f"field '{field.name}' is missing argument '{interface_arg.name}'"
+            elif arg.type_spec != interface_arg.type_spec:
+                yield (
+                    f"field '{field.name}' argument '{arg.name}' has type "
+                    f"{_render_type_spec(arg.type_spec)}, "
+                    f"expected {_render_type_spec(interface_arg.type_spec)}"
+                )
+
+
+def _field_args(
+    field: CompiledDataField | CompiledResolverField,
+) -> "tuple[CompiledArg, ...]":
+    if isinstance(field, CompiledResolverField):
+        return field.args
+    return ()
+
+
+def _is_type_spec_subtype(
+    spec: "TypeSpec", expected: "TypeSpec", types_by_name: dict[str, CompiledType]
+) -> bool:
+    """Checks output type covariance as required for interface field implementations."""
+    if spec.nullable and not expected.nullable:
+        return False
+    if spec.kind == "list" or expected.kind == "list":
+        if spec.of_type is None or expected.of_type is None:
+            return False
+        return _is_type_spec_subtype(spec.of_type, expected.of_type, types_by_name)
+    if spec.name == expected.name:
+        return True
+    if expected.kind == "union":
+        return spec.name in expected.union_members
+    candidate = types_by_name.get(spec.name or "")
+    return candidate is not None and expected.name in candidate.implements
+
+
+def _render_type_spec(spec: "TypeSpec") -> str:
+    if spec.of_type is not None:
+        rendered = f"[{_render_type_spec(spec.of_type)}]"
+    else:
+        rendered = str(spec.name)
+    return rendered if spec.nullable else f"{rendered}!"
+
+
+def _validate_mutation_root(mutation: "pytype") -> None:
+    """Rejects mutation roots that are not object types."""
+    compiled = _get_compiled_type(mutation)
+    if compiled.meta.kind is not TypeKind.OBJECT:
+        raise mutation_root_not_object(compiled.meta.name, compiled.meta.kind.value)
+
+
 def _validate_root_defaults(root: "pytype") -> None:
     compiled = _get_compiled_type(root)
     for field in compiled.object_fields:
@@ -1,150 +1,937 @@
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
 
+use async_graphql::Request;
 use async_graphql::dynamic::Schema;
-use async_graphql::futures_util::lock::Mutex;
-use async_graphql::futures_util::stream::{BoxStream, StreamExt};
-use async_graphql::parser::{parse_query, types::OperationType};
-use async_graphql::{Request, Variables};
-use pyo3::exceptions::PyStopAsyncIteration;
+use async_graphql::parser::parse_query;
+use async_graphql::registry::MetaType;
 use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyTuple, PyType};
 
-use crate::schema_types::register_schema;
-use crate::types::{ContextValue, PyObj};
-use crate::values::{py_to_value, response_to_py};
+use crate::capture::CallCapture;
+use crate::check::{operation_shape, single_operation, variable_errors};
+use crate::codegen::{TypeStyle, generate_types};
+use crate::document::root_type_name;
+use crate::errors::{
+    invalid_idempotency_ttl, invalid_max_value_depth, invalid_remote_timeout, not_picklable,
+    py_value_error, shadow_schema_cycle, unknown_reloaded_field, unknown_reloaded_type,
+    unknown_source_kind, validation_failed,
+};
+use crate::explain::explain_operation;
+use crate::ids::{IdInputs, TypedIds};
+use crate::introspection::{IntrospectionTrim, introspection_json};
+use crate::limits::{QuerySource, RequestLimits};
+use crate::options::SchemaOptions;
+use crate::rate_limit::RateLimits;
+use crate::reflection::{python_resolver_keys, schema_types, type_fields, type_registries};
+use crate::relay::NODE_INTERFACE;
+use crate::remote::RemoteExecutor;
+use crate::request::VariablesSource;
+use crate::schema_types::{SdlHeader, register_schema, schema_sdl};
+use crate::shadow::ShadowTraffic;
+use crate::types::{NodeResolvers, PyObj, SourceKind, SourceLookup};
+use crate::values::{ResultFormat, StringPolicy};
 
 #[pyclass(module = "grommet._core", name = "Schema")]
 pub(crate) struct SchemaWrapper {
+    definition: RwLock<Arc<Definition>>,
+    pub(crate) roles: Option<HashSet<String>>,
+    variants: std::sync::Mutex<HashMap<BTreeSet<String>, Py<SchemaWrapper>>>,
+    pub(crate) options: Arc<SchemaOptions>,
+}
+
+// The built schema with its bundle, swapped as a whole when a type is reloaded.
+pub(crate) struct Definition {
+    // Unique among the definitions of the process, so caches can tell them apart.
+    pub(crate) id: u64,
     schema: Arc<Schema>,
+    bundle: PyObj,
+    checker: PyOnceLock<Schema>,
+    pub(crate) typed_ids: Option<TypedIds>,
+    pub(crate) id_inputs: Option<IdInputs>,
 }
 
-impl SchemaWrapper {
-    fn convert_variables(variables: Option<Py<PyAny>>) -> PyResult<Option<async_graphql::Value>> {
-        match variables {
-            Some(vars) => Python::attach(|py| {
-                let bound = vars.bind(py);
-                py_to_value(py, bound)
-            })
-            .map(Some),
-            None => Ok(None),
+impl Definition {
+    fn new(schema: Schema, bundle: &Bound<'_, PyAny>) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Definition {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            typed_ids: match schema.registry().types.get(NODE_INTERFACE) {
+                Some(MetaType::Interface { possible_types, .. }) => {
+                    Some(TypedIds::new(possible_types.iter().cloned()))
+                }
+                _ => None,
+            },
+            id_inputs: IdInputs::of(schema.registry()),
+            schema: Arc::new(schema),
+            bundle: PyObj::new(bundle.clone().unbind()),
+            checker: PyOnceLock::new(),
         }
     }
+}
 
-    fn build_request(
-        query: String,
-        variables: Option<Py<PyAny>>,
-        context: Option<Py<PyAny>>,
-    ) -> PyResult<Request> {
-        let vars_value = Self::convert_variables(variables)?;
-        let mut request = Request::new(query);
-        if let Some(vars) = vars_value {
-            request = request.variables(Variables::from_value(vars));
-        }
-        if let Some(obj) = context {
-            request = request.data(ContextValue(PyObj::new(obj)));
-        }
-        Ok(request)
+impl SchemaWrapper {
+    fn from_bundle(
+        py: Python<'_>,
+        bundle: &Bound<'_, PyAny>,
+        roles: Option<HashSet<String>>,
+        options: Arc<SchemaOptions>,
+    ) -> PyResult<Self> {
+        let schema = register_schema(
+            py,
+            bundle,
+            roles.as_ref(),
+            false,
+            options.docstring_descriptions,
+        )?;
+        Ok(SchemaWrapper {
+            definition: RwLock::new(Arc::new(Definition::new(schema, bundle))),
+            roles,
+            variants: std::sync::Mutex::new(HashMap::new()),
+            options,
+        })
     }
 
-    fn is_subscription(query: &str) -> bool {
-        let Ok(doc) = parse_query(query) else {
-            return false;
-        };
-        for (_name, op) in doc.operations.iter() {
-            if op.node.ty == OperationType::Subscription {
-                return true;
-            }
+    pub(crate) fn result_format(&self, error_objects: bool) -> ResultFormat {
+        ResultFormat {
+            error_objects,
+            execution_result: self.options.execution_result,
+            values: self.options.value_settings(),
         }
-        false
+    }
+
+    pub(crate) fn definition(&self) -> Arc<Definition> {
+        self.definition
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn schema(&self) -> Arc<Schema> {
+        self.definition().schema.clone()
+    }
+
+    pub(crate) fn bundle(&self) -> PyObj {
+        self.definition().bundle.clone()
+    }
+
+    // A copy of the schema that validates requests without executing them, built on first use.
+    fn checker(&self, py: Python<'_>) -> PyResult<Schema> {
+        let definition = self.definition();
+        definition
+            .checker
+            .get_or_try_init(py, || {
+                register_schema(
+                    py,
+                    &definition.bundle.bind(py),
+                    self.roles.as_ref(),
+                    true,
+                    self.options.docstring_descriptions,
+                )
+            })
+            .cloned()
     }
 }
 
 #[pymethods]
 impl SchemaWrapper {
     #[new]
-    fn new(py: Python, bundle: &Bound<'_, PyAny>) -> PyResult<Self> {
-        let query: String = bundle.getattr("query")?.extract()?;
-        let mutation: Option<String> = bundle.getattr("mutation")?.extract()?;
-        let subscription: Option<String> = bundle.getattr("subscription")?.extract()?;
-        let types_list: Vec<Py<PyAny>> = bundle.getattr("types")?.extract()?;
+    #[pyo3(signature = (bundle, **options))]
+    fn new(
+        py: Python,
+        bundle: &Bound<'_, PyAny>,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let options = SchemaOptions::from_py(py, options)?;
+        let wrapper = Self::from_bundle(py, bundle, None, Arc::new(options))?;
+        let schema = wrapper.schema();
+        wrapper.options.check_bindings(schema.registry())?;
+        wrapper
+            .options
+            .check_mutation_resolvers(schema.registry(), bundle)?;
+        Ok(wrapper)
+    }
+
+    // Builds the schema on a worker thread, returning a coroutine for it.
+    #[classmethod]
+    #[pyo3(signature = (bundle, **options))]
+    fn build_async<'py>(
+        cls: &Bound<'py, PyType>,
+        bundle: &Bound<'py, PyAny>,
+        options: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        cls.py()
+            .import("asyncio")?
+            .getattr("to_thread")?
+            .call((cls, bundle), options)
+    }
+
+    // Schemas are rebuilt from their types rather than pickled.
+    fn __reduce__(&self) -> PyResult<Py<PyAny>> {
+        Err(not_picklable("schema"))
+    }
+
+    // Each role set is built from the full bundle once and cached on the base handle.
+    fn restrict(&self, py: Python<'_>, roles: Vec<String>) -> PyResult<Py<SchemaWrapper>> {
+        let key: BTreeSet<String> = roles.into_iter().collect();
+        let cached = self
+            .variants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .map(|variant| variant.clone_ref(py));
+        if let Some(variant) = cached {
+            return Ok(variant);
+        }
+
+        let roles = key.iter().cloned().collect();
+        let variant = Self::from_bundle(
+            py,
+            &self.bundle().bind(py),
+            Some(roles),
+            self.options.clone(),
+        )?;
+        let variant = Py::new(py, variant)?;
+        let mut variants = self.variants.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(variants.entry(key).or_insert(variant).clone_ref(py))
+    }
+
+    // Rebuilds the schema with one type, or some of its fields, replaced.
+    #[pyo3(signature = (type_def, resolvers=None))]
+    fn reload_type(
+        &self,
+        py: Python<'_>,
+        type_def: Bound<'_, PyAny>,
+        resolvers: Option<Vec<Bound<'_, PyAny>>>,
+    ) -> PyResult<()> {
+        let dataclasses = py.import("dataclasses")?;
+        let type_name: String = type_def.getattr("meta")?.getattr("name")?.extract()?;
+        let mut type_def = type_def;
+        if let Some(resolvers) = resolvers {
+            let mut fields: Vec<Bound<'_, PyAny>> = type_def.getattr("object_fields")?.extract()?;
+            for resolver in resolvers {
+                let resolver = resolver
+                    .getattr_opt("__grommet_compiled_resolver__")?
+                    .unwrap_or(resolver);
+                let field_name: String = resolver.getattr("name")?.extract()?;
+                let mut index = None;
+                for (position, field) in fields.iter().enumerate() {
+                    if field.getattr("name")?.extract::<String>()? == field_name {
+                        index = Some(position);
+                        break;
+                    }
+                }
+                let Some(index) = index else {
+                    return Err(unknown_reloaded_field(&type_name, &field_name));
+                };
+                fields[index] = resolver;
+            }
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("object_fields", PyTuple::new(py, fields)?)?;
+            type_def = dataclasses.call_method("replace", (type_def,), Some(&kwargs))?;
+        }
+
+        let bundle = self.bundle();
+        let bundle = bundle.bind(py);
+        let mut types: Vec<Bound<'_, PyAny>> = bundle.getattr("types")?.extract()?;
+        let mut index = None;
+        for (position, compiled) in types.iter().enumerate() {
+            if compiled
+                .getattr("meta")?
+                .getattr("name")?
+                .extract::<String>()?
+                == type_name
+            {
+                index = Some(position);
+                break;
+            }
+        }
+        let Some(index) = index else {
+            return Err(unknown_reloaded_type(&type_name));
+        };
+        types[index] = type_def;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("types", types)?;
+        let bundle = dataclasses.call_method("replace", (bundle,), Some(&kwargs))?;
 
         let schema = register_schema(
             py,
-            &query,
-            mutation.as_deref(),
-            subscription.as_deref(),
-            types_list,
+            &bundle,
+            self.roles.as_ref(),
+            false,
+            self.options.docstring_descriptions,
         )?;
-        Ok(SchemaWrapper {
-            schema: Arc::new(schema),
-        })
+        *self
+            .definition
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(Definition::new(schema, &bundle));
+        self.variants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        Ok(())
     }
 
     fn as_sdl(&self) -> PyResult<String> {
-        Ok(self.schema.sdl())
+        let header = self
+            .options
+            .sdl_header
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        Ok(schema_sdl(&self.schema(), &header))
+    }
+
+    // Sets the schema description and the comment lines that lead the exported SDL.
+    #[pyo3(signature = (description=None, *, comments=None))]
+    fn set_sdl_header(&self, description: Option<String>, comments: Option<String>) {
+        *self
+            .options
+            .sdl_header
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = SdlHeader {
+            description,
+            comments,
+        };
+    }
+
+    // Writes Python classes mirroring the schema's object, input, enum, and union types.
+    #[pyo3(signature = (path, style="dataclass"))]
+    fn generate_types(&self, path: PathBuf, style: &str) -> PyResult<()> {
+        let style = TypeStyle::parse(style).ok_or_else(|| {
+            py_value_error(format!(
+                "Unknown type style '{style}'; expected 'dataclass' or 'typeddict'."
+            ))
+        })?;
+        std::fs::write(path, generate_types(self.schema().registry(), style))?;
+        Ok(())
+    }
+
+    // Writes the schema's introspection result as JSON, for code generators and IDE plugins.
+    #[pyo3(signature = (path, minified=false))]
+    async fn export_introspection(&self, path: PathBuf, minified: bool) -> PyResult<()> {
+        let json = introspection_json(&self.schema(), minified).await?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    // Limits are shared with every variant derived from this handle. `None` disables a limit.
+    #[pyo3(signature = (
+        *,
+        max_query_bytes=None,
+        max_variables=None,
+        max_variable_bytes=None,
+        max_response_bytes=None,
+        max_list_items=None,
+    ))]
+    fn set_request_limits(
+        &self,
+        max_query_bytes: Option<usize>,
+        max_variables: Option<usize>,
+        max_variable_bytes: Option<usize>,
+        max_response_bytes: Option<usize>,
+        max_list_items: Option<usize>,
+    ) {
+        let mut limits = self
+            .options
+            .limits
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        *limits = RequestLimits {
+            max_query_bytes,
+            max_variables,
+            max_variable_bytes,
+            max_response_bytes,
+            max_list_items,
+        };
+    }
+
+    // Registers `hook(type_name, value)`, whose return value replaces each resolved value.
+    #[pyo3(signature = (hook))]
+    fn set_result_hook(&self, hook: Option<Py<PyAny>>) {
+        *self
+            .options
+            .result_hook
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = hook.map(PyObj::new);
+    }
+
+    // Registers `hook(metadata, context)`, run by every entry point to swap or reject contexts.
+    #[pyo3(signature = (hook))]
+    fn set_pre_execute(&self, hook: Option<Py<PyAny>>) {
+        *self
+            .options
+            .pre_execute
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = hook.map(PyObj::new);
+    }
+
+    // Registers Relay node fetchers by type name, each called as `fetcher(id, context)`.
+    #[pyo3(signature = (resolvers))]
+    fn set_node_resolvers(&self, resolvers: HashMap<String, Py<PyAny>>) {
+        let resolvers = resolvers
+            .into_iter()
+            .map(|(type_name, fetcher)| (type_name, PyObj::new(fetcher)))
+            .collect();
+        *self
+            .options
+            .node_resolvers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(NodeResolvers(Arc::new(resolvers)));
+    }
+
+    // Rate limits operations and fields with token buckets; `None` removes the limits.
+    #[pyo3(signature = (rules, key=None))]
+    fn set_rate_limits(
+        &self,
+        rules: Option<Vec<Bound<'_, PyDict>>>,
+        key: Option<Py<PyAny>>,
+    ) -> PyResult<()> {
+        let limits = rules
+            .map(|rules| RateLimits::new(&rules, key.map(PyObj::new)))
+            .transpose()?;
+        *self
+            .options
+            .rate_limits
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = limits.map(Arc::new);
+        Ok(())
+    }
+
+    // Forwards root data fields no default resolver covers to the GraphQL service at `url`.
+    #[pyo3(signature = (url, headers=None, transport=None, timeout_s=30.0))]
+    fn set_remote(
+        &self,
+        py: Python<'_>,
+        url: Option<String>,
+        headers: Option<HashMap<String, String>>,
+        transport: Option<Py<PyAny>>,
+        timeout_s: f64,
+    ) -> PyResult<()> {
+        if !(timeout_s.is_finite() && timeout_s > 0.0) {
+            return Err(invalid_remote_timeout(timeout_s));
+        }
+        let remote = match url {
+            Some(url) => {
+                let transport = match transport {
+                    Some(transport) => transport,
+                    None => py
+                        .import("grommet._remote")?
+                        .getattr("urllib_transport")?
+                        .call1((timeout_s,))?
+                        .unbind(),
+                };
+                Some(RemoteExecutor::new(
+                    url,
+                    headers.unwrap_or_default(),
+                    PyObj::new(transport),
+                ))
+            }
+            None => None,
+        };
+        *self
+            .options
+            .remote
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = remote;
+        Ok(())
+    }
+
+    // Mirrors the queries run through this handle to `schema`; `None` stops mirroring.
+    #[pyo3(signature = (schema, *, compare=true))]
+    fn set_shadow_schema(
+        &self,
+        py: Python<'_>,
+        schema: Option<Py<SchemaWrapper>>,
+        compare: bool,
+    ) -> PyResult<()> {
+        let shadow = match schema {
+            Some(schema) => {
+                let shadow = schema.bind(py).borrow();
+                if Arc::ptr_eq(&shadow.options, &self.options) || shadow.options.shadow().is_some()
+                {
+                    return Err(shadow_schema_cycle());
+                }
+                let manages_context = shadow.options.manage_context;
+                drop(shadow);
+                Some(ShadowTraffic::new(
+                    PyObj::new(schema.into_any()),
+                    compare,
+                    manages_context,
+                ))
+            }
+            None => None,
+        };
+        *self
+            .options
+            .shadow
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = shadow;
+        Ok(())
+    }
+
+    // Counts of the queries mirrored to the shadow schema, `None` without one.
+    fn shadow_stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.options
+            .shadow()
+            .map(|shadow| shadow.stats(py))
+            .transpose()
+    }
+
+    // Makes data fields read the parent's attribute and/or item named like the field.
+    #[pyo3(signature = (order=vec!["attr".to_string(), "item".to_string()], strict=false))]
+    fn set_source_lookup(&self, order: Vec<String>, strict: bool) -> PyResult<()> {
+        let order = order
+            .iter()
+            .map(|kind| match kind.as_str() {
+                "attr" => Ok(SourceKind::Attr),
+                "item" => Ok(SourceKind::Item),
+                _ => Err(unknown_source_kind(kind)),
+            })
+            .collect::<PyResult<_>>()?;
+        *self
+            .options
+            .source_lookup
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SourceLookup { order, strict });
+        Ok(())
     }
 
+    // Keeps the last `size` cursor-tagged events of each subscription for `resume_from`.
+    #[pyo3(signature = (size))]
+    fn set_replay_buffer(&self, size: usize) {
+        self.options
+            .replay
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_capacity(size);
+    }
+
+    // Sets how deeply converted lists and objects may nest.
+    #[pyo3(signature = (depth))]
+    fn set_max_value_depth(&self, depth: usize) -> PyResult<()> {
+        if depth == 0 {
+            return Err(invalid_max_value_depth());
+        }
+        self.options
+            .values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_depth = depth;
+        Ok(())
+    }
+
+    // Sets how many values a conversion handles between letting other threads run.
+    #[pyo3(signature = (items))]
+    fn set_gil_yield_interval(&self, items: usize) {
+        self.options
+            .values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .gil_yield_interval = items;
+    }
+
+    // Sets how strings with NUL characters or lone surrogates convert.
+    #[pyo3(signature = (policy))]
+    fn set_string_policy(&self, policy: &str) -> PyResult<()> {
+        let policy = StringPolicy::from_name(policy)?;
+        self.options
+            .values
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .strings = policy;
+        Ok(())
+    }
+
+    // Adds exception details and per-request stats to responses, or stops doing so.
+    #[pyo3(signature = (enabled=true))]
+    fn set_debug(&self, enabled: bool) {
+        self.options.debug.store(enabled, Ordering::Relaxed);
+    }
+
+    // Starts recording which schema fields executed operations select, or stops.
+    #[pyo3(signature = (enabled=true))]
+    fn set_coverage(&self, enabled: bool) {
+        self.options
+            .coverage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_enabled(enabled);
+    }
+
+    // Selection counts keyed by field coordinate (`Type.field`).
+    fn coverage(&self) -> PyResult<BTreeMap<String, usize>> {
+        self.options
+            .coverage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .report(self.schema().registry())
+            .ok_or_else(|| py_value_error("Coverage is not being recorded."))
+    }
+
+    // How long responses of mutations run with an `idempotency_key` are replayed for.
+    #[pyo3(signature = (ttl_s))]
+    fn set_idempotency_ttl(&self, ttl_s: f64) -> PyResult<()> {
+        let ttl = Duration::try_from_secs_f64(ttl_s)
+            .ok()
+            .filter(|ttl| !ttl.is_zero())
+            .ok_or_else(|| invalid_idempotency_ttl(ttl_s))?;
+        self.options.idempotency.set_ttl(ttl);
+        Ok(())
+    }
+
+    // Drops or shortens the descriptions of introspection results.
+    #[pyo3(signature = (*, omit_descriptions=false, max_description_length=None))]
+    fn set_introspection_trim(
+        &self,
+        omit_descriptions: bool,
+        max_description_length: Option<usize>,
+    ) {
+        *self
+            .options
+            .introspection_trim
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) =
+            (omit_descriptions || max_description_length.is_some()).then_some(IntrospectionTrim {
+                omit_descriptions,
+                max_description_length,
+            });
+    }
+
+    // Starts recording each resolver call of executed requests, or stops.
+    #[pyo3(signature = (enabled=true))]
+    fn set_call_capture(&self, enabled: bool) {
+        *self
+            .options
+            .call_capture
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = enabled.then(CallCapture::default);
+    }
+
+    // The resolver calls recorded so far, in completion order.
+    fn captured_calls<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.options
+            .call_capture()
+            .ok_or_else(|| py_value_error("Resolver calls are not being captured."))?
+            .to_py(py)
+    }
+
+    // The open subscription streams started through this handle or its variants.
+    fn active_subscriptions<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.options
+            .subscriptions
+            .live()
+            .iter()
+            .map(|handle| {
+                let entry = PyDict::new(py);
+                entry.set_item("operation_name", &handle.operation_name)?;
+                entry.set_item("field_name", &handle.field_name)?;
+                entry.set_item("started_at", handle.started_at)?;
+                entry.set_item("labels", &handle.labels)?;
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    // Closes the open subscription streams carrying `label`, or all of them.
+    #[pyo3(signature = (label=None))]
+    fn cancel_all(&self, label: Option<&str>) -> usize {
+        let handles = self.options.subscriptions.live();
+        let mut cancelled = 0;
+        for handle in handles {
+            if label.is_none_or(|label| handle.labels.iter().any(|candidate| candidate == label)) {
+                handle.cancel();
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    // Awaitables returned by resolvers run on the running event loop, or a helper loop.
+    #[pyo3(signature = (query, variables=None, context=None, *, options=None))]
     async fn execute(
+        &self,
+        query: QuerySource,
+        variables: Option<Py<PyAny>>,
+        context: Option<Py<PyAny>>,
+        options: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let variables = VariablesSource::Python(variables);
+        self.execute_request(query, variables, context, options)
+            .await
+    }
+
+    // Like `execute`, but takes the variables as an encoded JSON object.
+    #[pyo3(signature = (query, variables_json=None, context=None, *, options=None))]
+    async fn execute_raw(
+        &self,
+        query: QuerySource,
+        variables_json: Option<Vec<u8>>,
+        context: Option<Py<PyAny>>,
+        options: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let variables = VariablesSource::Json(variables_json);
+        self.execute_request(query, variables, context, options)
+            .await
+    }
+
+    // Runs a request with generated data standing in for unimplemented resolvers.
+    #[pyo3(signature = (query, variables=None, context=None, *, seed=None, list_length=2))]
+    async fn execute_mock(
         &self,
         query: String,
         variables: Option<Py<PyAny>>,
         context: Option<Py<PyAny>>,
+        seed: Option<u64>,
+        list_length: usize,
     ) -> PyResult<Py<PyAny>> {
-        let is_sub = Self::is_subscription(&query);
-        let request = Self::build_request(query, variables, context)?;
-        let schema = self.schema.clone();
-
-        if is_sub {
-            let stream = schema.execute_stream(request);
-            let sub_stream = SubscriptionStream {
-                stream: Arc::new(Mutex::new(Some(stream))),
-                closed: Arc::new(AtomicBool::new(false)),
-            };
-            Python::attach(|py| Ok(sub_stream.into_pyobject(py)?.into_any().unbind()))
-        } else {
-            let response = schema.execute(request).await;
-            Python::attach(|py| response_to_py(py, response))
-        }
+        self.mock(query, variables, context, seed, list_length)
+            .await
     }
-}
 
-#[pyclass(module = "grommet._core", name = "SubscriptionStream")]
-pub(crate) struct SubscriptionStream {
-    stream: Arc<Mutex<Option<BoxStream<'static, async_graphql::Response>>>>,
-    closed: Arc<AtomicBool>,
-}
+    // Runs a request and returns a stable SHA-256 hash of its data and errors.
+    #[pyo3(signature = (query, variables=None, context=None))]
+    async fn response_fingerprint(
+        &self,
+        query: String,
+        variables: Option<Py<PyAny>>,
+        context: Option<Py<PyAny>>,
+    ) -> PyResult<String> {
+        self.fingerprint(query, variables, context).await
+    }
 
-#[pymethods]
-impl SubscriptionStream {
-    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
-        slf
+    // Runs a single query root field with keyword arguments and returns its value.
+    #[pyo3(signature = (field_name, *, context=None, **args))]
+    async fn call(
+        &self,
+        field_name: String,
+        context: Option<Py<PyAny>>,
+        args: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        self.call_field(field_name, context, args).await
     }
 
-    fn __anext__<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
-        let py = slf.py();
-        let slf_obj: Py<Self> = slf.into();
-        slf_obj.bind(py).call_method0("_anext_impl")
+    // The schema's types with their kind and description, for admin UIs and doc generators.
+    fn types<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        schema_types(py, self.schema().registry())
     }
 
-    #[pyo3(name = "_anext_impl")]
-    async fn anext_impl(&self) -> PyResult<Py<PyAny>> {
-        if self.closed.load(Ordering::SeqCst) {
-            return Err(PyErr::new::<PyStopAsyncIteration, _>(""));
-        }
-        let mut guard = self.stream.lock().await;
-        let Some(stream) = guard.as_mut() else {
-            return Err(PyErr::new::<PyStopAsyncIteration, _>(""));
+    // The fields of a type with their types, arguments, and Python resolvers.
+    fn fields<'py>(&self, py: Python<'py>, type_name: &str) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let resolvers = python_resolver_keys(&self.bundle().bind(py))?;
+        type_fields(py, self.schema().registry(), &resolvers, type_name)
+    }
+
+    // Coordinates (`Type.field`) of the fields in this schema backed by Python resolvers.
+    fn resolver_keys(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        let schema = self.schema();
+        let types = &schema.registry().types;
+        Ok(python_resolver_keys(&self.bundle().bind(py))?
+            .into_iter()
+            .filter(|key| {
+                key.split_once('.').is_some_and(|(type_name, field_name)| {
+                    types
+                        .get(type_name)
+                        .is_some_and(|ty| ty.field_by_name(field_name).is_some())
+                })
+            })
+            .collect())
+    }
+
+    // Plans an operation without running it.
+    #[pyo3(signature = (query, operation_name=None))]
+    fn explain<'py>(
+        &self,
+        py: Python<'py>,
+        query: &str,
+        operation_name: Option<&str>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let doc = parse_query(query).map_err(|err| validation_failed(err.to_string()))?;
+        let mut operations = doc.operations.iter();
+        let (name, operation) = match operation_name {
+            Some(name) => operations
+                .find(|(operation, _)| operation.is_some_and(|operation| operation == name))
+                .ok_or_else(|| validation_failed(format!("Unknown operation named \"{name}\".")))?,
+            None => match (operations.next(), operations.next()) {
+                (Some(operation), None) => operation,
+                _ => {
+                    return Err(validation_failed(
+                        "Operation name is required for documents with several operations.",
+                    ));
+                }
+            },
         };
-        match stream.next().await {
-            Some(response) => Python::attach(|py| response_to_py(py, response)),
-            None => Err(PyErr::new::<PyStopAsyncIteration, _>("")),
+        let operation = &operation.node;
+        let schema = self.schema();
+        let registry = schema.registry();
+        let root = root_type_name(registry, operation.ty).ok_or_else(|| {
+            validation_failed(format!(
+                "Schema does not support {} operations.",
+                operation.ty
+            ))
+        })?;
+        let plan = explain_operation(
+            py,
+            registry,
+            &self.bundle().bind(py),
+            &doc,
+            root,
+            &operation.selection_set.node,
+        )?;
+        plan.set_item("operation", operation.ty.to_string())?;
+        plan.set_item("name", name.map(|name| name.as_str()))?;
+        Ok(plan)
+    }
+
+    // Validates a single-operation document and the caller's variable types.
+    #[pyo3(signature = (query, variables_types=None))]
+    async fn check_operation(
+        &self,
+        query: String,
+        variables_types: Option<HashMap<String, String>>,
+    ) -> PyResult<Py<PyAny>> {
+        let doc = parse_query(&query).map_err(|err| validation_failed(err.to_string()))?;
+        let checker = Python::attach(|py| self.checker(py))?;
+        let mut request = Request::new(query);
+        request.set_parsed_query(doc.clone());
+        let response = checker.execute(request).await;
+        let mut errors: Vec<String> = response
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect();
+        let operation = single_operation(&doc);
+        if let Some(operation) = operation {
+            errors.extend(variable_errors(
+                operation,
+                &variables_types.unwrap_or_default(),
+            ));
+        }
+        match operation {
+            Some(operation) if errors.is_empty() => Python::attach(|py| {
+                Ok(
+                    operation_shape(py, self.schema().registry(), &doc, operation)?
+                        .into_any()
+                        .unbind(),
+                )
+            }),
+            _ => Err(validation_failed(errors.join("\n"))),
         }
     }
 
-    async fn aclose(&self) -> PyResult<()> {
-        self.closed.store(true, Ordering::SeqCst);
-        let mut guard = self.stream.lock().await;
-        *guard = None;
-        Ok(())
+    // Parses and validates operations ahead of time, keeping the valid ones parsed.
+    #[pyo3(signature = (operations))]
+    async fn warm_up(&self, operations: Vec<String>) -> PyResult<HashMap<String, Vec<String>>> {
+        let checker = Python::attach(|py| self.checker(py))?;
+        let mut failures = HashMap::new();
+        for query in operations {
+            let doc = match parse_query(&query) {
+                Ok(doc) => doc,
+                Err(err) => {
+                    failures.insert(query, vec![err.to_string()]);
+                    continue;
+                }
+            };
+            let mut request = Request::new(query.clone());
+            request.set_parsed_query(doc.clone());
+            // The checker does not execute, so missing variables are not reported.
+            let errors: Vec<String> = checker
+                .execute(request)
+                .await
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect();
+            if errors.is_empty() {
+                self.options
+                    .documents
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(query, Arc::new(doc));
+            } else {
+                failures.insert(query, errors);
+            }
+        }
+        Ok(failures)
+    }
+
+    // A snapshot of how this handle is wired, for startup logging and support bundles.
+    fn debug_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let schema = self.schema();
+        let scalars = py
+            .import("grommet.metadata")?
+            .getattr("scalar_bindings")?
+            .call0()?;
+        let info = type_registries(py, schema.registry(), scalars.cast::<PyDict>()?)?;
+        info.set_item("resolver_keys", self.resolver_keys(py)?)?;
+        info.set_item(
+            "roles",
+            self.roles.as_ref().map(|roles| sorted(roles.iter())),
+        )?;
+        info.set_item("bindings", self.options.bindings_info(py)?)?;
+        info.set_item("limits", self.options.limits().to_py(py)?)?;
+        info.set_item("settings", self.options.settings_info(py)?)?;
+        Ok(info)
     }
+
+    // Entry counts and lookup hit rates of the caches behind this handle.
+    fn cache_stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let documents = self
+            .options
+            .documents
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        let variants = self
+            .variants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        let variant_stats = PyDict::new(py);
+        variant_stats.set_item("entries", variants)?;
+        let stats = PyDict::new(py);
+        stats.set_item(
+            "documents",
+            self.options.document_lookups.to_py(py, documents)?,
+        )?;
+        stats.set_item("idempotency", self.options.idempotency.stats(py)?)?;
+        stats.set_item("variants", variant_stats)?;
+        Ok(stats)
+    }
+
+    // Empties the caches reported by `cache_stats`, keeping idempotency entries.
+    fn clear_caches(&self) {
+        self.options
+            .documents
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.options.document_lookups.reset();
+        self.variants
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    // Runs each named operation of a document, parsed once, keyed by name.
+    #[pyo3(signature = (query, operations, variables=None, context=None))]
+    async fn execute_document(
+        &self,
+        query: String,
+        operations: Vec<String>,
+        variables: Option<HashMap<String, Py<PyAny>>>,
+        context: Option<Py<PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        self.run_document(query, operations, variables, context)
+            .await
+    }
+}
+
+pub(crate) fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+    let mut names: Vec<_> = names.collect();
+    names.sort();
+    names
 }
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Poll, Waker};
+
+use async_graphql::Error;
+use async_graphql::futures_util::future::{BoxFuture, FutureExt, Shared};
+use pyo3::prelude::*;
+use pyo3::types::PyCFunction;
+
+use crate::types::PyObj;
+
+// A batched field, its depth in the response, and its serialized arguments.
+pub(crate) type BatchKey = (usize, usize, String);
+
+type BatchValues = Shared<BoxFuture<'static, Result<Arc<Vec<PyObj>>, Error>>>;
+
+// Parents collected for one batch key while the executor polls sibling fields. The batch closes
+// when the event loop next gets control, after every sibling has had a chance to join.
+#[derive(Default)]
+struct BatchState {
+    parents: Vec<PyObj>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+struct OpenBatch {
+    state: Arc<Mutex<BatchState>>,
+    values: BatchValues,
+}
+
+// The parents of a batch, available once it closes.
+pub(crate) struct BatchParents(Arc<Mutex<BatchState>>);
+
+impl BatchParents {
+    pub(crate) async fn collect(self) -> Vec<PyObj> {
+        poll_fn(|cx| {
+            let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.closed {
+                Poll::Ready(std::mem::take(&mut state.parents))
+            } else {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+// A parent's place in a batch. The batch call is shared by its members, so it completes as long
+// as any of them is still waiting for a value.
+pub(crate) struct BatchMember {
+    values: BatchValues,
+    index: usize,
+}
+
+impl BatchMember {
+    pub(crate) async fn value(self) -> Result<PyObj, Error> {
+        let values = self.values.await?;
+        Ok(values[self.index].clone())
+    }
+}
+
+// Open batches of a request, attached to every request.
+#[derive(Clone, Default)]
+pub(crate) struct BatchLoader(Arc<Mutex<HashMap<BatchKey, OpenBatch>>>);
+
+impl BatchLoader {
+    // Adds `parent` to the open batch for `key`. A new batch is opened when there is none, with
+    // `run` turning its parents into one value per parent.
+    pub(crate) fn join<F>(
+        &self,
+        py: Python<'_>,
+        key: BatchKey,
+        parent: PyObj,
+        run: F,
+    ) -> PyResult<BatchMember>
+    where
+        F: FnOnce(BatchParents) -> BoxFuture<'static, Result<Vec<PyObj>, Error>>,
+    {
+        let mut batches = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(batch) = batches.get(&key) {
+            let mut state = batch.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.parents.push(parent);
+            return Ok(BatchMember {
+                values: batch.values.clone(),
+                index: state.parents.len() - 1,
+            });
+        }
+
+        let state = Arc::new(Mutex::new(BatchState {
+            parents: vec![parent],
+            ..BatchState::default()
+        }));
+        let (loader, closing, closing_key) = (self.0.clone(), state.clone(), key.clone());
+        let close = PyCFunction::new_closure(
+            py,
+            Some(c"grommet_batch_close"),
+            None,
+            move |_args, _kwargs| -> PyResult<()> {
+                loader
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .remove(&closing_key);
+                let mut state = closing.lock().unwrap_or_else(PoisonError::into_inner);
+                state.closed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+                Ok(())
+            },
+        )?;
+        py.import("asyncio")?
+            .call_method0("get_running_loop")?
+            .call_method1("call_soon", (close,))?;
+
+        let values = run(BatchParents(state.clone()))
+            .map(|values| values.map(Arc::new))
+            .boxed()
+            .shared();
+        batches.insert(
+            key,
+            OpenBatch {
+                state,
+                values: values.clone(),
+            },
+        );
+        Ok(BatchMember { values, index: 0 })
+    }
+}
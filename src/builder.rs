@@ -0,0 +1,296 @@
+use async_graphql::Value;
+use async_graphql::dynamic::Schema;
+use async_graphql::registry::{MetaType, MetaTypeName, Registry};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+
+use crate::api::SchemaWrapper;
+use crate::errors::invalid_selection;
+use crate::values::{ValueSettings, py_to_value};
+
+// A field to select, with its arguments and subselections, built fluently: every method returns
+// a new selection. Field names and aliases are checked when a `QueryBuilder` renders the
+// operation.
+#[pyclass(
+    module = "grommet._core",
+    name = "FieldSelection",
+    frozen,
+    skip_from_py_object
+)]
+#[derive(Clone)]
+pub(crate) struct FieldSelection {
+    name: String,
+    alias: Option<String>,
+    args: Vec<(String, Value)>,
+    selections: Vec<FieldSelection>,
+}
+
+impl FieldSelection {
+    fn new(name: String) -> Self {
+        FieldSelection {
+            name,
+            alias: None,
+            args: Vec::new(),
+            selections: Vec::new(),
+        }
+    }
+
+    // Renders the selection as a field of `type_name`, checking it against the schema. `path`
+    // is the dotted path of the parent, for error messages.
+    fn render(&self, registry: &Registry, type_name: &str, path: &str) -> PyResult<String> {
+        let path = if path.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{path}.{}", self.name)
+        };
+        let field = match self.name.as_str() {
+            "__typename" => None,
+            name => Some(
+                registry
+                    .types
+                    .get(type_name)
+                    .and_then(|ty| ty.field_by_name(name))
+                    .ok_or_else(|| {
+                        invalid_selection(&path, &format!("\"{type_name}\" has no such field"))
+                    })?,
+            ),
+        };
+
+        let mut rendered = match &self.alias {
+            Some(alias) => {
+                check_name(&path, "alias", alias)?;
+                format!("{alias}: {}", self.name)
+            }
+            None => self.name.clone(),
+        };
+        if !self.args.is_empty() {
+            let mut args = Vec::new();
+            for (name, value) in &self.args {
+                let Some(arg) = field.and_then(|field| field.args.get(name)) else {
+                    return Err(invalid_selection(
+                        &path,
+                        &format!("the field has no argument \"{name}\""),
+                    ));
+                };
+                let literal = enum_literals(registry, &arg.ty, value.clone(), &path)?;
+                args.push(format!("{name}: {literal}"));
+            }
+            rendered.push_str(&format!("({})", args.join(", ")));
+        }
+
+        let field_type = field.map_or("String!", |field| field.ty.as_str());
+        let child_type = MetaTypeName::concrete_typename(field_type);
+        let leaf = registry.types.get(child_type).is_none_or(MetaType::is_leaf);
+        match (leaf, self.selections.is_empty()) {
+            (true, false) => Err(invalid_selection(
+                &path,
+                &format!("\"{child_type}\" has no fields to select"),
+            )),
+            (false, true) => Err(invalid_selection(
+                &path,
+                &format!("fields of \"{child_type}\" must be selected"),
+            )),
+            (true, true) => Ok(rendered),
+            (false, false) => {
+                let selections = render_all(registry, child_type, &path, &self.selections)?;
+                Ok(format!("{rendered} {{ {selections} }}"))
+            }
+        }
+    }
+}
+
+fn render_all(
+    registry: &Registry,
+    type_name: &str,
+    path: &str,
+    selections: &[FieldSelection],
+) -> PyResult<String> {
+    let rendered = selections
+        .iter()
+        .map(|selection| selection.render(registry, type_name, path))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(rendered.join(" "))
+}
+
+// Whether `name` is a GraphQL name, which aliases, operation names, enum values and input
+// object keys are written as.
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|char| char == '_' || char.is_ascii_alphanumeric())
+}
+
+fn check_name(path: &str, what: &str, name: &str) -> PyResult<()> {
+    if is_name(name) {
+        Ok(())
+    } else {
+        Err(invalid_selection(
+            path,
+            &format!("{what} {name:?} is not a GraphQL name"),
+        ))
+    }
+}
+
+// Strings given for enum arguments, directly or in lists and input objects, are written as enum
+// values, and object keys are checked to be names. `path` locates the selection for error
+// messages.
+fn enum_literals(registry: &Registry, ty: &str, value: Value, path: &str) -> PyResult<Value> {
+    let named = MetaTypeName::concrete_typename(ty);
+    Ok(match (registry.types.get(named), value) {
+        (Some(MetaType::Enum { .. }), Value::String(name)) => {
+            check_name(path, "enum value", &name)?;
+            Value::Enum(async_graphql::Name::new(name))
+        }
+        (_, Value::List(items)) => {
+            let item_type = match MetaTypeName::create(ty) {
+                MetaTypeName::NonNull(inner) => inner,
+                _ => ty,
+            };
+            let item_type = match MetaTypeName::create(item_type) {
+                MetaTypeName::List(inner) => inner,
+                _ => item_type,
+            };
+            Value::List(
+                items
+                    .into_iter()
+                    .map(|item| enum_literals(registry, item_type, item, path))
+                    .collect::<PyResult<_>>()?,
+            )
+        }
+        (input, Value::Object(fields)) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, value)| {
+                    check_name(path, "input field", &name)?;
+                    let field = match input {
+                        Some(MetaType::InputObject { input_fields, .. }) => {
+                            input_fields.get(name.as_str())
+                        }
+                        _ => None,
+                    };
+                    let ty = field.map_or("", |field| field.ty.as_str());
+                    Ok((name, enum_literals(registry, ty, value, path)?))
+                })
+                .collect::<PyResult<_>>()?,
+        ),
+        (_, value) => value,
+    })
+}
+
+fn selections(items: &Bound<'_, PyTuple>) -> PyResult<Vec<FieldSelection>> {
+    items
+        .iter()
+        .map(|item| match item.cast::<FieldSelection>() {
+            Ok(selection) => Ok(selection.get().clone()),
+            Err(_) => Ok(FieldSelection::new(item.extract()?)),
+        })
+        .collect()
+}
+
+#[pymethods]
+impl FieldSelection {
+    // Adds subselections, given as field names or `FieldSelection`s.
+    #[pyo3(signature = (*selections))]
+    fn select(&self, selections: &Bound<'_, PyTuple>) -> PyResult<Self> {
+        let mut selected = self.clone();
+        selected.selections.extend(self::selections(selections)?);
+        Ok(selected)
+    }
+
+    fn alias(&self, alias: String) -> Self {
+        FieldSelection {
+            alias: Some(alias),
+            ..self.clone()
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FieldSelection({:?})", self.name)
+    }
+}
+
+// Builds operations for a schema from `FieldSelection`s, checking field and argument names
+// against the schema as it is when they are rendered, so that typos fail while building a
+// query rather than when running it.
+#[pyclass(module = "grommet._core", name = "QueryBuilder", frozen)]
+pub(crate) struct QueryBuilder {
+    schema: Py<SchemaWrapper>,
+}
+
+impl QueryBuilder {
+    fn operation(
+        &self,
+        py: Python<'_>,
+        kind: &str,
+        root: impl FnOnce(&Schema) -> Option<String>,
+        selections: &Bound<'_, PyTuple>,
+        name: Option<&str>,
+    ) -> PyResult<String> {
+        let schema = self.schema.bind(py).borrow().schema();
+        let Some(root) = root(&schema) else {
+            return Err(invalid_selection(kind, "the schema has no such root type"));
+        };
+        if let Some(name) = name {
+            check_name(kind, "operation name", name)?;
+        }
+        let selections = self::selections(selections)?;
+        if selections.is_empty() {
+            return Err(invalid_selection(kind, "no fields are selected"));
+        }
+        let rendered = render_all(schema.registry(), &root, "", &selections)?;
+        Ok(match name {
+            Some(name) => format!("{kind} {name} {{ {rendered} }}"),
+            None => format!("{kind} {{ {rendered} }}"),
+        })
+    }
+}
+
+#[pymethods]
+impl QueryBuilder {
+    #[new]
+    fn new(schema: Py<SchemaWrapper>) -> Self {
+        QueryBuilder { schema }
+    }
+
+    // A field selection, with keyword arguments as its arguments.
+    #[pyo3(signature = (name, **args))]
+    fn field(
+        &self,
+        py: Python<'_>,
+        name: String,
+        args: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<FieldSelection> {
+        let mut selection = FieldSelection::new(name);
+        for (arg, value) in args.into_iter().flatten() {
+            selection.args.push((
+                arg.extract()?,
+                py_to_value(py, &value, ValueSettings::default())?,
+            ));
+        }
+        Ok(selection)
+    }
+
+    #[pyo3(signature = (*selections, name=None))]
+    fn query(
+        &self,
+        py: Python<'_>,
+        selections: &Bound<'_, PyTuple>,
+        name: Option<&str>,
+    ) -> PyResult<String> {
+        let root = |schema: &Schema| Some(schema.registry().query_type.clone());
+        self.operation(py, "query", root, selections, name)
+    }
+
+    #[pyo3(signature = (*selections, name=None))]
+    fn mutation(
+        &self,
+        py: Python<'_>,
+        selections: &Bound<'_, PyTuple>,
+        name: Option<&str>,
+    ) -> PyResult<String> {
+        let root = |schema: &Schema| schema.registry().mutation_type.clone();
+        self.operation(py, "mutation", root, selections, name)
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// Lookup outcomes of one of a schema's caches, reported by `cache_stats`.
+#[derive(Default)]
+pub(crate) struct HitCounter {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl HitCounter {
+    pub(crate) fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    // `{"entries", "hits", "misses", "hit_rate"}` for a cache holding `entries` values; the hit
+    // rate is `None` until the cache has been looked up.
+    pub(crate) fn to_py<'py>(
+        &self,
+        py: Python<'py>,
+        entries: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+        let stats = PyDict::new(py);
+        stats.set_item("entries", entries)?;
+        stats.set_item("hits", hits)?;
+        stats.set_item("misses", misses)?;
+        stats.set_item(
+            "hit_rate",
+            (lookups > 0).then(|| hits as f64 / lookups as f64),
+        )?;
+        Ok(stats)
+    }
+}
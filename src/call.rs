@@ -0,0 +1,77 @@
+use async_graphql::registry::{MetaField, MetaType, MetaTypeName, Registry};
+use pyo3::prelude::*;
+
+use crate::errors::{unknown_call_argument, unknown_root_field};
+
+// The query `call` runs for one root field: each keyword argument becomes a variable of the
+// argument's declared type, and an object or interface result selects its leaf fields that take
+// no required arguments, or `__typename` when it has none, as unions do.
+pub(crate) fn call_query(
+    registry: &Registry,
+    field_name: &str,
+    arg_names: &[String],
+) -> PyResult<String> {
+    let field = registry
+        .types
+        .get(&registry.query_type)
+        .and_then(|ty| ty.field_by_name(field_name))
+        .ok_or_else(|| unknown_root_field(field_name))?;
+    let mut variables = Vec::new();
+    let mut arguments = Vec::new();
+    for name in arg_names {
+        let arg = field
+            .args
+            .get(name)
+            .ok_or_else(|| unknown_call_argument(field_name, name))?;
+        variables.push(format!("${name}: {}", arg.ty));
+        arguments.push(format!("{name}: ${name}"));
+    }
+
+    let mut query = String::from("query");
+    if !variables.is_empty() {
+        query.push_str(&format!("({})", variables.join(", ")));
+    }
+    query.push_str(&format!(" {{ {field_name}"));
+    if !arguments.is_empty() {
+        query.push_str(&format!("({})", arguments.join(", ")));
+    }
+    if let Some(selection) = selection(registry, field) {
+        query.push_str(&format!(" {{ {selection} }}"));
+    }
+    query.push_str(" }");
+    Ok(query)
+}
+
+fn selection(registry: &Registry, field: &MetaField) -> Option<String> {
+    let ty = registry
+        .types
+        .get(MetaTypeName::concrete_typename(&field.ty))?;
+    if ty.is_leaf() {
+        return None;
+    }
+    let leaves: Vec<&str> = ty
+        .fields()
+        .into_iter()
+        .flat_map(|fields| fields.values())
+        .filter(|field| !field.name.starts_with("__") && takes_no_required_args(field))
+        .filter(|field| {
+            registry
+                .types
+                .get(MetaTypeName::concrete_typename(&field.ty))
+                .is_some_and(MetaType::is_leaf)
+        })
+        .map(|field| field.name.as_str())
+        .collect();
+    if leaves.is_empty() {
+        Some("__typename".to_owned())
+    } else {
+        Some(leaves.join(" "))
+    }
+}
+
+fn takes_no_required_args(field: &MetaField) -> bool {
+    field
+        .args
+        .values()
+        .all(|arg| !arg.ty.ends_with('!') || arg.default_value.is_some())
+}
@@ -0,0 +1,57 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// Longest summary kept of a parent or result, in characters.
+const SUMMARY_CHARS: usize = 200;
+
+// One resolver invocation: the field coordinate and path it resolved, the arguments it received,
+// and summaries of its parent and what it returned or raised.
+pub(crate) struct CapturedCall {
+    pub(crate) key: String,
+    pub(crate) path: String,
+    pub(crate) kwargs: Py<PyDict>,
+    pub(crate) parent: String,
+    pub(crate) result: Result<String, String>,
+}
+
+// Resolver invocations recorded in completion order while the schema captures them, so tests
+// can assert how resolvers were called.
+#[derive(Clone, Default)]
+pub(crate) struct CallCapture(Arc<Mutex<Vec<CapturedCall>>>);
+
+impl CallCapture {
+    pub(crate) fn record(&self, call: CapturedCall) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(call);
+    }
+
+    pub(crate) fn to_py<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let calls = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        calls
+            .iter()
+            .map(|call| {
+                let entry = PyDict::new(py);
+                entry.set_item("key", &call.key)?;
+                entry.set_item("path", &call.path)?;
+                entry.set_item("kwargs", call.kwargs.bind(py).copy()?)?;
+                entry.set_item("parent", &call.parent)?;
+                entry.set_item("result", call.result.as_ref().ok())?;
+                entry.set_item("error", call.result.as_ref().err())?;
+                Ok(entry)
+            })
+            .collect()
+    }
+}
+
+// The `repr` of a value, shortened to a readable length.
+pub(crate) fn summarize(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    let repr = value.repr()?.to_string();
+    Ok(match repr.char_indices().nth(SUMMARY_CHARS) {
+        Some((end, _)) => format!("{}...", &repr[..end]),
+        None => repr,
+    })
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery,
+};
+use async_graphql::parser::parse_query;
+use async_graphql::parser::types::{
+    BaseType, DocumentOperations, ExecutableDocument, OperationDefinition, Selection, SelectionSet,
+    Type,
+};
+use async_graphql::registry::{MetaTypeName, Registry};
+use async_graphql::{Response, ServerResult, Value, Variables};
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::document::root_type_name;
+use crate::errors::parse_error;
+
+// Installed only on the checking copy of a schema: requests run through parsing and validation,
+// then stop before any resolver is called.
+pub(crate) struct ValidateOnly;
+
+struct ValidateOnlyExtension;
+
+impl ExtensionFactory for ValidateOnly {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ValidateOnlyExtension)
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for ValidateOnlyExtension {
+    async fn execute(
+        &self,
+        _ctx: &ExtensionContext<'_>,
+        _operation_name: Option<&str>,
+        _next: NextExecute<'_>,
+    ) -> Response {
+        Response::new(Value::Null)
+    }
+}
+
+// Installed on every schema: codes the errors of queries that do not parse `PARSE_ERROR` where
+// parsing fails, apart from the document limits checked along with it.
+pub(crate) struct ParseErrors;
+
+struct ParseErrorsExtension;
+
+impl ExtensionFactory for ParseErrors {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ParseErrorsExtension)
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for ParseErrorsExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        next.run(ctx, query, variables)
+            .await
+            .map_err(|error| match parse_query(query) {
+                Err(err) => parse_error(err),
+                Ok(_) => error,
+            })
+    }
+}
+
+// The operation a single-operation document runs; validation has already rejected any other.
+pub(crate) fn single_operation(doc: &ExecutableDocument) -> Option<&OperationDefinition> {
+    match &doc.operations {
+        DocumentOperations::Single(operation) => Some(&operation.node),
+        DocumentOperations::Multiple(operations) if operations.len() == 1 => {
+            operations.values().next().map(|operation| &operation.node)
+        }
+        DocumentOperations::Multiple(_) => None,
+    }
+}
+
+// Whether a value of the `provided` type may be passed where `declared` is expected, including
+// the single-item coercion into lists.
+fn type_accepts(declared: &Type, provided: &Type) -> bool {
+    if !declared.nullable && provided.nullable {
+        return false;
+    }
+    match (&declared.base, &provided.base) {
+        (BaseType::Named(declared), BaseType::Named(provided)) => declared == provided,
+        (BaseType::List(declared), BaseType::List(provided)) => type_accepts(declared, provided),
+        (BaseType::List(declared), BaseType::Named(_)) => type_accepts(declared, provided),
+        (BaseType::Named(_), BaseType::List(_)) => false,
+    }
+}
+
+// Compare the caller's variable types against the operation's variable definitions.
+pub(crate) fn variable_errors(
+    operation: &OperationDefinition,
+    variables_types: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for definition in &operation.variable_definitions {
+        let definition = &definition.node;
+        let name = definition.name.node.as_str();
+        let declared = &definition.var_type.node;
+        match variables_types.get(name) {
+            None if !declared.nullable && definition.default_value.is_none() => {
+                errors.push(format!(
+                    "Variable \"${name}\" of required type \"{declared}\" was not provided."
+                ));
+            }
+            None => {}
+            Some(provided) => match Type::new(provided) {
+                Some(provided) if type_accepts(declared, &provided) => {}
+                Some(provided) => errors.push(format!(
+                    "Variable \"${name}\" of type \"{provided}\" cannot be used as \
+                     \"{declared}\"."
+                )),
+                None => errors.push(format!(
+                    "Variable \"${name}\" has an invalid type \"{provided}\"."
+                )),
+            },
+        }
+    }
+    let mut undeclared: Vec<&String> = variables_types
+        .keys()
+        .filter(|name| {
+            !operation
+                .variable_definitions
+                .iter()
+                .any(|definition| definition.node.name.node.as_str() == name.as_str())
+        })
+        .collect();
+    undeclared.sort();
+    for name in undeclared {
+        errors.push(format!(
+            "Variable \"${name}\" is not defined by the operation."
+        ));
+    }
+    errors
+}
+
+// Selected result fields keyed by response name. Composite fields carry their sub-selection.
+enum Shape {
+    Leaf(String),
+    Composite(String, IndexMap<String, Shape>),
+}
+
+pub(crate) fn field_type(registry: &Registry, type_name: &str, field_name: &str) -> Option<String> {
+    match field_name {
+        "__typename" => Some("String!".to_string()),
+        "__schema" => Some("__Schema!".to_string()),
+        "__type" => Some("__Type".to_string()),
+        _ => registry
+            .types
+            .get(type_name)?
+            .field_by_name(field_name)
+            .map(|field| field.ty.clone()),
+    }
+}
+
+fn collect_shape(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    type_name: &str,
+    selection_set: &SelectionSet,
+    shape: &mut IndexMap<String, Shape>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let Some(ty) = field_type(registry, type_name, &field.name.node) else {
+                    continue;
+                };
+                let key = field.response_key().node.to_string();
+                if field.selection_set.node.items.is_empty() {
+                    shape.insert(key, Shape::Leaf(ty));
+                    continue;
+                }
+                let child_type = MetaTypeName::concrete_typename(&ty).to_string();
+                let entry = shape
+                    .entry(key)
+                    .or_insert_with(|| Shape::Composite(ty, IndexMap::new()));
+                if let Shape::Composite(_, fields) = entry {
+                    collect_shape(
+                        registry,
+                        doc,
+                        &child_type,
+                        &field.selection_set.node,
+                        fields,
+                    );
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.node.fragment_name.node.as_str();
+                if let Some(fragment) = doc.fragments.get(name) {
+                    let fragment = &fragment.node;
+                    let condition = fragment.type_condition.node.on.node.as_str();
+                    collect_shape(
+                        registry,
+                        doc,
+                        condition,
+                        &fragment.selection_set.node,
+                        shape,
+                    );
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment = &fragment.node;
+                let condition = fragment
+                    .type_condition
+                    .as_ref()
+                    .map_or(type_name, |condition| condition.node.on.node.as_str());
+                collect_shape(
+                    registry,
+                    doc,
+                    condition,
+                    &fragment.selection_set.node,
+                    shape,
+                );
+            }
+        }
+    }
+}
+
+fn shape_to_py<'py>(
+    py: Python<'py>,
+    shape: &IndexMap<String, Shape>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, entry) in shape {
+        match entry {
+            Shape::Leaf(ty) => dict.set_item(key, ty)?,
+            Shape::Composite(ty, fields) => {
+                let nested = PyDict::new(py);
+                nested.set_item("type", ty)?;
+                nested.set_item("fields", shape_to_py(py, fields)?)?;
+                dict.set_item(key, nested)?;
+            }
+        }
+    }
+    Ok(dict)
+}
+
+// Result shape of a validated operation: leaf fields map to their GraphQL type string, and
+// composite fields to `{"type": ..., "fields": {...}}`.
+pub(crate) fn operation_shape<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    operation: &OperationDefinition,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut shape = IndexMap::new();
+    if let Some(root) = root_type_name(registry, operation.ty) {
+        collect_shape(
+            registry,
+            doc,
+            root,
+            &operation.selection_set.node,
+            &mut shape,
+        );
+    }
+    shape_to_py(py, &shape)
+}
@@ -0,0 +1,207 @@
+use std::fmt::Write;
+
+use async_graphql::registry::{MetaType, MetaTypeName, Registry};
+
+const HEADER: &str = "# Generated by grommet from the live schema. Do not edit.\n\
+                      from __future__ import annotations\n";
+
+#[derive(Clone, Copy)]
+pub(crate) enum TypeStyle {
+    Dataclass,
+    TypedDict,
+}
+
+impl TypeStyle {
+    pub(crate) fn parse(style: &str) -> Option<Self> {
+        match style {
+            "dataclass" => Some(TypeStyle::Dataclass),
+            "typeddict" => Some(TypeStyle::TypedDict),
+            _ => None,
+        }
+    }
+}
+
+fn is_generated(name: &str, ty: &MetaType) -> bool {
+    !name.starts_with("__")
+        && matches!(
+            ty,
+            MetaType::Object { .. }
+                | MetaType::Interface { .. }
+                | MetaType::InputObject { .. }
+                | MetaType::Enum { .. }
+                | MetaType::Union { .. }
+        )
+}
+
+fn scalar_annotation(name: &str) -> &str {
+    match name {
+        "String" | "ID" => "str",
+        "Int" => "int",
+        "Float" => "float",
+        "Boolean" => "bool",
+        _ => name,
+    }
+}
+
+// Render a GraphQL type reference (e.g. `[Int!]`) as a Python annotation (`list[int] | None`).
+fn annotation(registry: &Registry, ty: &str) -> String {
+    let (inner, nullable) = match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(inner) => (inner, false),
+        _ => (ty, true),
+    };
+    let rendered = match MetaTypeName::create(inner) {
+        MetaTypeName::List(item) => format!("list[{}]", annotation(registry, item)),
+        _ => match registry.types.get(inner) {
+            Some(MetaType::Scalar { .. }) | None => {
+                let scalar = scalar_annotation(inner);
+                if scalar == inner {
+                    "Any".to_string()
+                } else {
+                    scalar.to_string()
+                }
+            }
+            Some(_) => inner.to_string(),
+        },
+    };
+    if nullable {
+        format!("{rendered} | None")
+    } else {
+        rendered
+    }
+}
+
+fn write_docstring(out: &mut String, description: Option<&str>) {
+    if let Some(description) = description {
+        let _ = writeln!(
+            out,
+            "    \"\"\"{}\"\"\"\n",
+            description.replace("\"\"\"", "\\\"\"\"")
+        );
+    }
+}
+
+fn write_class<'a>(
+    out: &mut String,
+    registry: &Registry,
+    style: TypeStyle,
+    name: &str,
+    description: Option<&str>,
+    fields: impl Iterator<Item = (&'a str, &'a str)>,
+) {
+    match style {
+        TypeStyle::Dataclass => {
+            let _ = writeln!(out, "\n\n@dataclasses.dataclass\nclass {name}:");
+        }
+        TypeStyle::TypedDict => {
+            let _ = writeln!(out, "\n\nclass {name}(TypedDict):");
+        }
+    }
+    write_docstring(out, description);
+    let mut empty = true;
+    for (field_name, ty) in fields {
+        if field_name.starts_with("__") {
+            continue;
+        }
+        empty = false;
+        let _ = writeln!(out, "    {field_name}: {}", annotation(registry, ty));
+    }
+    if empty && description.is_none() {
+        out.push_str("    pass\n");
+    }
+}
+
+// Build Python source declaring every object, interface, input, enum, and union type.
+pub(crate) fn generate_types(registry: &Registry, style: TypeStyle) -> String {
+    let mut body = String::new();
+    let mut uses_any = false;
+    let mut uses_enum = false;
+
+    for (name, ty) in &registry.types {
+        if !is_generated(name, ty) {
+            continue;
+        }
+        match ty {
+            MetaType::Object {
+                description,
+                fields,
+                ..
+            }
+            | MetaType::Interface {
+                description,
+                fields,
+                ..
+            } => write_class(
+                &mut body,
+                registry,
+                style,
+                name,
+                description.as_deref(),
+                fields
+                    .values()
+                    .map(|field| (field.name.as_str(), field.ty.as_str())),
+            ),
+            MetaType::InputObject {
+                description,
+                input_fields,
+                ..
+            } => write_class(
+                &mut body,
+                registry,
+                style,
+                name,
+                description.as_deref(),
+                input_fields
+                    .values()
+                    .map(|field| (field.name.as_str(), field.ty.as_str())),
+            ),
+            MetaType::Enum {
+                description,
+                enum_values,
+                ..
+            } => {
+                uses_enum = true;
+                let _ = writeln!(body, "\n\nclass {name}(enum.Enum):");
+                write_docstring(&mut body, description.as_deref());
+                for value in enum_values.keys() {
+                    let _ = writeln!(body, "    {value} = \"{value}\"");
+                }
+            }
+            MetaType::Union { possible_types, .. } => {
+                let members: Vec<&str> = possible_types.iter().map(String::as_str).collect();
+                let _ = writeln!(body, "\n\ntype {name} = {}", members.join(" | "));
+            }
+            MetaType::Scalar { .. } => {}
+        }
+        uses_any |= body.contains(": Any") || body.contains("[Any");
+    }
+
+    let mut imports = Vec::new();
+    if matches!(style, TypeStyle::Dataclass) {
+        imports.push("import dataclasses".to_string());
+    }
+    if uses_enum {
+        imports.push("import enum".to_string());
+    }
+    let mut typing = Vec::new();
+    if uses_any {
+        typing.push("Any");
+    }
+    if matches!(style, TypeStyle::TypedDict) {
+        typing.push("TypedDict");
+    }
+    if !typing.is_empty() {
+        if !imports.is_empty() {
+            imports.push(String::new());
+        }
+        imports.push(format!("from typing import {}", typing.join(", ")));
+    }
+
+    let mut out = String::from(HEADER);
+    if !imports.is_empty() {
+        out.push('\n');
+        out.push_str(&imports.join("\n"));
+        out.push('\n');
+    }
+    out.push_str(&body);
+    out
+}
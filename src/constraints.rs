@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_graphql::dynamic::ObjectAccessor;
+use async_graphql::{Error, Value};
+use pyo3::prelude::*;
+use pyo3::types::PyAnyMethods;
+use regex::Regex;
+
+use crate::errors::{py_err_to_error, py_value_error};
+use crate::types::ConvertCtx;
+use crate::types::PyObj;
+use crate::values::value_to_py_bound;
+
+// Declarative limits compiled from `grommet.Field` metadata. Length limits apply to strings and
+// lists; range and pattern limits apply to scalar values, including each item of a list.
+pub(crate) struct ValueConstraints {
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    pattern: Option<Regex>,
+}
+
+impl ValueConstraints {
+    fn from_py(payload: &Bound<'_, PyAny>) -> PyResult<Option<Self>> {
+        let constraints = payload.getattr("constraints")?;
+        if constraints.is_none() {
+            return Ok(None);
+        }
+        let pattern: Option<String> = constraints.getattr("pattern")?.extract()?;
+        let pattern = pattern
+            .map(|pattern| {
+                Regex::new(&pattern)
+                    .map_err(|err| py_value_error(format!("Invalid constraint pattern: {err}")))
+            })
+            .transpose()?;
+        Ok(Some(ValueConstraints {
+            min_length: constraints.getattr("min_length")?.extract()?,
+            max_length: constraints.getattr("max_length")?.extract()?,
+            minimum: constraints.getattr("minimum")?.extract()?,
+            maximum: constraints.getattr("maximum")?.extract()?,
+            pattern,
+        }))
+    }
+
+    fn check_length(&self, length: usize) -> Option<String> {
+        if let Some(min_length) = self.min_length
+            && length < min_length
+        {
+            return Some(format!("must have a length of at least {min_length}"));
+        }
+        if let Some(max_length) = self.max_length
+            && length > max_length
+        {
+            return Some(format!("must have a length of at most {max_length}"));
+        }
+        None
+    }
+
+    fn check_scalar(&self, value: &Value) -> Option<String> {
+        if let Value::String(text) = value
+            && let Some(message) = self.check_length(text.chars().count())
+        {
+            return Some(message);
+        }
+        self.check_item(value)
+    }
+
+    // Range and pattern limits only, for a scalar value or an item of a constrained list.
+    fn check_item(&self, value: &Value) -> Option<String> {
+        match value {
+            Value::String(text) => match &self.pattern {
+                Some(pattern) if !pattern.is_match(text) => {
+                    Some(format!("must match pattern \"{}\"", pattern.as_str()))
+                }
+                _ => None,
+            },
+            Value::Number(number) => {
+                let number = number.as_f64()?;
+                if let Some(minimum) = self.minimum
+                    && number < minimum
+                {
+                    return Some(format!("must be greater than or equal to {minimum}"));
+                }
+                if let Some(maximum) = self.maximum
+                    && number > maximum
+                {
+                    return Some(format!("must be less than or equal to {maximum}"));
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+// An argument or input object field, with the named input type it carries.
+struct ConstrainedInput {
+    name: String,
+    type_name: String,
+    constraints: Option<ValueConstraints>,
+}
+
+impl ConstrainedInput {
+    fn from_py(payload: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let mut spec = payload.getattr("type_spec")?;
+        while spec.getattr("kind")?.extract::<String>()? == "list" {
+            spec = spec.getattr("of_type")?;
+        }
+        Ok(ConstrainedInput {
+            name: payload.getattr("name")?.extract()?,
+            type_name: spec.getattr("name")?.extract()?,
+            constraints: ValueConstraints::from_py(payload)?,
+        })
+    }
+}
+
+// Constrained fields of every input object type that can carry a constraint, directly or
+// through nested input objects.
+pub(crate) struct InputConstraints {
+    types: HashMap<String, Vec<ConstrainedInput>>,
+}
+
+impl InputConstraints {
+    pub(crate) fn from_types(py: Python<'_>, types: &[Py<PyAny>]) -> PyResult<Self> {
+        let mut all_types: HashMap<String, Vec<ConstrainedInput>> = HashMap::new();
+        for compiled_type in types {
+            let compiled_type = compiled_type.bind(py);
+            let meta = compiled_type.getattr("meta")?;
+            if meta
+                .getattr("kind")?
+                .getattr("value")?
+                .extract::<String>()?
+                != "input"
+            {
+                continue;
+            }
+            let fields: Vec<Py<PyAny>> = compiled_type.getattr("input_fields")?.extract()?;
+            let fields = fields
+                .iter()
+                .map(|field| ConstrainedInput::from_py(field.bind(py)))
+                .collect::<PyResult<Vec<_>>>()?;
+            all_types.insert(meta.getattr("name")?.extract()?, fields);
+        }
+
+        let mut constrained: HashSet<String> = HashSet::new();
+        loop {
+            let before = constrained.len();
+            for (name, fields) in &all_types {
+                if fields.iter().any(|field| {
+                    field.constraints.is_some() || constrained.contains(&field.type_name)
+                }) {
+                    constrained.insert(name.clone());
+                }
+            }
+            if constrained.len() == before {
+                break;
+            }
+        }
+
+        all_types.retain(|name, _| constrained.contains(name));
+        Ok(InputConstraints { types: all_types })
+    }
+
+    fn is_constrained(&self, input: &ConstrainedInput) -> bool {
+        input.constraints.is_some() || self.types.contains_key(&input.type_name)
+    }
+
+    fn check_input(
+        &self,
+        input: &ConstrainedInput,
+        value: &Value,
+        path: &str,
+    ) -> Result<(), String> {
+        if let Some(constraints) = &input.constraints {
+            let message = match value {
+                Value::List(items) => constraints
+                    .check_length(items.len())
+                    .or_else(|| items.iter().find_map(|item| constraints.check_item(item))),
+                _ => constraints.check_scalar(value),
+            };
+            if let Some(message) = message {
+                return Err(invalid_argument(path, message));
+            }
+        }
+        if let Some(fields) = self.types.get(&input.type_name) {
+            self.check_input_object(fields, value, path)?;
+        }
+        Ok(())
+    }
+
+    fn check_input_object(
+        &self,
+        fields: &[ConstrainedInput],
+        value: &Value,
+        path: &str,
+    ) -> Result<(), String> {
+        match value {
+            Value::List(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check_input_object(fields, item, &format!("{path}[{index}]"))?;
+                }
+            }
+            Value::Object(object) => {
+                for field in fields {
+                    if let Some(field_value) = object.get(field.name.as_str()) {
+                        let field_path = format!("{path}.{}", field.name);
+                        self.check_input(field, field_value, &field_path)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// Connection-style arguments opt into the pagination rules by declaring an Int `maximum`.
+const PAGE_SIZE_ARGS: [&str; 3] = ["first", "last", "limit"];
+const OFFSET_ARG: &str = "offset";
+
+fn invalid_argument(path: &str, message: impl std::fmt::Display) -> String {
+    format!("Invalid value for argument \"{path}\": {message}")
+}
+
+// Per-field argument checks evaluated before the resolver is called: pagination rules first,
+// then declarative constraints, then any `validate` callables attached to individual arguments.
+pub(crate) struct ArgumentChecks {
+    args: Vec<ConstrainedInput>,
+    inputs: Arc<InputConstraints>,
+    validators: Vec<(String, PyObj)>,
+    page_sizes: Vec<(String, f64)>,
+    exclusive_pages: bool,
+}
+
+impl ArgumentChecks {
+    // Returns `None` when no argument is constrained or validated, keeping the resolver fast path.
+    pub(crate) fn from_args(
+        py: Python<'_>,
+        args: &[Py<PyAny>],
+        inputs: &Arc<InputConstraints>,
+    ) -> PyResult<Option<Self>> {
+        let mut constrained = Vec::new();
+        let mut validators = Vec::new();
+        let mut page_sizes = Vec::new();
+        let mut names = HashSet::new();
+        for arg in args {
+            let arg = arg.bind(py);
+            let validate = arg.getattr("validate")?;
+            let is_named = arg
+                .getattr("type_spec")?
+                .getattr("kind")?
+                .extract::<String>()?
+                == "named";
+            let mut arg = ConstrainedInput::from_py(arg)?;
+            names.insert(arg.name.clone());
+            // Paginated arguments never go below zero. Page sizes above the maximum are clamped
+            // to it, while offsets above it are rejected.
+            if is_named
+                && arg.type_name == "Int"
+                && (PAGE_SIZE_ARGS.contains(&arg.name.as_str()) || arg.name == OFFSET_ARG)
+                && let Some(constraints) = arg.constraints.as_mut()
+                && let Some(maximum) = constraints.maximum
+            {
+                constraints.minimum = Some(constraints.minimum.map_or(0.0, |min| min.max(0.0)));
+                if arg.name != OFFSET_ARG {
+                    constraints.maximum = None;
+                    page_sizes.push((arg.name.clone(), maximum));
+                }
+            }
+            if !validate.is_none() {
+                validators.push((arg.name.clone(), PyObj::new(validate.unbind())));
+            }
+            if inputs.is_constrained(&arg) {
+                constrained.push(arg);
+            }
+        }
+        if constrained.is_empty() && validators.is_empty() {
+            return Ok(None);
+        }
+        let exclusive_pages = names.contains("first")
+            && names.contains("last")
+            && page_sizes
+                .iter()
+                .any(|(name, _)| name == "first" || name == "last");
+        Ok(Some(ArgumentChecks {
+            args: constrained,
+            inputs: inputs.clone(),
+            validators,
+            page_sizes,
+            exclusive_pages,
+        }))
+    }
+
+    // The value a paginated page-size argument is clamped to, when it exceeds the maximum.
+    pub(crate) fn clamp(&self, name: &str, value: &Value) -> Option<Value> {
+        let (_, maximum) = self.page_sizes.iter().find(|(arg, _)| arg == name)?;
+        match value {
+            Value::Number(number) if number.as_f64()? > *maximum => {
+                Some(Value::from(*maximum as i64))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn validate(
+        &self,
+        args: &ObjectAccessor<'_>,
+        convert: &ConvertCtx<'_>,
+    ) -> Result<(), Error> {
+        if self.exclusive_pages {
+            let is_set = |name: &str| args.get(name).is_some_and(|value| !value.is_null());
+            if is_set("first") && is_set("last") {
+                return Err(Error::new(
+                    "Arguments \"first\" and \"last\" cannot be used together.",
+                ));
+            }
+        }
+        for arg in &self.args {
+            if let Some(value) = args.get(&arg.name) {
+                self.inputs
+                    .check_input(arg, value.as_value(), &arg.name)
+                    .map_err(Error::new)?;
+            }
+        }
+        if self.validators.is_empty() {
+            return Ok(());
+        }
+        Python::attach(|py| {
+            for (name, validator) in &self.validators {
+                let Some(value) = args.get(name) else {
+                    continue;
+                };
+                let value = value_to_py_bound(py, value.as_value(), convert.values)
+                    .map_err(py_err_to_error)?;
+                if let Err(err) = validator.bind(py).call1((value,)) {
+                    return Err(Error::new(invalid_argument(name, err.value(py))));
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::{BTreeMap, HashMap};
+
+use async_graphql::parser::types::{ExecutableDocument, OperationDefinition};
+use async_graphql::registry::Registry;
+
+use crate::document::operation_field_coordinates;
+
+// Counts of the field coordinates selected by executed operations, kept while coverage is
+// being recorded.
+#[derive(Default)]
+pub(crate) struct Coverage {
+    hits: Option<HashMap<String, usize>>,
+}
+
+impl Coverage {
+    // Enabling starts a fresh recording; disabling discards it.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.hits = enabled.then(HashMap::new);
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.hits.is_some()
+    }
+
+    // Records the fields selected by the operation a request runs.
+    pub(crate) fn record(
+        &mut self,
+        registry: &Registry,
+        doc: &ExecutableDocument,
+        operation: &OperationDefinition,
+    ) {
+        let Some(hits) = self.hits.as_mut() else {
+            return;
+        };
+        for coordinate in operation_field_coordinates(registry, doc, operation) {
+            let key = format!("{}.{}", coordinate.type_name, coordinate.field_name);
+            *hits.entry(key).or_default() += 1;
+        }
+    }
+
+    // Hits for every field of the schema's object and interface types, zero for fields no
+    // operation selected. `None` when coverage is not being recorded.
+    pub(crate) fn report(&self, registry: &Registry) -> Option<BTreeMap<String, usize>> {
+        let hits = self.hits.as_ref()?;
+        let mut report = BTreeMap::new();
+        for (type_name, ty) in &registry.types {
+            let Some(fields) = ty.fields().filter(|_| !type_name.starts_with("__")) else {
+                continue;
+            };
+            for field_name in fields.keys().filter(|name| !name.starts_with("__")) {
+                let key = format!("{type_name}.{field_name}");
+                let count = hits.get(&key).copied().unwrap_or_default();
+                report.insert(key, count);
+            }
+        }
+        Some(report)
+    }
+}
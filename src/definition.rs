@@ -0,0 +1,415 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::schema_types::decode_type_kind;
+
+const BUILTIN_SCALARS: [&str; 5] = ["Boolean", "Float", "ID", "Int", "String"];
+
+#[derive(Clone, Copy)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+// Where a type reference appears, deciding which kinds of types it may name.
+#[derive(Clone, Copy)]
+enum Usage {
+    Output,
+    Input,
+}
+
+struct Problem {
+    path: String,
+    message: String,
+    severity: Severity,
+}
+
+// One compiled type of the bundle, read leniently so that a malformed entry is reported rather
+// than aborting the whole check.
+struct DefinedType<'py> {
+    path: String,
+    name: String,
+    kind: String,
+    compiled: Bound<'py, PyAny>,
+}
+
+#[derive(Default)]
+struct Report {
+    problems: Vec<Problem>,
+}
+
+impl Report {
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(path.into(), message.into(), Severity::Error);
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(path.into(), message.into(), Severity::Warning);
+    }
+
+    fn push(&mut self, path: String, message: String, severity: Severity) {
+        self.problems.push(Problem {
+            path,
+            message,
+            severity,
+        });
+    }
+
+    fn has_errors(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| matches!(problem.severity, Severity::Error))
+    }
+}
+
+fn read_type<'py>(path: String, compiled: Bound<'py, PyAny>) -> PyResult<DefinedType<'py>> {
+    let meta = compiled.getattr("meta")?;
+    Ok(DefinedType {
+        path,
+        name: meta.getattr("name")?.extract()?,
+        kind: decode_type_kind(&meta)?,
+        compiled,
+    })
+}
+
+fn named_type(spec: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    let kind: String = spec.getattr("kind")?.extract()?;
+    if kind == "list" {
+        return named_type(&spec.getattr("of_type")?);
+    }
+    spec.getattr("name")?.extract()
+}
+
+struct Checker<'a, 'py> {
+    kinds: HashMap<&'a str, &'a str>,
+    types: &'a [DefinedType<'py>],
+    report: Report,
+}
+
+impl<'py> Checker<'_, 'py> {
+    fn kind_of(&self, name: &str) -> Option<&str> {
+        if BUILTIN_SCALARS.contains(&name) {
+            return Some("scalar");
+        }
+        self.kinds.get(name).copied()
+    }
+
+    fn check_reference(&mut self, path: String, spec: &Bound<'py, PyAny>, usage: Usage) {
+        let name = match named_type(spec) {
+            Ok(Some(name)) => name,
+            Ok(None) => return self.report.error(path, "Type reference has no name."),
+            Err(err) => return self.report.error(path, err.to_string()),
+        };
+        let allowed: &[&str] = match usage {
+            Usage::Output => &["scalar", "object", "interface", "union"],
+            Usage::Input => &["scalar", "input"],
+        };
+        match self.kind_of(&name) {
+            None => self.report.error(path, format!("Unknown type \"{name}\".")),
+            Some(kind) if !allowed.contains(&kind) => {
+                let usage = match usage {
+                    Usage::Output => "an output",
+                    Usage::Input => "an input",
+                };
+                self.report.error(
+                    path,
+                    format!("Type \"{name}\" has kind {kind}, which cannot be {usage}."),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn check_fields(&mut self, ty: &DefinedType<'py>, attr: &str) -> PyResult<()> {
+        let fields: Vec<Bound<'py, PyAny>> = ty.compiled.getattr(attr)?.extract()?;
+        if fields.is_empty() {
+            self.report.error(
+                format!("{}.{attr}", ty.path),
+                format!("Type \"{}\" must define at least one field.", ty.name),
+            );
+        }
+        let usage = if ty.kind == "input" {
+            Usage::Input
+        } else {
+            Usage::Output
+        };
+        for (index, field) in fields.iter().enumerate() {
+            let path = format!("{}.{attr}[{index}]", ty.path);
+            self.check_reference(
+                format!("{path}.type_spec"),
+                &field.getattr("type_spec")?,
+                usage,
+            );
+            if !field.hasattr("args")? {
+                continue;
+            }
+            let args: Vec<Bound<'py, PyAny>> = field.getattr("args")?.extract()?;
+            for (index, arg) in args.iter().enumerate() {
+                self.check_reference(
+                    format!("{path}.args[{index}].type_spec"),
+                    &arg.getattr("type_spec")?,
+                    Usage::Input,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn check_members(&mut self, ty: &DefinedType<'py>, attr: &str, expected: &str) -> PyResult<()> {
+        let members: Vec<String> = ty.compiled.getattr(attr)?.extract()?;
+        if attr == "possible_types" && members.is_empty() {
+            self.report.error(
+                format!("{}.{attr}", ty.path),
+                format!(
+                    "Union \"{}\" must contain at least one object type.",
+                    ty.name
+                ),
+            );
+        }
+        for (index, member) in members.iter().enumerate() {
+            if self.kind_of(member) != Some(expected) {
+                self.report.error(
+                    format!("{}.{attr}[{index}]", ty.path),
+                    format!("\"{member}\" is not a registered {expected} type."),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // Fields of the interfaces `ty` implements that it does not define.
+    fn check_implementations(&mut self, ty: &DefinedType<'py>) -> PyResult<()> {
+        let field_names = |ty: &DefinedType<'py>| -> PyResult<HashSet<String>> {
+            let fields: Vec<Bound<'py, PyAny>> = ty.compiled.getattr("object_fields")?.extract()?;
+            fields
+                .iter()
+                .map(|field| field.getattr("name")?.extract())
+                .collect()
+        };
+        let defined = field_names(ty)?;
+        let implements: Vec<String> = ty.compiled.getattr("implements")?.extract()?;
+        for (index, interface) in implements.iter().enumerate() {
+            let Some(definition) = self
+                .types
+                .iter()
+                .find(|other| &other.name == interface && other.kind == "interface")
+            else {
+                continue;
+            };
+            let mut missing: Vec<String> = field_names(definition)?
+                .difference(&defined)
+                .cloned()
+                .collect();
+            missing.sort();
+            let name = &ty.name;
+            for field in missing {
+                self.report.error(
+                    format!("{}.implements[{index}]", ty.path),
+                    format!(
+                        "Type \"{name}\" does not define field \"{field}\" of interface \
+                         \"{interface}\"."
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn check_type(&mut self, ty: &DefinedType<'py>) -> PyResult<()> {
+        match ty.kind.as_str() {
+            "object" | "interface" => {
+                self.check_fields(ty, "object_fields")?;
+                self.check_members(ty, "implements", "interface")?;
+                self.check_implementations(ty)
+            }
+            "subscription" => self.check_fields(ty, "subscription_fields"),
+            "input" => self.check_fields(ty, "input_fields"),
+            "union" => self.check_members(ty, "possible_types", "object"),
+            kind => {
+                self.report
+                    .error(ty.path.clone(), format!("Unknown type kind \"{kind}\"."));
+                Ok(())
+            }
+        }
+    }
+
+    fn check_root(&mut self, path: &str, name: Option<String>, expected: &str) {
+        let Some(name) = name else {
+            return;
+        };
+        match self.kind_of(&name) {
+            Some(kind) if kind == expected => {}
+            Some(kind) => self.report.error(
+                path,
+                format!("Root type \"{name}\" has kind {kind}, expected {expected}."),
+            ),
+            None => self
+                .report
+                .error(path, format!("Root type \"{name}\" is not defined.")),
+        }
+    }
+
+    // Types that no root reaches through fields, arguments, union members, or implementations.
+    fn unreachable(&self, roots: &[String]) -> PyResult<Vec<&DefinedType<'py>>> {
+        let mut references: HashMap<String, Vec<String>> = HashMap::new();
+        for ty in self.types {
+            let mut named = Vec::new();
+            let attrs: &[&str] = match ty.kind.as_str() {
+                "union" => &[],
+                _ => &["object_fields", "subscription_fields", "input_fields"],
+            };
+            for attr in attrs {
+                let fields: Vec<Bound<'py, PyAny>> = ty.compiled.getattr(attr)?.extract()?;
+                for field in fields {
+                    named.extend(named_type(&field.getattr("type_spec")?)?);
+                    if field.hasattr("args")? {
+                        let args: Vec<Bound<'py, PyAny>> = field.getattr("args")?.extract()?;
+                        for arg in args {
+                            named.extend(named_type(&arg.getattr("type_spec")?)?);
+                        }
+                    }
+                }
+            }
+            if ty.kind == "union" {
+                named.extend(
+                    ty.compiled
+                        .getattr("possible_types")?
+                        .extract::<Vec<String>>()?,
+                );
+            } else {
+                // Implementations are reachable from the interfaces they implement.
+                let implements: Vec<String> = ty.compiled.getattr("implements")?.extract()?;
+                for interface in implements {
+                    references
+                        .entry(interface)
+                        .or_default()
+                        .push(ty.name.clone());
+                }
+            }
+            references.entry(ty.name.clone()).or_default().extend(named);
+        }
+
+        let mut reached: HashSet<String> = HashSet::new();
+        let mut pending: VecDeque<String> = roots.iter().cloned().collect();
+        while let Some(name) = pending.pop_front() {
+            if let Some(next) = references.get(&name)
+                && !reached.contains(&name)
+            {
+                pending.extend(next.iter().cloned());
+            }
+            reached.insert(name);
+        }
+        Ok(self
+            .types
+            .iter()
+            .filter(|ty| !reached.contains(&ty.name))
+            .collect())
+    }
+}
+
+// Problems with a schema bundle, found without building a schema: malformed entries, duplicate
+// and unknown types, type references of the wrong kind, interface fields missing from their
+// implementations, and root types of the wrong kind as errors; types no root reaches as
+// warnings.
+#[pyfunction]
+pub(crate) fn validate_definition<'py>(
+    py: Python<'py>,
+    bundle: &Bound<'py, PyAny>,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let mut report = Report::default();
+    let mut types = Vec::new();
+    match bundle
+        .getattr("types")
+        .and_then(|types| types.extract::<Vec<Bound<'py, PyAny>>>())
+    {
+        Ok(compiled_types) => {
+            for (index, compiled) in compiled_types.into_iter().enumerate() {
+                let path = format!("types[{index}]");
+                match read_type(path.clone(), compiled) {
+                    Ok(ty) => types.push(ty),
+                    Err(err) => report.error(path, format!("Malformed type entry: {err}")),
+                }
+            }
+        }
+        Err(err) => report.error("types", format!("Malformed type list: {err}")),
+    }
+
+    let mut kinds = HashMap::new();
+    for ty in &types {
+        if kinds.insert(ty.name.as_str(), ty.kind.as_str()).is_some() {
+            report.error(
+                format!("{}.meta.name", ty.path),
+                format!("Type \"{}\" is defined more than once.", ty.name),
+            );
+        }
+    }
+    let mut checker = Checker {
+        kinds,
+        types: &types,
+        report,
+    };
+    for ty in &types {
+        if let Err(err) = checker.check_type(ty) {
+            checker
+                .report
+                .error(ty.path.clone(), format!("Malformed type entry: {err}"));
+        }
+    }
+
+    let mut roots = Vec::new();
+    for (attr, expected) in [
+        ("query", "object"),
+        ("mutation", "object"),
+        ("subscription", "subscription"),
+    ] {
+        match bundle
+            .getattr(attr)
+            .and_then(|name| name.extract::<Option<String>>())
+        {
+            Ok(name) => {
+                if attr == "query" && name.is_none() {
+                    checker.report.error(attr, "A query root type is required.");
+                }
+                roots.extend(name.clone());
+                checker.check_root(attr, name, expected);
+            }
+            Err(err) => checker
+                .report
+                .error(attr, format!("Malformed root type: {err}")),
+        }
+    }
+    if !checker.report.has_errors() {
+        let unreachable: Vec<(String, String)> = checker
+            .unreachable(&roots)?
+            .into_iter()
+            .map(|ty| (ty.path.clone(), ty.name.clone()))
+            .collect();
+        for (path, name) in unreachable {
+            checker.report.warning(
+                path,
+                format!("Type \"{name}\" is not reachable from the root types."),
+            );
+        }
+    }
+
+    let report = checker.report;
+    report
+        .problems
+        .into_iter()
+        .map(|problem| {
+            let entry = PyDict::new(py);
+            entry.set_item("path", problem.path)?;
+            entry.set_item("message", problem.message)?;
+            entry.set_item(
+                "severity",
+                match problem.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+            )?;
+            Ok(entry)
+        })
+        .collect()
+}
@@ -0,0 +1,228 @@
+use std::borrow::Cow;
+
+use async_graphql::{Name, Value};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::response::ResponseHandle;
+use crate::values::{
+    ExecutionResult, OperationResult, ValueSettings, payload_to_value, value_to_py_bound,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+// Dotted paths left out of a comparison, e.g. `users.*.updatedAt`: `*` matches any key or list
+// index, and ignoring a path ignores everything under it.
+pub(crate) struct IgnorePaths(Vec<Vec<String>>);
+
+impl IgnorePaths {
+    pub(crate) fn new(paths: &[String]) -> Self {
+        IgnorePaths(
+            paths
+                .iter()
+                .map(|path| path.split('.').map(str::to_owned).collect())
+                .collect(),
+        )
+    }
+
+    fn contains(&self, path: &[Segment]) -> bool {
+        self.0.iter().any(|pattern| {
+            pattern.len() == path.len()
+                && pattern.iter().zip(path).all(|(pattern, segment)| {
+                    pattern == "*"
+                        || match segment {
+                            Segment::Key(key) => pattern == key,
+                            Segment::Index(index) => pattern.parse() == Ok(*index),
+                        }
+                })
+        })
+    }
+}
+
+// A value that differs between two responses: `left` or `right` is `None` when only the other
+// response has a value at `path`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Difference<'a> {
+    pub(crate) path: Vec<Segment>,
+    pub(crate) left: Option<&'a Value>,
+    pub(crate) right: Option<&'a Value>,
+}
+
+// The differences between two response trees in document order of the left one, with values
+// present only in the right one after the left one's keys. Objects compare by key regardless of
+// key order and lists by index.
+pub(crate) fn response_differences<'a>(
+    left: &'a Value,
+    right: &'a Value,
+    ignore: &IgnorePaths,
+) -> Vec<Difference<'a>> {
+    let mut differences = Vec::new();
+    compare(
+        Some(left),
+        Some(right),
+        &mut Vec::new(),
+        ignore,
+        &mut differences,
+    );
+    differences
+}
+
+fn compare<'a>(
+    left: Option<&'a Value>,
+    right: Option<&'a Value>,
+    path: &mut Vec<Segment>,
+    ignore: &IgnorePaths,
+    differences: &mut Vec<Difference<'a>>,
+) {
+    if ignore.contains(path) {
+        return;
+    }
+    match (left, right) {
+        (Some(Value::Object(left)), Some(Value::Object(right))) => {
+            for (key, value) in left {
+                path.push(Segment::Key(key.to_string()));
+                compare(Some(value), right.get(key), path, ignore, differences);
+                path.pop();
+            }
+            for (key, value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                path.push(Segment::Key(key.to_string()));
+                compare(None, Some(value), path, ignore, differences);
+                path.pop();
+            }
+        }
+        (Some(Value::List(left)), Some(Value::List(right))) => {
+            for index in 0..left.len().max(right.len()) {
+                path.push(Segment::Index(index));
+                compare(left.get(index), right.get(index), path, ignore, differences);
+                path.pop();
+            }
+        }
+        (left, right) if left == right => {}
+        (left, right) => differences.push(Difference {
+            path: path.clone(),
+            left,
+            right,
+        }),
+    }
+}
+
+// What a response is compared on: its data and, for executed responses, the messages of its
+// errors in order.
+struct Compared<'a> {
+    data: Cow<'a, Value>,
+    errors: Option<Vec<Value>>,
+}
+
+// The response data behind a `ResponseHandle`, without converting it, the data of an
+// `OperationResult` or `ExecutionResult` converted to a value tree, or a Python payload
+// converted as a whole.
+fn compared<'a>(py: Python<'_>, response: &'a Bound<'_, PyAny>) -> PyResult<Compared<'a>> {
+    if let Ok(handle) = response.cast::<ResponseHandle>() {
+        let response = handle.get().response();
+        let messages = response
+            .errors
+            .iter()
+            .map(|error| Value::String(error.message.clone()))
+            .collect();
+        return Ok(Compared {
+            data: Cow::Borrowed(&response.data),
+            errors: Some(messages),
+        });
+    }
+    if response.is_instance_of::<OperationResult>() || response.is_instance_of::<ExecutionResult>()
+    {
+        let data = payload_to_value(py, &response.getattr("data")?, ValueSettings::default())?;
+        return Ok(Compared {
+            data: Cow::Owned(data),
+            errors: Some(error_messages(&response.getattr("errors")?)?),
+        });
+    }
+    Ok(Compared {
+        data: Cow::Owned(payload_to_value(py, response, ValueSettings::default())?),
+        errors: None,
+    })
+}
+
+// The messages of a result's errors, given as dicts or `GraphQLError`s.
+fn error_messages(errors: &Bound<'_, PyAny>) -> PyResult<Vec<Value>> {
+    if errors.is_none() {
+        return Ok(Vec::new());
+    }
+    let mut messages = Vec::new();
+    for error in errors.try_iter()? {
+        let error = error?;
+        let message = match error.cast::<PyDict>() {
+            Ok(error) => error.as_any().get_item("message")?,
+            Err(_) => error.getattr("message")?,
+        };
+        messages.push(Value::String(message.extract()?));
+    }
+    Ok(messages)
+}
+
+// Deep-compares two responses' data, given as `ResponseHandle`s, results or plain payloads, and
+// returns their differences as `{"path", "left", "right", "missing"}` dicts. `missing` names the
+// side without a value at the path, whose entry is `None`, so missing values are told apart from
+// nulls. When both sides are handles or results, their error messages are compared too, at
+// paths starting with `errors`. Paths in `ignore_paths` are dotted, with `*` matching any key
+// or index.
+#[pyfunction]
+#[pyo3(signature = (a, b, ignore_paths=None))]
+pub(crate) fn compare_responses<'py>(
+    py: Python<'py>,
+    a: &Bound<'py, PyAny>,
+    b: &Bound<'py, PyAny>,
+    ignore_paths: Option<Vec<String>>,
+) -> PyResult<Bound<'py, PyList>> {
+    let (left, right) = (compared(py, a)?, compared(py, b)?);
+    let ignore = IgnorePaths::new(&ignore_paths.unwrap_or_default());
+    // Error messages are compared when both sides carry them, under a leading `errors` key.
+    let errors = match (left.errors, right.errors) {
+        (Some(left), Some(right)) => {
+            let tree =
+                |messages| Value::Object([(Name::new("errors"), Value::List(messages))].into());
+            Some((tree(left), tree(right)))
+        }
+        _ => None,
+    };
+    let differences = py.detach(|| {
+        let mut differences = response_differences(&left.data, &right.data, &ignore);
+        if let Some((left, right)) = &errors {
+            differences.extend(response_differences(left, right, &ignore));
+        }
+        differences
+    });
+    let entries = PyList::empty(py);
+    for difference in differences {
+        let entry = PyDict::new(py);
+        let path = PyList::empty(py);
+        for segment in difference.path {
+            match segment {
+                Segment::Key(key) => path.append(key)?,
+                Segment::Index(index) => path.append(index)?,
+            }
+        }
+        entry.set_item("path", path)?;
+        let side = |value: Option<&Value>| {
+            value
+                .map(|value| value_to_py_bound(py, value, ValueSettings::default()))
+                .transpose()
+        };
+        entry.set_item("left", side(difference.left)?)?;
+        entry.set_item("right", side(difference.right)?)?;
+        entry.set_item(
+            "missing",
+            match (difference.left, difference.right) {
+                (None, _) => Some("left"),
+                (_, None) => Some("right"),
+                _ => None,
+            },
+        )?;
+        entries.append(entry)?;
+    }
+    Ok(entries)
+}
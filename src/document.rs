@@ -0,0 +1,537 @@
+use std::collections::HashSet;
+
+use async_graphql::parser::types::{
+    ExecutableDocument, OperationDefinition, OperationType, Selection, SelectionSet,
+};
+use async_graphql::registry::{MetaType, MetaTypeName, Registry};
+use async_graphql::{Name, Pos, Positioned, ServerError, Value};
+
+use crate::errors::multiple_subscription_root_fields;
+
+// A schema field selected by an operation, keyed by its parent type.
+pub(crate) struct FieldCoordinate {
+    pub(crate) type_name: String,
+    pub(crate) field_name: String,
+    pub(crate) pos: Pos,
+}
+
+pub(crate) fn root_type_name(registry: &Registry, ty: OperationType) -> Option<&str> {
+    match ty {
+        OperationType::Query => Some(registry.query_type.as_str()),
+        OperationType::Mutation => registry.mutation_type.as_deref(),
+        OperationType::Subscription => registry.subscription_type.as_deref(),
+    }
+}
+
+struct CoordinateWalker<'a> {
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    visited_fragments: HashSet<String>,
+    coordinates: Vec<FieldCoordinate>,
+}
+
+impl CoordinateWalker<'_> {
+    fn walk(&mut self, type_name: &str, selection_set: &SelectionSet) {
+        let doc = self.doc;
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let field_name = field.node.name.node.as_str();
+                    // Introspection and __typename selections are not schema fields.
+                    if field_name.starts_with("__") {
+                        continue;
+                    }
+                    self.coordinates.push(FieldCoordinate {
+                        type_name: type_name.to_string(),
+                        field_name: field_name.to_string(),
+                        pos: field.pos,
+                    });
+                    let child_type = self
+                        .registry
+                        .types
+                        .get(type_name)
+                        .and_then(|ty| ty.field_by_name(field_name))
+                        .map(|meta| MetaTypeName::concrete_typename(&meta.ty).to_string());
+                    if let Some(child_type) = child_type {
+                        self.walk(&child_type, &field.node.selection_set.node);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if !self.visited_fragments.insert(name.to_string()) {
+                        continue;
+                    }
+                    if let Some(fragment) = doc.fragments.get(name) {
+                        self.walk(
+                            &fragment.node.type_condition.node.on.node,
+                            &fragment.node.selection_set.node,
+                        );
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    let fragment_type = inline
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map_or(type_name, |condition| condition.node.on.node.as_str());
+                    self.walk(fragment_type, &inline.node.selection_set.node);
+                }
+            }
+        }
+    }
+}
+
+// The name and root field names of the operation a document runs, so logs and metrics can be
+// grouped by operation. Root fields are named as in the schema rather than by their aliases.
+#[derive(Clone)]
+pub(crate) struct OperationSummary {
+    pub(crate) name: Option<String>,
+    pub(crate) root_fields: Vec<String>,
+}
+
+// The operation a request runs: the one named `operation_name`, or the document's only one.
+pub(crate) fn select_operation<'a>(
+    doc: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<(Option<&'a Name>, &'a Positioned<OperationDefinition>)> {
+    let mut operations = doc.operations.iter();
+    match operation_name {
+        Some(operation_name) => {
+            operations.find(|(name, _)| name.is_some_and(|name| name.as_str() == operation_name))
+        }
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => Some(operation),
+            _ => None,
+        },
+    }
+}
+
+// Summarizes the operation a request runs, see `select_operation`.
+pub(crate) fn summarize_operation(
+    doc: &ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<OperationSummary> {
+    let (name, operation) = select_operation(doc, operation_name)?;
+    let mut root_fields = Vec::new();
+    collect_root_fields(
+        doc,
+        &operation.node.selection_set.node,
+        &mut HashSet::new(),
+        &mut root_fields,
+    );
+    Some(OperationSummary {
+        name: name.map(ToString::to_string),
+        root_fields,
+    })
+}
+
+fn collect_root_fields(
+    doc: &ExecutableDocument,
+    selection_set: &SelectionSet,
+    visited_fragments: &mut HashSet<String>,
+    fields: &mut Vec<String>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let name = field.node.name.node.as_str();
+                if !name.starts_with("__") && !fields.iter().any(|seen| seen == name) {
+                    fields.push(name.to_string());
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.node.fragment_name.node.as_str();
+                if let Some(fragment) = doc.fragments.get(name)
+                    && visited_fragments.insert(name.to_string())
+                {
+                    let selection_set = &fragment.node.selection_set.node;
+                    collect_root_fields(doc, selection_set, visited_fragments, fields);
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                let selection_set = &inline.node.selection_set.node;
+                collect_root_fields(doc, selection_set, visited_fragments, fields);
+            }
+        }
+    }
+}
+
+// Subscription operations must select exactly one root field, counted by response key once
+// fragments are expanded, so that each subscription is a single stream of events.
+pub(crate) fn subscription_root_field_errors(doc: &ExecutableDocument) -> Vec<ServerError> {
+    let mut errors = Vec::new();
+    for (name, operation) in doc.operations.iter() {
+        if operation.node.ty != OperationType::Subscription {
+            continue;
+        }
+        let mut keys = Vec::new();
+        collect_response_keys(
+            doc,
+            &operation.node.selection_set.node,
+            &mut HashSet::new(),
+            &mut keys,
+        );
+        if keys.len() > 1 {
+            let name = name.map(|name| name.as_str());
+            errors.push(multiple_subscription_root_fields(
+                name,
+                &keys,
+                operation.pos,
+            ));
+        }
+    }
+    errors
+}
+
+fn collect_response_keys(
+    doc: &ExecutableDocument,
+    selection_set: &SelectionSet,
+    visited_fragments: &mut HashSet<String>,
+    keys: &mut Vec<String>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let key = field.node.response_key().node.as_str();
+                if !keys.iter().any(|seen| seen == key) {
+                    keys.push(key.to_string());
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.node.fragment_name.node.as_str();
+                if let Some(fragment) = doc.fragments.get(name)
+                    && visited_fragments.insert(name.to_string())
+                {
+                    let selection_set = &fragment.node.selection_set.node;
+                    collect_response_keys(doc, selection_set, visited_fragments, keys);
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                let selection_set = &inline.node.selection_set.node;
+                collect_response_keys(doc, selection_set, visited_fragments, keys);
+            }
+        }
+    }
+}
+
+// Collect every schema field coordinate selected by the document's operations. Unknown types
+// and fields are still reported (without descending into them) so callers see what was asked for.
+pub(crate) fn collect_field_coordinates(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+) -> Vec<FieldCoordinate> {
+    doc.operations
+        .iter()
+        .flat_map(|(_name, operation)| operation_field_coordinates(registry, doc, &operation.node))
+        .collect()
+}
+
+// Collect the schema field coordinates selected by one operation of the document.
+pub(crate) fn operation_field_coordinates(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    operation: &OperationDefinition,
+) -> Vec<FieldCoordinate> {
+    let mut walker = CoordinateWalker {
+        registry,
+        doc,
+        visited_fragments: HashSet::new(),
+        coordinates: Vec::new(),
+    };
+    if let Some(root) = root_type_name(registry, operation.ty) {
+        walker.walk(root, &operation.selection_set.node);
+    }
+    walker.coordinates
+}
+
+// Adds where input coercion failed to the errors the engine reports it at an argument: the
+// `argument`, the `input_path` of keys and indices below it where the value does not coerce to
+// the argument's type, and when the failing value was passed through a variable, the `variable`
+// and the `variable_path` within it. Errors at a value that uses a variable the request does not
+// provide name it as `variable`.
+pub(crate) fn describe_input_errors(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: Option<&Value>,
+    errors: &mut [ServerError],
+) {
+    let provided = |name: &str| {
+        match variables {
+            Some(Value::Object(variables)) => variables.get(name).cloned(),
+            _ => None,
+        }
+        .or_else(|| {
+            let definitions = doc
+                .operations
+                .iter()
+                .flat_map(|(_name, operation)| operation.node.variable_definitions.iter());
+            definitions
+                .filter(|definition| definition.node.name.node.as_str() == name)
+                .find_map(|definition| definition.node.default_value())
+                .cloned()
+        })
+    };
+    for error in errors {
+        if !error.path.is_empty() {
+            continue;
+        }
+        let Some(&pos) = error.locations.first() else {
+            continue;
+        };
+        let Some(argument) = argument_at(registry, doc, pos) else {
+            continue;
+        };
+        if argument.value_pos == pos && argument.name.pos != pos {
+            if let Some(missing) = argument
+                .variables()
+                .into_iter()
+                .find(|name| provided(name).is_none())
+            {
+                let extensions = error.extensions.get_or_insert_with(Default::default);
+                extensions.set("variable", missing);
+            }
+            continue;
+        }
+        let Some(input_path) = argument
+            .value(&provided)
+            .and_then(|value| invalid_input_path(registry, argument.ty, &value))
+        else {
+            continue;
+        };
+        let variable = variable_at(&argument.marked, &input_path);
+        let extensions = error.extensions.get_or_insert_with(Default::default);
+        extensions.set("argument", argument.name.node.as_str());
+        extensions.set("input_path", Value::List(input_path));
+        if let Some((variable, variable_path)) = variable {
+            extensions.set("variable", variable);
+            extensions.set("variable_path", Value::List(variable_path));
+        }
+    }
+}
+
+// An argument given in the document, with its declared type and its value, each variable it
+// uses replaced by an enum value named `$<variable>`, which no document can spell, so they can be
+// told apart.
+struct ArgumentAt<'a> {
+    name: &'a Positioned<Name>,
+    value_pos: Pos,
+    marked: Value,
+    ty: &'a str,
+}
+
+impl ArgumentAt<'_> {
+    fn variables(&self) -> Vec<&str> {
+        fn collect<'v>(value: &'v Value, names: &mut Vec<&'v str>) {
+            match value {
+                Value::Enum(name) => names.extend(name.strip_prefix('$')),
+                Value::List(items) => items.iter().for_each(|item| collect(item, names)),
+                Value::Object(fields) => fields.values().for_each(|field| collect(field, names)),
+                _ => {}
+            }
+        }
+        let mut names = Vec::new();
+        collect(&self.marked, &mut names);
+        names
+    }
+
+    // The value as the engine coerced it, with the variables the request provides.
+    fn value(&self, provided: &impl Fn(&str) -> Option<Value>) -> Option<Value> {
+        fn substitute(value: &Value, provided: &impl Fn(&str) -> Option<Value>) -> Option<Value> {
+            Some(match value {
+                Value::Enum(name) => match name.strip_prefix('$') {
+                    Some(variable) => provided(variable)?,
+                    None => value.clone(),
+                },
+                Value::List(items) => Value::List(
+                    items
+                        .iter()
+                        .map(|item| substitute(item, provided))
+                        .collect::<Option<_>>()?,
+                ),
+                Value::Object(fields) => Value::Object(
+                    fields
+                        .iter()
+                        .map(|(key, field)| Some((key.clone(), substitute(field, provided)?)))
+                        .collect::<Option<_>>()?,
+                ),
+                _ => value.clone(),
+            })
+        }
+        substitute(&self.marked, provided)
+    }
+}
+
+// The field or directive argument whose name or value is at `pos`.
+fn argument_at<'a>(
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    pos: Pos,
+) -> Option<ArgumentAt<'a>> {
+    let operations = doc.operations.iter().filter_map(|(_name, operation)| {
+        let root = root_type_name(registry, operation.node.ty)?;
+        Some((root, &operation.node.selection_set.node))
+    });
+    let fragments = doc.fragments.values().map(|fragment| {
+        let on = fragment.node.type_condition.node.on.node.as_str();
+        (on, &fragment.node.selection_set.node)
+    });
+    operations
+        .chain(fragments)
+        .find_map(|(type_name, selection_set)| argument_in(registry, type_name, selection_set, pos))
+}
+
+fn argument_in<'a>(
+    registry: &'a Registry,
+    type_name: &'a str,
+    selection_set: &'a SelectionSet,
+    pos: Pos,
+) -> Option<ArgumentAt<'a>> {
+    selection_set.items.iter().find_map(|selection| {
+        let (field_arguments, directives, nested) = match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let meta = registry
+                    .types
+                    .get(type_name)
+                    .and_then(|ty| ty.field_by_name(&field.name.node));
+                (
+                    meta.map(|meta| (&meta.args, field.arguments.as_slice())),
+                    &field.directives,
+                    meta.map(|meta| {
+                        let child = MetaTypeName::concrete_typename(&meta.ty);
+                        (child, &field.selection_set.node)
+                    }),
+                )
+            }
+            Selection::InlineFragment(inline) => {
+                let inline = &inline.node;
+                let on = inline
+                    .type_condition
+                    .as_ref()
+                    .map_or(type_name, |condition| condition.node.on.node.as_str());
+                (
+                    None,
+                    &inline.directives,
+                    Some((on, &inline.selection_set.node)),
+                )
+            }
+            Selection::FragmentSpread(spread) => (None, &spread.node.directives, None),
+        };
+        let directive_arguments = directives.iter().filter_map(|directive| {
+            let meta = registry.directives.get(directive.node.name.node.as_str())?;
+            Some((&meta.args, directive.node.arguments.as_slice()))
+        });
+        field_arguments
+            .into_iter()
+            .chain(directive_arguments)
+            .find_map(|(declared, arguments)| {
+                let (name, value) = arguments
+                    .iter()
+                    .find(|(name, value)| name.pos == pos || value.pos == pos)?;
+                let marked = value
+                    .node
+                    .clone()
+                    .into_const_with(|variable| {
+                        Ok::<_, ()>(Value::Enum(Name::new(format!("${variable}"))))
+                    })
+                    .ok()?;
+                Some(ArgumentAt {
+                    name,
+                    value_pos: value.pos,
+                    marked,
+                    ty: declared.get(name.node.as_str())?.ty.as_str(),
+                })
+            })
+            .or_else(|| {
+                let (child, selection_set) = nested?;
+                argument_in(registry, child, selection_set, pos)
+            })
+    })
+}
+
+// Where a value does not coerce to the input type `ty`, as the keys and indices leading to the
+// failing part; `None` when it coerces. Follows the engine's input coercion rules.
+fn invalid_input_path(registry: &Registry, ty: &str, value: &Value) -> Option<Vec<Value>> {
+    let nested = |segment: Value, ty: &str, value: &Value| {
+        let mut path = invalid_input_path(registry, ty, value)?;
+        path.insert(0, segment);
+        Some(path)
+    };
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(_) if *value == Value::Null => Some(Vec::new()),
+        MetaTypeName::NonNull(inner) => invalid_input_path(registry, inner, value),
+        MetaTypeName::List(inner) => match value {
+            Value::List(items) => items
+                .iter()
+                .enumerate()
+                .find_map(|(index, item)| nested(Value::from(index), inner, item)),
+            Value::Null => None,
+            _ => invalid_input_path(registry, inner, value),
+        },
+        MetaTypeName::Named(_) if *value == Value::Null => None,
+        MetaTypeName::Named(name) => match registry.types.get(name)? {
+            MetaType::Scalar {
+                is_valid: Some(is_valid),
+                ..
+            } => (!is_valid(value)).then(Vec::new),
+            MetaType::Enum { enum_values, .. } => match value {
+                Value::Enum(name) if enum_values.contains_key(name.as_str()) => None,
+                Value::String(name) if enum_values.contains_key(name.as_str()) => None,
+                _ => Some(Vec::new()),
+            },
+            MetaType::InputObject {
+                input_fields,
+                oneof,
+                ..
+            } => {
+                let Value::Object(fields) = value else {
+                    return None;
+                };
+                if *oneof && (fields.len() != 1 || fields.values().any(|v| *v == Value::Null)) {
+                    return Some(Vec::new());
+                }
+                for field in input_fields.values() {
+                    match fields.get(field.name.as_str()) {
+                        Some(item) => {
+                            let key = Value::String(field.name.clone());
+                            if let Some(path) = nested(key, &field.ty, item) {
+                                return Some(path);
+                            }
+                        }
+                        None if MetaTypeName::create(&field.ty).is_non_null()
+                            && field.default_value.is_none() =>
+                        {
+                            return Some(Vec::new());
+                        }
+                        None => {}
+                    }
+                }
+                fields
+                    .keys()
+                    .any(|key| !input_fields.contains_key(key.as_str()))
+                    .then(Vec::new)
+            }
+            _ => None,
+        },
+    }
+}
+
+// The variable the value at `path` was given through, and the rest of the path within it.
+fn variable_at(value: &Value, path: &[Value]) -> Option<(String, Vec<Value>)> {
+    let mut value = value;
+    for depth in 0..=path.len() {
+        if let Value::Enum(name) = value
+            && let Some(variable) = name.strip_prefix('$')
+        {
+            return Some((variable.to_string(), path[depth..].to_vec()));
+        }
+        value = match (value, path.get(depth)?) {
+            (Value::Object(fields), Value::String(key)) => fields.get(key.as_str())?,
+            (Value::List(items), Value::Number(index)) => {
+                items.get(usize::try_from(index.as_u64()?).ok()?)?
+            }
+            _ => return None,
+        };
+    }
+    None
+}
@@ -1,9 +1,254 @@
-use async_graphql::Error;
-use pyo3::PyErr;
-use pyo3::exceptions::{PyTypeError, PyValueError};
+use std::collections::HashSet;
+use std::sync::Arc;
 
+use async_graphql::dynamic::TypeRef;
+use async_graphql::parser::Error as ParseError;
+use async_graphql::parser::types::OperationType;
+use async_graphql::{Error, ErrorExtensionValues, Name, Pos, Response, ServerError, Value};
+use pyo3::exceptions::{PyException, PyRuntimeError, PyTimeoutError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyTuple, PyType, PyTypeMethods};
+
+const UNAUTHORIZED_STATUS: i32 = 401;
+
+// Machine-readable `code` extensions set on every error the core returns, so clients can
+// branch on them instead of matching messages. Limits, rate limiting, and authentication
+// failures have codes of their own.
+pub(crate) const PARSE_ERROR: &str = "PARSE_ERROR";
+pub(crate) const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
+pub(crate) const RESOLVER_ERROR: &str = "RESOLVER_ERROR";
+pub(crate) const TIMEOUT: &str = "TIMEOUT";
+pub(crate) const INTERNAL: &str = "INTERNAL";
+pub(crate) const OPERATION_NOT_ALLOWED: &str = "OPERATION_NOT_ALLOWED";
+
+// Keeps the exception as the error's source so debug responses can describe it later. It is
+// coded along with the response's other errors, see `assign_error_codes`.
 pub(crate) fn py_err_to_error(err: PyErr) -> Error {
-    Error::new(err.to_string())
+    Error::new_with_source(err)
+}
+
+pub(crate) fn with_code(mut error: Error, code: &str) -> Error {
+    error
+        .extensions
+        .get_or_insert_with(Default::default)
+        .set("code", code);
+    error
+}
+
+pub(crate) fn parse_error(err: ParseError) -> ServerError {
+    let mut error = ServerError::from(err);
+    error
+        .extensions
+        .get_or_insert_with(Default::default)
+        .set("code", PARSE_ERROR);
+    error
+}
+
+// Codes the errors that have no code yet, all under the one GIL acquisition. Errors raised by
+// Python code are coded `TIMEOUT` for `TimeoutError`s and `RESOLVER_ERROR` otherwise. Other
+// errors at a field path failed resolving it, including the engine's checks of the resolved
+// value, such as a null for a non-null type, whose messages are prefixed `internal:` too. Other
+// errors prefixed so are engine invariant failures, and the rest failed validation; parse
+// failures are coded where they happen.
+pub(crate) fn assign_error_codes(py: Python<'_>, errors: &mut [ServerError]) {
+    for error in errors {
+        if error
+            .extensions
+            .as_ref()
+            .is_some_and(|extensions| extensions.get("code").is_some())
+        {
+            continue;
+        }
+        let code = match error.source::<PyErr>() {
+            Some(err) if err.is_instance_of::<PyTimeoutError>(py) => TIMEOUT,
+            Some(_) => RESOLVER_ERROR,
+            None if !error.path.is_empty() => RESOLVER_ERROR,
+            None if error.message.starts_with("internal:") => INTERNAL,
+            None => VALIDATION_ERROR,
+        };
+        error
+            .extensions
+            .get_or_insert_with(Default::default)
+            .set("code", code);
+    }
+}
+
+// The HTTP status the GraphQL-over-HTTP spec has servers answer a response with, once its
+// errors are coded: 500 when an engine invariant failed, 400 when the request failed before
+// executing because it did not parse, validate, or pass the request limits, and 200 otherwise,
+// field errors included.
+pub(crate) fn status_hint(response: &Response) -> u16 {
+    let coded = |error: &ServerError, codes: &[&str]| {
+        let code = error.extensions.as_ref().and_then(|ext| ext.get("code"));
+        matches!(code, Some(Value::String(code)) if codes.contains(&code.as_str()))
+    };
+    if response
+        .errors
+        .iter()
+        .any(|error| coded(error, &[INTERNAL]))
+    {
+        500
+    } else if response.data == Value::Null
+        && !response.errors.is_empty()
+        && response.errors.iter().all(|error| {
+            coded(
+                error,
+                &[
+                    PARSE_ERROR,
+                    VALIDATION_ERROR,
+                    OPERATION_NOT_ALLOWED,
+                    "BAD_REQUEST",
+                ],
+            )
+        })
+    {
+        400
+    } else {
+        200
+    }
+}
+
+// Adds `extensions.exception` to errors raised by Python code: the formatted traceback as a
+// `stacktrace` array of lines, and the messages of the exceptions it was chained from through
+// `__cause__` or `__context__`, innermost last.
+pub(crate) fn describe_exceptions(py: Python<'_>, response: &mut Response) -> PyResult<()> {
+    let traceback = py.import("traceback")?;
+    for error in &mut response.errors {
+        let Some(err) = error.source::<PyErr>() else {
+            continue;
+        };
+        let exception = err.value(py);
+        let formatted: Vec<String> = traceback
+            .call_method1("format_exception", (exception,))?
+            .extract()?;
+        let stacktrace = formatted
+            .concat()
+            .lines()
+            .map(|line| Value::String(line.to_string()))
+            .collect();
+        let causes = exception_chain(exception.as_any())?
+            .into_iter()
+            .map(Value::String)
+            .collect();
+        let details = [
+            (Name::new("stacktrace"), Value::List(stacktrace)),
+            (Name::new("causes"), Value::List(causes)),
+        ];
+        error
+            .extensions
+            .get_or_insert_with(Default::default)
+            .set("exception", Value::Object(details.into_iter().collect()));
+    }
+    Ok(())
+}
+
+fn exception_chain(exception: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    let mut messages = Vec::new();
+    let mut seen = HashSet::from([exception.as_ptr()]);
+    let mut current = exception.clone();
+    loop {
+        let mut next = current.getattr("__cause__")?;
+        if next.is_none() && !current.getattr("__suppress_context__")?.is_truthy()? {
+            next = current.getattr("__context__")?;
+        }
+        if next.is_none() || !seen.insert(next.as_ptr()) {
+            return Ok(messages);
+        }
+        messages.push(PyErr::from_value(next.clone()).to_string());
+        current = next;
+    }
+}
+
+pyo3::create_exception!(
+    grommet._core,
+    GrommetError,
+    PyException,
+    "Base exception for grommet errors."
+);
+
+static SCHEMA_BUILD_ERROR: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static VALIDATION_FAILED: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static SUBSCRIPTION_CLOSED: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+// Exception classes of the core derive from `GrommetError` and from the built-in exception it
+// raised before they existed, so `except ValueError` handlers keep catching them.
+fn exception_type<'py, E: pyo3::PyTypeInfo>(
+    py: Python<'py>,
+    cell: &'static PyOnceLock<Py<PyType>>,
+    name: &str,
+    doc: &str,
+) -> PyResult<Bound<'py, PyType>> {
+    cell.get_or_try_init(py, || {
+        let namespace = PyDict::new(py);
+        namespace.set_item("__module__", "grommet._core")?;
+        namespace.set_item("__doc__", doc)?;
+        let bases = PyTuple::new(py, [py.get_type::<GrommetError>(), py.get_type::<E>()])?;
+        py.get_type::<PyType>()
+            .call1((name, bases, namespace))?
+            .cast_into::<PyType>()
+            .map(Bound::unbind)
+            .map_err(PyErr::from)
+    })
+    .map(|ty| ty.bind(py).clone())
+}
+
+fn schema_build_error_type(py: Python<'_>) -> PyResult<Bound<'_, PyType>> {
+    exception_type::<PyValueError>(
+        py,
+        &SCHEMA_BUILD_ERROR,
+        "SchemaBuildError",
+        "Raised when a schema cannot be built from its types and options.",
+    )
+}
+
+fn validation_failed_type(py: Python<'_>) -> PyResult<Bound<'_, PyType>> {
+    exception_type::<PyValueError>(
+        py,
+        &VALIDATION_FAILED,
+        "ValidationFailed",
+        "Raised when an operation does not parse or validate against the schema.",
+    )
+}
+
+fn subscription_closed_type(py: Python<'_>) -> PyResult<Bound<'_, PyType>> {
+    exception_type::<PyRuntimeError>(
+        py,
+        &SUBSCRIPTION_CLOSED,
+        "SubscriptionClosed",
+        "Raised when a subscription stream can no longer deliver events.",
+    )
+}
+
+fn raise(
+    get_type: for<'py> fn(Python<'py>) -> PyResult<Bound<'py, PyType>>,
+    message: String,
+) -> PyErr {
+    Python::attach(|py| match get_type(py) {
+        Ok(ty) => PyErr::from_type(ty, message),
+        Err(err) => err,
+    })
+}
+
+pub(crate) fn schema_build_error(message: impl Into<String>) -> PyErr {
+    raise(schema_build_error_type, message.into())
+}
+
+pub(crate) fn validation_failed(message: impl Into<String>) -> PyErr {
+    raise(validation_failed_type, message.into())
+}
+
+pub(crate) fn subscription_closed(message: impl Into<String>) -> PyErr {
+    raise(subscription_closed_type, message.into())
+}
+
+pub(crate) fn add_exceptions(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add("GrommetError", py.get_type::<GrommetError>())?;
+    module.add("SchemaBuildError", schema_build_error_type(py)?)?;
+    module.add("ValidationFailed", validation_failed_type(py)?)?;
+    module.add("SubscriptionClosed", subscription_closed_type(py)?)?;
+    Ok(())
 }
 
 pub(crate) fn py_type_error(message: impl Into<String>) -> PyErr {
@@ -19,6 +264,18 @@ pub(crate) fn no_parent_value() -> Error {
     Error::new("No parent value for field")
 }
 
+pub(crate) fn not_picklable(what: &str) -> PyErr {
+    py_type_error(format!(
+        "Cannot pickle a grommet {what}; build the schema in each process instead."
+    ))
+}
+
+pub(crate) fn stream_used_after_fork() -> PyErr {
+    subscription_closed(
+        "Subscription stream was started in the parent process and cannot be used after fork.",
+    )
+}
+
 pub(crate) fn subscription_requires_async_iterator() -> PyErr {
     py_type_error("Subscription resolver must return an async iterator")
 }
@@ -27,6 +284,286 @@ pub(crate) fn expected_list_value() -> PyErr {
     py_type_error("Expected list for GraphQL list type")
 }
 
-pub(crate) fn unsupported_value_type() -> PyErr {
-    py_type_error("Unsupported value type")
+pub(crate) fn too_many_list_items(items: usize, limit: usize) -> PyErr {
+    py_value_error(format!(
+        "List has {items} items, exceeding the limit of {limit}."
+    ))
+}
+
+// Fields fail with this once the response passes its size limit. The response then drops its
+// data and reports the limit once, in place of these errors.
+pyo3::create_exception!(
+    grommet._core,
+    ResponseTooLarge,
+    PyValueError,
+    "The response passed its size limit."
+);
+
+pub(crate) fn response_too_large(limit: usize) -> PyErr {
+    ResponseTooLarge::new_err(format!("Response exceeds the limit of {limit} bytes."))
+}
+
+pub(crate) fn is_response_too_large(py: Python<'_>, error: &ServerError) -> bool {
+    error
+        .source::<PyErr>()
+        .is_some_and(|err| err.is_instance_of::<ResponseTooLarge>(py))
+}
+
+pub(crate) fn batch_size_mismatch(parents: usize, values: usize) -> PyErr {
+    py_value_error(format!(
+        "Batch resolver returned {values} values for {parents} parents"
+    ))
+}
+
+fn qualified_type_name(value: &Bound<'_, PyAny>) -> String {
+    value
+        .get_type()
+        .fully_qualified_name()
+        .map_or_else(|_| "<unknown>".to_string(), |name| name.to_string())
+}
+
+pub(crate) fn unknown_source_kind(kind: &str) -> PyErr {
+    py_value_error(format!(
+        "Unknown source lookup \"{kind}\"; expected \"attr\" or \"item\"."
+    ))
+}
+
+// Rejection of a request by a pre-execute hook that raised; the exception is kept as the error's
+// source.
+pub(crate) fn pre_execute_rejected(py: Python<'_>, err: PyErr) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "UNAUTHENTICATED");
+    extensions.set("status", UNAUTHORIZED_STATUS);
+    let mut error = ServerError::new(err.value(py).to_string(), None);
+    error.source = Some(Arc::new(err));
+    error.extensions = Some(extensions);
+    error
+}
+
+// Follows graphql-js's wording, pointing at the rule since the engine itself would merge the
+// streams of every selected field.
+pub(crate) fn multiple_subscription_root_fields(
+    name: Option<&str>,
+    keys: &[String],
+    pos: Pos,
+) -> ServerError {
+    let operation = match name {
+        Some(name) => format!("Subscription \"{name}\""),
+        None => "Anonymous Subscription".to_string(),
+    };
+    let keys = keys
+        .iter()
+        .map(|key| format!("\"{key}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    ServerError::new(
+        format!(
+            "{operation} must select only one top level field, but selects {keys} \
+             (https://spec.graphql.org/October2021/#sec-Single-root-field)."
+        ),
+        Some(pos),
+    )
+}
+
+// Rejection of a mutation or subscription by a read-only request, at the operation's position
+// and with its type as the `operation` extension.
+pub(crate) fn operation_not_allowed(operation: OperationType, pos: Pos) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", OPERATION_NOT_ALLOWED);
+    extensions.set("operation", operation.to_string());
+    let mut error = ServerError::new(
+        format!("Operations of type \"{operation}\" are not allowed on read-only requests."),
+        Some(pos),
+    );
+    error.extensions = Some(extensions);
+    error
+}
+
+pub(crate) fn unknown_request_option(option: &str) -> PyErr {
+    py_value_error(format!("Unknown request option '{option}'."))
+}
+
+pub(crate) fn unknown_schema_option(option: &str) -> PyErr {
+    py_type_error(format!("Unknown schema option '{option}'."))
+}
+
+pub(crate) fn invalid_rate_limit(problem: &str) -> PyErr {
+    py_value_error(format!("Rate limit rule {problem}."))
+}
+
+pub(crate) fn cyclic_value(path: &str) -> PyErr {
+    py_value_error(format!("Cyclic value detected at path '{path}'."))
+}
+
+pub(crate) fn value_too_deep(max_depth: usize) -> PyErr {
+    py_value_error(format!(
+        "Value nesting exceeds the maximum depth of {max_depth}."
+    ))
+}
+
+pub(crate) fn invalid_max_value_depth() -> PyErr {
+    py_value_error("The maximum value depth must be at least 1.")
+}
+
+pub(crate) fn unknown_string_policy(policy: &str) -> PyErr {
+    py_value_error(format!(
+        "Unknown string policy \"{policy}\"; expected \"keep\", \"error\" or \"replace\"."
+    ))
+}
+
+pub(crate) fn invalid_string(problem: &str) -> PyErr {
+    py_value_error(format!(
+        "Cannot convert a string containing {problem} to a GraphQL value."
+    ))
+}
+
+pub(crate) fn unknown_resolver_key(key: &str) -> PyErr {
+    schema_build_error(format!(
+        "Resolver given for \"{key}\", which is not \"*\", \"Type.*\", or a field of the schema."
+    ))
+}
+
+pub(crate) fn underivable_resolver_key(qualname: &str) -> PyErr {
+    schema_build_error(format!(
+        "Cannot derive a resolver key from \"{qualname}\"; expected a qualified name ending in \
+         \"Type.field\"."
+    ))
+}
+
+pub(crate) fn ambiguous_resolver_key(key: &str) -> PyErr {
+    schema_build_error(format!(
+        "Several resolvers given for \"{key}\"; pass a dict to key them explicitly."
+    ))
+}
+
+pub(crate) fn duplicate_resolver_registration(key: &str) -> PyErr {
+    py_value_error(format!("A resolver is already registered for \"{key}\"."))
+}
+
+pub(crate) fn unknown_root_field(field_name: &str) -> PyErr {
+    validation_failed(format!("Unknown field \"{field_name}\" on the query type."))
+}
+
+pub(crate) fn unknown_call_argument(field_name: &str, arg_name: &str) -> PyErr {
+    validation_failed(format!(
+        "Unknown argument \"{arg_name}\" for field \"{field_name}\"."
+    ))
+}
+
+// The exception `call` raises for the first error of its response: the exception a resolver
+// raised, or `ValidationFailed` with the error's message, e.g. for an argument of the wrong
+// type.
+pub(crate) fn call_failed(py: Python<'_>, error: &ServerError) -> PyErr {
+    match error.source::<PyErr>() {
+        Some(err) => err.clone_ref(py),
+        None => validation_failed(error.message.clone()),
+    }
+}
+
+pub(crate) fn invalid_selection(path: &str, problem: &str) -> PyErr {
+    validation_failed(format!("Cannot select \"{path}\": {problem}."))
+}
+
+pub(crate) fn unknown_reloaded_type(type_name: &str) -> PyErr {
+    schema_build_error(format!(
+        "Cannot reload \"{type_name}\", which is not a type of the schema."
+    ))
+}
+
+pub(crate) fn unknown_reloaded_field(type_name: &str, field_name: &str) -> PyErr {
+    schema_build_error(format!(
+        "Cannot reload resolver \"{field_name}\", which is not a field of \"{type_name}\"."
+    ))
+}
+
+pub(crate) fn unknown_interface_binding(interface: &str) -> PyErr {
+    schema_build_error(format!(
+        "Implementors given for \"{interface}\", which is not an interface of the schema."
+    ))
+}
+
+pub(crate) fn unknown_interface_implementor(interface: &str, type_name: &str) -> PyErr {
+    schema_build_error(format!(
+        "\"{type_name}\" is not an object type implementing \"{interface}\"."
+    ))
+}
+
+pub(crate) fn invalid_default_literal(literal: &str, reason: &str) -> PyErr {
+    schema_build_error(format!(
+        "Default literal {literal:?} is not a GraphQL value: {reason}"
+    ))
+}
+
+pub(crate) fn mutation_field_requires_resolver(type_name: &str, field_name: &str) -> PyErr {
+    schema_build_error(format!(
+        "Mutation root \"{type_name}\" field \"{field_name}\" needs a resolver; mutations are \
+         not read from their source."
+    ))
+}
+
+pub(crate) fn unknown_serializer_type(type_name: &str) -> PyErr {
+    schema_build_error(format!(
+        "Serializer given for \"{type_name}\", which is not an object type of the schema."
+    ))
+}
+
+pub(crate) fn invalid_stream_timeout(option: &str, seconds: f64) -> PyErr {
+    py_value_error(format!(
+        "Subscription option \"{option}\" must be a positive number of seconds, got {seconds}."
+    ))
+}
+
+pub(crate) fn shadow_schema_cycle() -> PyErr {
+    py_value_error(
+        "A shadow schema cannot be the schema itself, one of its variants, or a schema that \
+         mirrors its own traffic.",
+    )
+}
+
+pub(crate) fn invalid_idempotency_ttl(seconds: f64) -> PyErr {
+    py_value_error(format!(
+        "Idempotency TTL must be a positive number of seconds, got {seconds}."
+    ))
+}
+
+pub(crate) fn invalid_remote_timeout(seconds: f64) -> PyErr {
+    py_value_error(format!(
+        "Remote timeout must be a positive number of seconds, got {seconds}."
+    ))
+}
+
+pub(crate) fn missing_source(name: &str, parent: &Bound<'_, PyAny>) -> PyErr {
+    py_type_error(format!(
+        "Source \"{name}\" not found on parent of type '{}'",
+        qualified_type_name(parent)
+    ))
+}
+
+pub(crate) fn unsupported_value_type(value: &Bound<'_, PyAny>, expected: Option<&str>) -> PyErr {
+    let type_name = qualified_type_name(value);
+    match expected {
+        Some(expected) => py_type_error(format!(
+            "Cannot convert a value of type '{type_name}' to GraphQL type \"{expected}\""
+        )),
+        None => py_type_error(format!(
+            "Cannot convert a value of type '{type_name}' to a GraphQL value"
+        )),
+    }
+}
+
+pub(crate) fn lossy_conversion(value: &Bound<'_, PyAny>, expected: &str) -> String {
+    let type_name = qualified_type_name(value);
+    format!("Lossy conversion of a value of type '{type_name}' to GraphQL type \"{expected}\"")
+}
+
+pub(crate) fn field_value_error(
+    py: Python<'_>,
+    path: &str,
+    output_type: &TypeRef,
+    err: &PyErr,
+) -> PyErr {
+    py_type_error(format!(
+        "Invalid value for field \"{path}\" of type \"{output_type}\": {}",
+        err.value(py)
+    ))
 }
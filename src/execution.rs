@@ -0,0 +1,598 @@
+use std::sync::{Arc, PoisonError};
+use std::time::Instant;
+
+use async_graphql::futures_util::lock::Mutex;
+use async_graphql::futures_util::stream::{StreamExt, abortable};
+use async_graphql::parser::parse_query;
+use async_graphql::parser::types::{
+    ExecutableDocument, OperationDefinition, OperationType, Selection,
+};
+use async_graphql::{Request, Response, ServerError, Variables};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::api::{SchemaWrapper, sorted};
+use crate::batch::BatchLoader;
+use crate::document::{
+    OperationSummary, describe_input_errors, select_operation, subscription_root_field_errors,
+    summarize_operation,
+};
+use crate::errors::{
+    describe_exceptions, operation_not_allowed, parse_error, pre_execute_rejected,
+};
+use crate::idempotency::IdempotencyKey;
+use crate::limits::{QuerySource, RequestLimits, parse_variables};
+use crate::mock::MockData;
+use crate::ordering::KeyOrder;
+use crate::replay::SubscriptionReplay;
+use crate::request::{ManagedContexts, RequestOptions, VariablesSource, clone_context};
+use crate::resolver::{BoxFut, awaitable_into_future, running_loop};
+use crate::response::response_into_py;
+use crate::shadow::{MirroredRequest, ShadowTraffic};
+use crate::signature::operation_signature;
+use crate::stream::SubscriptionStream;
+use crate::subscriptions::SubscriptionHandle;
+use crate::types::{
+    CoercionWarnings, ContextValue, MaxListItems, PyObj, RequestStats, ResponseBudget, ResultHook,
+    SchemaBundle, SubscriptionErrors,
+};
+use crate::values::{payload_to_py, payload_to_value};
+use crate::visibility::hidden_field_errors;
+
+impl SchemaWrapper {
+    fn convert_variables(
+        &self,
+        variables: Option<Py<PyAny>>,
+    ) -> PyResult<Option<async_graphql::Value>> {
+        match variables {
+            Some(vars) => Python::attach(|py| {
+                let bound = vars.bind(py);
+                payload_to_value(py, bound, self.options.value_settings())
+            })
+            .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // Converts variables once the limits have been checked on the Python payload, so an
+    // oversized one is rejected before it is converted.
+    pub(crate) fn checked_variables(
+        &self,
+        limits: &RequestLimits,
+        variables: Option<Py<PyAny>>,
+    ) -> PyResult<Result<Option<async_graphql::Value>, ServerError>> {
+        if let Some(vars) = &variables
+            && let Err(error) = Python::attach(|py| limits.check_py_variables(vars.bind(py)))
+        {
+            return Ok(Err(error));
+        }
+        self.convert_variables(variables).map(Ok)
+    }
+
+    // Runs the pre-execute hook, if any, with the request's metadata and context. Returns the
+    // context to execute with, or the error rejecting the request when the hook raised.
+    pub(crate) async fn pre_execute(
+        &self,
+        query: &str,
+        variables: Option<&async_graphql::Value>,
+        doc: Option<&ExecutableDocument>,
+        summary: Option<&OperationSummary>,
+        context: Option<Py<PyAny>>,
+    ) -> PyResult<Result<Option<Py<PyAny>>, ServerError>> {
+        let Some(hook) = self.options.pre_execute() else {
+            return Ok(Ok(context));
+        };
+        let future = Python::attach(|py| -> PyResult<BoxFut> {
+            let metadata = PyDict::new(py);
+            metadata.set_item("query", query)?;
+            let variables = variables
+                .map(|vars| payload_to_py(py, vars, self.options.value_settings()))
+                .transpose()?;
+            metadata.set_item("variables", variables)?;
+            let name = summary.and_then(|s| s.name.as_deref());
+            metadata.set_item("operation_name", name)?;
+            let root_fields = summary.map_or(&[][..], |s| s.root_fields.as_slice());
+            metadata.set_item("root_fields", root_fields)?;
+            let signature = doc.and_then(|doc| operation_signature(doc, name));
+            metadata.set_item("signature", signature)?;
+            let context = context
+                .as_ref()
+                .map_or_else(|| py.None(), |c| c.clone_ref(py));
+            Ok(match hook.bind(py).call1((metadata, context)) {
+                Ok(value) if value.hasattr("__await__")? => awaitable_into_future(value),
+                result => Box::pin(std::future::ready(result.map(Bound::unbind))),
+            })
+        })?;
+        let result = future.await;
+        Python::attach(|py| match result {
+            Ok(replacement) if replacement.is_none(py) => Ok(Ok(context)),
+            Ok(replacement) => Ok(Ok(Some(replacement))),
+            Err(err) => Ok(Err(pre_execute_rejected(py, err))),
+        })
+    }
+
+    // The parsed document of a query, from the warmed-up operations when it is one of them.
+    pub(crate) fn parse_document(
+        &self,
+        query: &str,
+    ) -> Result<Arc<ExecutableDocument>, ServerError> {
+        let documents = self
+            .options
+            .documents
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let warmed = documents.get(query);
+        self.options.document_lookups.record(warmed.is_some());
+        match warmed {
+            Some(doc) => Ok(doc.clone()),
+            None => parse_query(query).map(Arc::new).map_err(parse_error),
+        }
+    }
+
+    fn record_coverage(&self, doc: &ExecutableDocument, operation: &OperationDefinition) {
+        let mut coverage = self
+            .options
+            .coverage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if coverage.is_enabled() {
+            coverage.record(self.schema().registry(), doc, operation);
+        }
+    }
+
+    pub(crate) fn build_request(
+        &self,
+        query: String,
+        vars_value: Option<async_graphql::Value>,
+        context: Option<PyObj>,
+    ) -> PyResult<Request> {
+        let mut request = Request::new(query)
+            .data(BatchLoader::default())
+            .data(SchemaBundle(self.bundle()))
+            .data(self.options.value_settings())
+            .data(self.options.serial_locks.clone());
+        if let Some(vars) = vars_value {
+            request = request.variables(Variables::from_value(vars));
+        }
+        if let Some(obj) = context {
+            request = request.data(ContextValue(obj));
+        }
+        if let Some(hook) = self.options.result_hook() {
+            request = request.data(ResultHook(hook));
+        }
+        if let Some(resolvers) = self.options.node_resolvers() {
+            request = request.data(resolvers);
+        }
+        if let Some(lookup) = self.options.source_lookup() {
+            request = request.data(lookup);
+        }
+        if let Some(resolver) = &self.options.default_resolver {
+            request = request.data(resolver.clone());
+        }
+        if let Some(serializers) = &self.options.serializers {
+            request = request.data(serializers.clone());
+        }
+        if let Some(interfaces) = &self.options.interfaces {
+            request = request.data(interfaces.clone());
+        }
+        if let Some(capture) = self.options.call_capture() {
+            request = request.data(capture);
+        }
+        if let Some(remote) = self.options.remote() {
+            request = request.data(remote);
+        }
+        if let Some(limit) = self.options.limits().max_list_items {
+            request = request.data(MaxListItems(limit));
+        }
+        let definition = self.definition();
+        if self.options.typed_ids
+            && let Some(typed_ids) = &definition.typed_ids
+        {
+            request = request.data(typed_ids.clone());
+        }
+        if let Some(id_inputs) = &definition.id_inputs {
+            request = request.data(id_inputs.clone());
+        }
+        Ok(request)
+    }
+
+    // Decodes the query and converts the variables of a request, each once it passed the size
+    // limits.
+    fn checked_request(
+        &self,
+        query: QuerySource,
+        variables: VariablesSource,
+    ) -> PyResult<Result<Prepared, ServerError>> {
+        let limits = self.options.limits();
+        let query = match query
+            .decode()
+            .and_then(|query| limits.check_query(&query).map(|()| query))
+        {
+            Ok(query) => query,
+            Err(error) => return Ok(Err(error)),
+        };
+        let variables = match variables {
+            VariablesSource::Python(variables) => self.checked_variables(&limits, variables)?,
+            VariablesSource::Json(json) => json.as_deref().map(parse_variables).transpose(),
+        };
+        Ok(variables.map(|variables| Prepared {
+            query,
+            variables,
+            parsed: None,
+        }))
+    }
+
+    // The body shared by `execute` and `execute_raw`, closing the request's managed contexts
+    // however it ends.
+    pub(crate) async fn execute_request(
+        &self,
+        query: QuerySource,
+        variables: VariablesSource,
+        context: Option<Py<PyAny>>,
+        options: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let contexts = ManagedContexts::new(self.options.manage_context, &context);
+        let result = async {
+            let options = RequestOptions::from_py(options, self.options.value_settings())?;
+            let (raw, format) = (options.raw, self.result_format(options.error_objects));
+            let execution = match self.checked_request(query, variables)? {
+                Ok(request) => {
+                    self.run(request, context, options, Entry::Execute, &contexts)
+                        .await?
+                }
+                Err(error) => Execution::Response(Response::from_errors(vec![error])),
+            };
+            Python::attach(|py| match execution {
+                Execution::Response(response) => response_into_py(py, response, raw, format),
+                Execution::Stream(stream) => Ok(stream.into_pyobject(py)?.into_any().unbind()),
+            })
+        }
+        .await;
+        contexts.finish(result).await
+    }
+
+    // Runs a request through `run` for its single response, closing its managed contexts
+    // however it ends.
+    pub(crate) async fn respond(
+        &self,
+        query: QuerySource,
+        variables: VariablesSource,
+        context: Option<Py<PyAny>>,
+        options: RequestOptions,
+        entry: Entry,
+    ) -> PyResult<Response> {
+        let contexts = ManagedContexts::new(self.options.manage_context, &context);
+        let result = async {
+            match self.checked_request(query, variables)? {
+                Ok(request) => {
+                    self.run_single(request, context, options, entry, &contexts)
+                        .await
+                }
+                Err(error) => Ok(Response::from_errors(vec![error])),
+            }
+        }
+        .await;
+        contexts.finish(result).await
+    }
+
+    // Runs a request through `run` for its single response, leaving its managed contexts to
+    // the caller.
+    pub(crate) async fn run_single(
+        &self,
+        request: Prepared,
+        context: Option<Py<PyAny>>,
+        options: RequestOptions,
+        entry: Entry,
+        contexts: &ManagedContexts,
+    ) -> PyResult<Response> {
+        match self.run(request, context, options, entry, contexts).await? {
+            Execution::Response(response) => Ok(response),
+            Execution::Stream(_) => unreachable!("only `execute` opens streams"),
+        }
+    }
+
+    // Runs a request whose query and variables passed the size limits: every entry point goes
+    // through here, so the pre-execute hook, the guards, the caches and the post-processing
+    // apply to all of them.
+    pub(crate) async fn run(
+        &self,
+        request: Prepared,
+        context: Option<Py<PyAny>>,
+        options: RequestOptions,
+        entry: Entry,
+        contexts: &ManagedContexts,
+    ) -> PyResult<Execution> {
+        let Prepared {
+            query,
+            variables,
+            parsed,
+        } = request;
+        let parsed = parsed.unwrap_or_else(|| self.parse_document(&query));
+        let doc = parsed.as_ref().ok().cloned();
+        let operation_name = options.operation_name.clone();
+        let summary = doc
+            .as_deref()
+            .and_then(|doc| summarize_operation(doc, operation_name.as_deref()));
+        let idempotency_key = options
+            .idempotency_key
+            .clone()
+            .zip(doc.as_deref())
+            .and_then(|(key, doc)| {
+                let schema = (
+                    self.definition().id,
+                    self.roles.as_ref().map(|roles| sorted(roles.iter())),
+                );
+                let (name, variables) = (operation_name.as_deref(), variables.as_ref());
+                IdempotencyKey::for_mutation(key, schema, &query, name, variables, doc)
+            });
+        let subscription = doc
+            .as_deref()
+            .and_then(|doc| Self::subscription_operation(doc, operation_name.as_deref()))
+            .filter(|_| matches!(entry, Entry::Execute));
+        let context = match self
+            .pre_execute(
+                &query,
+                variables.as_ref(),
+                doc.as_deref(),
+                summary.as_ref(),
+                context,
+            )
+            .await?
+        {
+            Ok(context) => context,
+            Err(error) => return Ok(Execution::failed(vec![error])),
+        };
+        contexts.add(&context);
+        let limits = self.options.limits();
+        if let Some(Err(error)) = variables.as_ref().map(|vars| limits.check_variables(vars)) {
+            return Ok(Execution::failed(vec![error]));
+        }
+
+        if options.parse_only {
+            return Ok(Execution::Response(match parsed {
+                Ok(_) => Response::new(async_graphql::Value::Null),
+                Err(error) => Response::from_errors(vec![error]),
+            }));
+        }
+
+        if options.read_only
+            && let Some(doc) = &doc
+        {
+            let errors = read_only_errors(doc);
+            if !errors.is_empty() {
+                return Ok(Execution::failed(errors));
+            }
+        }
+
+        if let Some(visibility) = &options.visibility {
+            let errors = Python::attach(|py| {
+                hidden_field_errors(self.schema().registry(), &query, visibility.bind(py))
+            })?;
+            if !errors.is_empty() {
+                return Ok(Execution::failed(errors));
+            }
+        }
+
+        if let Some(doc) = &doc {
+            let errors = subscription_root_field_errors(doc);
+            if !errors.is_empty() {
+                return Ok(Execution::failed(errors));
+            }
+        }
+
+        if let (Some(rate_limits), Some(doc)) = (self.options.rate_limits(), &doc) {
+            let limited = Python::attach(|py| {
+                let context = context.as_ref().map(|context| context.bind(py));
+                let (schema, name) = (self.schema(), operation_name.as_deref());
+                rate_limits.check(py, schema.registry(), doc, name, context)
+            })?;
+            if let Err(error) = limited {
+                return Ok(Execution::failed(vec![error]));
+            }
+        }
+        let reservation = idempotency_key
+            .as_ref()
+            .map(|key| self.options.idempotency.reserve(key));
+        let _reserved = match &reservation {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        if let Some(response) = idempotency_key
+            .as_ref()
+            .and_then(|key| self.options.idempotency.replay(key))
+        {
+            return Ok(Execution::Response(response));
+        }
+        let operation = doc
+            .as_deref()
+            .and_then(|doc| select_operation(doc, operation_name.as_deref()))
+            .map(|(_name, operation)| &operation.node);
+        if let (Some(doc), Some(operation)) = (&doc, operation) {
+            self.record_coverage(doc, operation);
+        }
+        // Mocked data is not what the primary serves, so there is nothing to compare.
+        let mirrored = self
+            .options
+            .shadow()
+            .filter(|_| !matches!(entry, Entry::Mock(_)))
+            .filter(|_| doc.as_deref().is_some_and(ShadowTraffic::mirrors))
+            .filter(|shadow| {
+                context.is_none() || shadow.mirrors_context(self.options.manage_context)
+            })
+            .map(|shadow| {
+                let request = MirroredRequest {
+                    query: query.clone(),
+                    operation_name: operation_name.clone(),
+                    variables: variables.clone(),
+                    context: clone_context(&context),
+                };
+                (shadow, request)
+            });
+        // Operations declaring variables keep them to locate input coercion errors with.
+        let described = doc.clone().map(|doc| {
+            let declared =
+                operation.is_some_and(|operation| !operation.variable_definitions.is_empty());
+            let variables = variables.clone().filter(|_| declared);
+            (doc, variables)
+        });
+        let mut request =
+            options.apply(self.build_request(query, variables, context.map(PyObj::new))?);
+        if let Some(doc) = &doc {
+            request.set_parsed_query(ExecutableDocument::clone(doc));
+        }
+        if let Some(summary) = summary {
+            request = request.data(summary);
+        }
+        if let Entry::Mock(mock) = entry {
+            request = request.data(mock);
+        }
+        let trim = self.options.introspection_trim().zip(doc.clone());
+        let order = if options.sort_keys {
+            Some(KeyOrder::Sorted)
+        } else {
+            doc.map(|doc| KeyOrder::Selection {
+                doc,
+                operation: operation_name,
+            })
+        };
+        let warnings = options.coercion_warnings.then(CoercionWarnings::default);
+        if let Some(warnings) = &warnings {
+            request = request.data(warnings.clone());
+        }
+        let stats = self.options.debug().then(RequestStats::default);
+        if let Some(stats) = &stats {
+            request = request.data(stats.clone());
+        }
+        let schema = self.schema();
+
+        if let Some((operation_name, field_name)) = subscription {
+            let last_cursor = Arc::new(std::sync::Mutex::new(None));
+            let errors = SubscriptionErrors::default();
+            let request = request
+                .data(SubscriptionReplay {
+                    buffer: self.options.replay.clone(),
+                    resume_from: options.resume_from,
+                    last_cursor: last_cursor.clone(),
+                })
+                .data(errors.clone());
+            let (stream, abort) = abortable(schema.execute_stream(request));
+            let handle = Arc::new(SubscriptionHandle::new(
+                operation_name,
+                field_name,
+                options.stream.labels.clone(),
+                abort,
+            ));
+            self.options.subscriptions.register(&handle);
+            let contexts = contexts.take();
+            let event_loop = (!contexts.is_empty()).then(running_loop).flatten();
+            return Ok(Execution::Stream(SubscriptionStream {
+                stream: Arc::new(Mutex::new(Some(stream.boxed()))),
+                contexts: std::sync::Mutex::new(contexts),
+                handle,
+                last_cursor,
+                warnings,
+                errors,
+                stats,
+                order,
+                started: Instant::now(),
+                options: options.stream,
+                format: self.result_format(options.error_objects),
+                event_loop,
+            }));
+        }
+
+        let budget = limits.max_response_bytes.map(ResponseBudget::new);
+        if let Some(budget) = &budget {
+            request = request.data(budget.clone());
+        }
+        let mut response = schema.execute(request).await;
+        if let Some((doc, variables)) = &described {
+            let registry = schema.registry();
+            describe_input_errors(registry, doc, variables.as_ref(), &mut response.errors);
+        }
+        if let Some((trim, doc)) = &trim {
+            trim.apply(doc, &mut response);
+        }
+        limits.check_response(&mut response, budget.as_ref());
+        if let Some(order) = &order {
+            order.apply(&mut response);
+        }
+        // The shadow's response goes through the same steps before the two are compared.
+        if let Some((shadow, request)) = &mirrored {
+            let settings = self.options.value_settings();
+            Python::attach(|py| shadow.mirror(py, request, &response, settings));
+        }
+        if let Some(key) = idempotency_key {
+            self.options.idempotency.store(key, &response);
+        }
+        if let Some(warnings) = &warnings {
+            warnings.drain_into(&mut response);
+        }
+        if let Some(stats) = &stats {
+            stats.drain_into(&mut response);
+            Python::attach(|py| describe_exceptions(py, &mut response))?;
+        }
+        Ok(Execution::Response(response))
+    }
+
+    // The operation name and root field of the subscription the request runs, if it runs one.
+    fn subscription_operation(
+        doc: &ExecutableDocument,
+        operation_name: Option<&str>,
+    ) -> Option<(Option<String>, Option<String>)> {
+        let (name, op) = select_operation(doc, operation_name)?;
+        if op.node.ty != OperationType::Subscription {
+            return None;
+        }
+        let field = op
+            .node
+            .selection_set
+            .node
+            .items
+            .iter()
+            .find_map(|selection| match &selection.node {
+                Selection::Field(field) => Some(field.node.name.node.to_string()),
+                _ => None,
+            });
+        Some((name.map(ToString::to_string), field))
+    }
+}
+
+// A request whose query and variables passed the size limits, with its document when the
+// caller already parsed it.
+pub(crate) struct Prepared {
+    pub(crate) query: String,
+    pub(crate) variables: Option<async_graphql::Value>,
+    pub(crate) parsed: Option<Result<Arc<ExecutableDocument>, ServerError>>,
+}
+
+// The entry point a request came through: `execute` and `execute_raw` open a stream for a
+// subscription, which the others run for a single response, and `execute_mock` resolves
+// unimplemented fields to generated data.
+pub(crate) enum Entry {
+    Execute,
+    Single,
+    Mock(MockData),
+}
+
+// What `run` produced: a response, or the stream of a subscription.
+pub(crate) enum Execution {
+    Response(Response),
+    Stream(SubscriptionStream),
+}
+
+impl Execution {
+    fn failed(errors: Vec<ServerError>) -> Self {
+        Execution::Response(Response::from_errors(errors))
+    }
+}
+
+// Rejects the document's mutations and subscriptions for `read_only` requests, so a public
+// endpoint can serve the same schema as internal traffic without its write paths.
+fn read_only_errors(doc: &ExecutableDocument) -> Vec<ServerError> {
+    doc.operations
+        .iter()
+        .filter(|(_, operation)| operation.node.ty != OperationType::Query)
+        .map(|(_, operation)| operation_not_allowed(operation.node.ty, operation.pos))
+        .collect()
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use async_graphql::parser::types::{ExecutableDocument, Field, Selection, SelectionSet};
+use async_graphql::registry::{MetaTypeName, Registry};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::check::field_type;
+use crate::errors::validation_failed;
+
+// How a field's value is produced, keyed by `Type.field`: `attribute` fields read the parent
+// object, and `python` fields call a resolver. Fields missing from the bundle are `builtin`.
+fn resolver_table<'py>(
+    py: Python<'py>,
+    bundle: &Bound<'py, PyAny>,
+) -> PyResult<HashMap<String, (Bound<'py, PyDict>, usize)>> {
+    let mut table = HashMap::new();
+    for compiled_type in bundle.getattr("types")?.try_iter()? {
+        let compiled_type = compiled_type?;
+        if !compiled_type.hasattr("object_fields")? {
+            continue;
+        }
+        let type_name: String = compiled_type.getattr("meta")?.getattr("name")?.extract()?;
+        for field in compiled_type.getattr("object_fields")?.try_iter()? {
+            let field = field?;
+            let field_name: String = field.getattr("name")?.extract()?;
+            let resolver = PyDict::new(py);
+            let is_data_field = field.hasattr("resolver_func")?;
+            if is_data_field {
+                resolver.set_item("kind", "attribute")?;
+            } else {
+                resolver.set_item("kind", "python")?;
+                resolver.set_item("name", field.getattr("func")?.getattr("__qualname__")?)?;
+                resolver.set_item("async", field.getattr("is_async")?)?;
+                resolver.set_item("batch", field.getattr("batch")?)?;
+            }
+            let calls = usize::from(!is_data_field);
+            table.insert(format!("{type_name}.{field_name}"), (resolver, calls));
+        }
+    }
+    Ok(table)
+}
+
+struct Explainer<'a, 'py> {
+    py: Python<'py>,
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    resolvers: HashMap<String, (Bound<'py, PyDict>, usize)>,
+}
+
+impl<'py> Explainer<'_, 'py> {
+    // Whether a fragment on `condition` applies to objects of `type_name`.
+    fn applies(&self, condition: &str, type_name: &str) -> bool {
+        condition == type_name
+            || self
+                .registry
+                .types
+                .get(condition)
+                .is_some_and(|ty| ty.is_possible_type(type_name))
+    }
+
+    // Appends the planned fields of a selection on objects of `type_name`, returning their cost.
+    fn selection(
+        &self,
+        type_name: &str,
+        selection_set: &SelectionSet,
+        fragments: &mut Vec<String>,
+        fields: &Bound<'py, PyList>,
+    ) -> PyResult<usize> {
+        let mut cost = 0;
+        for selection in &selection_set.items {
+            let (condition, selection_set) = match &selection.node {
+                Selection::Field(field) => {
+                    cost += self.field(type_name, &field.node, fragments, fields)?;
+                    continue;
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if fragments.iter().any(|active| active == name) {
+                        continue;
+                    }
+                    let Some(fragment) = self.doc.fragments.get(name) else {
+                        return Err(validation_failed(format!("Unknown fragment \"{name}\".")));
+                    };
+                    fragments.push(name.to_string());
+                    let fragment = &fragment.node;
+                    let condition = fragment.type_condition.node.on.node.as_str();
+                    let applies = self.applies(condition, type_name);
+                    if applies {
+                        cost += self.selection(
+                            type_name,
+                            &fragment.selection_set.node,
+                            fragments,
+                            fields,
+                        )?;
+                    }
+                    fragments.pop();
+                    continue;
+                }
+                Selection::InlineFragment(fragment) => (
+                    fragment.node.type_condition.as_ref(),
+                    &fragment.node.selection_set.node,
+                ),
+            };
+            if condition.is_none_or(|condition| self.applies(&condition.node.on.node, type_name)) {
+                cost += self.selection(type_name, selection_set, fragments, fields)?;
+            }
+        }
+        Ok(cost)
+    }
+
+    fn field(
+        &self,
+        type_name: &str,
+        field: &Field,
+        fragments: &mut Vec<String>,
+        fields: &Bound<'py, PyList>,
+    ) -> PyResult<usize> {
+        let py = self.py;
+        let name = field.name.node.as_str();
+        let ty = field_type(self.registry, type_name, name).ok_or_else(|| {
+            validation_failed(format!("Unknown field \"{name}\" on type \"{type_name}\"."))
+        })?;
+        let (resolver, mut cost) = match self.resolvers.get(&format!("{type_name}.{name}")) {
+            Some((resolver, calls)) => (resolver.copy()?, *calls),
+            None => {
+                let resolver = PyDict::new(py);
+                resolver.set_item("kind", "builtin")?;
+                (resolver, 0)
+            }
+        };
+
+        let entry = PyDict::new(py);
+        entry.set_item("field", name)?;
+        entry.set_item(
+            "alias",
+            field.alias.as_ref().map(|alias| alias.node.as_str()),
+        )?;
+        entry.set_item("parent", type_name)?;
+        entry.set_item("type", &ty)?;
+        entry.set_item("resolver", resolver)?;
+
+        let selection_set = &field.selection_set.node;
+        if !selection_set.items.is_empty() && !name.starts_with("__") {
+            let child_type = MetaTypeName::concrete_typename(&ty);
+            match self
+                .registry
+                .types
+                .get(child_type)
+                .and_then(|ty| ty.possible_types())
+            {
+                Some(possible_types) => {
+                    let branches = PyDict::new(py);
+                    let mut branch_cost = 0;
+                    for possible_type in possible_types {
+                        let branch = PyList::empty(py);
+                        branch_cost = branch_cost.max(self.selection(
+                            possible_type,
+                            selection_set,
+                            fragments,
+                            &branch,
+                        )?);
+                        branches.set_item(possible_type, branch)?;
+                    }
+                    cost += branch_cost;
+                    entry.set_item("branches", branches)?;
+                }
+                None => {
+                    let children = PyList::empty(py);
+                    cost += self.selection(child_type, selection_set, fragments, &children)?;
+                    entry.set_item("fields", children)?;
+                }
+            }
+        }
+        entry.set_item("cost", cost)?;
+        fields.append(entry)?;
+        Ok(cost)
+    }
+}
+
+// The planned field tree of an operation. Each field names its resolver and an estimated cost:
+// the Python resolver calls it makes per parent object, counting list items once and the most
+// expensive branch of abstract types.
+pub(crate) fn explain_operation<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+    bundle: &Bound<'py, PyAny>,
+    doc: &ExecutableDocument,
+    root: &str,
+    selection_set: &SelectionSet,
+) -> PyResult<Bound<'py, PyDict>> {
+    let explainer = Explainer {
+        py,
+        registry,
+        doc,
+        resolvers: resolver_table(py, bundle)?,
+    };
+    let fields = PyList::empty(py);
+    let cost = explainer.selection(root, selection_set, &mut Vec::new(), &fields)?;
+    let plan = PyDict::new(py);
+    plan.set_item("root", root)?;
+    plan.set_item("cost", cost)?;
+    plan.set_item("fields", fields)?;
+    Ok(plan)
+}
@@ -0,0 +1,164 @@
+use async_graphql::Value;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyModule, PyString};
+
+use crate::errors::py_value_error;
+
+// Payloads with at least this many values convert through a single orjson call and a serde
+// pass over the encoded JSON, instead of one Python call per value. Below it, the extra
+// encoding costs more than it saves.
+const FAST_PATH_VALUES: usize = 1024;
+
+// orjson options making it fail on dataclasses, datetimes, and subclasses of builtin types,
+// should one slip past the check for plain payloads.
+const PASSTHROUGH_OPTIONS: [&str; 3] = [
+    "OPT_PASSTHROUGH_DATACLASS",
+    "OPT_PASSTHROUGH_DATETIME",
+    "OPT_PASSTHROUGH_SUBCLASS",
+];
+
+struct Orjson {
+    module: Py<PyModule>,
+    options: u64,
+}
+
+// The orjson module with its passthrough options, or `None` when it is not installed.
+fn orjson(py: Python<'_>) -> Option<&Orjson> {
+    static ORJSON: PyOnceLock<Option<Orjson>> = PyOnceLock::new();
+    ORJSON
+        .get_or_init(py, || {
+            let module = py.import("orjson").ok()?;
+            let mut options = 0;
+            for name in PASSTHROUGH_OPTIONS {
+                options |= module.getattr(name).ok()?.extract::<u64>().ok()?;
+            }
+            Some(Orjson {
+                module: module.unbind(),
+                options,
+            })
+        })
+        .as_ref()
+}
+
+// The number of values in a Python payload that converts through orjson exactly as through
+// `py_to_value`: builtin dicts with string keys, lists, strings, booleans, 64-bit integers,
+// finite floats and `None`, nested no deeper than `max_depth` and without cycles. `None` when
+// it holds anything else, so that such payloads convert, or fail, on the recursive path.
+fn plain_values(value: &Bound<'_, PyAny>, max_depth: usize) -> Option<usize> {
+    let mut open: Vec<(usize, Vec<Bound<'_, PyAny>>)> = Vec::new();
+    let mut count = 0;
+    let mut item = value.clone();
+    loop {
+        count += 1;
+        if let Some(items) = plain_items(&item)? {
+            let id = item.as_ptr() as usize;
+            if open.len() == max_depth || open.iter().any(|(open_id, _)| *open_id == id) {
+                return None;
+            }
+            open.push((id, items));
+        }
+        item = loop {
+            let Some((_, items)) = open.last_mut() else {
+                return Some(count);
+            };
+            match items.pop() {
+                Some(next) => break next,
+                None => drop(open.pop()),
+            }
+        };
+    }
+}
+
+// The items of a plain list or dict, `Some(None)` for a plain scalar, or `None` for anything
+// else.
+fn plain_items<'py>(value: &Bound<'py, PyAny>) -> Option<Option<Vec<Bound<'py, PyAny>>>> {
+    if value.is_none() || value.is_exact_instance_of::<PyBool>() {
+        return Some(None);
+    }
+    if value.is_exact_instance_of::<PyString>() {
+        return Some(None);
+    }
+    if value.is_exact_instance_of::<PyInt>() {
+        return value.extract::<i64>().ok().map(|_| None);
+    }
+    if value.is_exact_instance_of::<PyFloat>() {
+        let float = value.extract::<f64>().ok()?;
+        return float.is_finite().then_some(None);
+    }
+    if let Ok(list) = value.cast_exact::<PyList>() {
+        return Some(Some(list.iter().collect()));
+    }
+    let dict = value.cast_exact::<PyDict>().ok()?;
+    let mut items = Vec::with_capacity(dict.len());
+    for (key, item) in dict.iter() {
+        if !key.is_exact_instance_of::<PyString>() {
+            return None;
+        }
+        items.push(item);
+    }
+    Some(Some(items))
+}
+
+// The number of values in a response payload, or `None` when it holds binary values, which
+// decode differently from JSON, or nests deeper than `max_depth`.
+fn value_count(value: &Value, max_depth: usize) -> Option<usize> {
+    let nested = |depth: usize| depth.checked_sub(1);
+    match value {
+        Value::List(items) => {
+            let depth = nested(max_depth)?;
+            items
+                .iter()
+                .try_fold(1, |count, item| Some(count + value_count(item, depth)?))
+        }
+        Value::Object(map) => {
+            let depth = nested(max_depth)?;
+            map.values()
+                .try_fold(1, |count, item| Some(count + value_count(item, depth)?))
+        }
+        Value::Binary(_) => None,
+        _ => Some(1),
+    }
+}
+
+// Converts a large Python payload by encoding it with orjson. `None` when orjson is missing,
+// the payload is small, or it holds values orjson would convert differently.
+pub(crate) fn py_to_value_fast(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    max_depth: usize,
+) -> Option<Value> {
+    let orjson = orjson(py)?;
+    if plain_values(value, max_depth)? < FAST_PATH_VALUES {
+        return None;
+    }
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("option", orjson.options).ok()?;
+    let encoded = orjson
+        .module
+        .bind(py)
+        .call_method("dumps", (value,), Some(&kwargs))
+        .ok()?;
+    serde_json::from_slice(encoded.cast::<PyBytes>().ok()?.as_bytes()).ok()
+}
+
+// Converts a large response payload by decoding its JSON encoding with orjson. `None` when
+// orjson is missing, or the payload is small or converts differently.
+pub(crate) fn value_to_py_fast<'py>(
+    py: Python<'py>,
+    value: &Value,
+    max_depth: usize,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Some(orjson) = orjson(py) else {
+        return Ok(None);
+    };
+    if value_count(value, max_depth).is_none_or(|count| count < FAST_PATH_VALUES) {
+        return Ok(None);
+    }
+    let encoded = serde_json::to_vec(value).map_err(|err| py_value_error(err.to_string()))?;
+    let decoded = orjson
+        .module
+        .bind(py)
+        .call_method1("loads", (PyBytes::new(py, &encoded),))?;
+    Ok(Some(decoded))
+}
@@ -0,0 +1,62 @@
+use async_graphql::Response;
+use pyo3::prelude::*;
+use serde_json::{Number, Value};
+
+use crate::errors::py_value_error;
+
+// The SHA-256 hex digest of a response's data and errors in canonical JSON. Extensions are
+// left out, so debug stats and coercion warnings do not change it.
+pub(crate) fn response_fingerprint(py: Python<'_>, response: &Response) -> PyResult<String> {
+    let mut body = serde_json::to_value(response).map_err(|err| py_value_error(err.to_string()))?;
+    if let Value::Object(body) = &mut body {
+        body.remove("extensions");
+    }
+    let mut canonical = String::new();
+    write_canonical(&body, &mut canonical);
+    py.import("hashlib")?
+        .call_method1("sha256", (canonical.as_bytes(),))?
+        .call_method0("hexdigest")?
+        .extract()
+}
+
+// Compact JSON with object keys sorted at every level and numbers in one spelling per value.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Number(number) => out.push_str(&canonical_number(number)),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| key.as_str());
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+        _ => out.push_str(&value.to_string()),
+    }
+}
+
+// Floats without a fractional part are written as integers, so `2.0` and `2` match.
+fn canonical_number(number: &Number) -> String {
+    match number.as_f64() {
+        Some(float) if number.is_f64() && float.fract() == 0.0 && float.abs() < 9.0e15 => {
+            (float as i64).to_string()
+        }
+        _ => number.to_string(),
+    }
+}
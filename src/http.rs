@@ -0,0 +1,59 @@
+use async_graphql::Value;
+use pyo3::prelude::*;
+
+use crate::errors::py_value_error;
+use crate::limits::parse_variables;
+use crate::values::{ValueSettings, value_to_py_bound};
+
+// The parameters of a GraphQL-over-HTTP GET request.
+pub(crate) struct GetRequest {
+    pub(crate) query: String,
+    pub(crate) operation_name: Option<String>,
+    pub(crate) variables: Option<Value>,
+}
+
+// Decode a URL query string. `variables` is a JSON-encoded object; empty parameters are
+// treated as absent and unknown parameters are ignored.
+pub(crate) fn parse_get_request(query_string: &str) -> Result<GetRequest, String> {
+    let query_string = query_string.strip_prefix('?').unwrap_or(query_string);
+    let (mut query, mut operation_name, mut variables) = (None, None, None);
+    for (key, value) in form_urlencoded::parse(query_string.as_bytes()) {
+        let slot = match key.as_ref() {
+            "query" => &mut query,
+            "operationName" => &mut operation_name,
+            "variables" => &mut variables,
+            _ => continue,
+        };
+        *slot = Some(value.into_owned()).filter(|value| !value.is_empty());
+    }
+    let query = query.ok_or_else(|| "Missing \"query\" parameter.".to_string())?;
+    let variables = match variables {
+        Some(variables) => match parse_variables(variables.as_bytes()) {
+            Ok(Value::Null) => None,
+            Ok(variables) => Some(variables),
+            Err(error) => return Err(error.message),
+        },
+        None => None,
+    };
+    Ok(GetRequest {
+        query,
+        operation_name,
+        variables,
+    })
+}
+
+// Returns `(query, operation_name, variables)` for a GET request's query string.
+#[pyfunction]
+pub(crate) fn parse_http_get(
+    py: Python<'_>,
+    query_string: &str,
+) -> PyResult<(String, Option<String>, Option<Py<PyAny>>)> {
+    let request = parse_get_request(query_string).map_err(py_value_error)?;
+    let variables = request
+        .variables
+        .map(|variables| {
+            value_to_py_bound(py, &variables, ValueSettings::default()).map(Bound::unbind)
+        })
+        .transpose()?;
+    Ok((request.query, request.operation_name, variables))
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use async_graphql::futures_util::lock::Mutex as AsyncMutex;
+use async_graphql::parser::types::{ExecutableDocument, OperationType};
+use async_graphql::{Response, Value};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::caches::HitCounter;
+use crate::document::select_operation;
+
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Responses of mutations run with an idempotency key, kept for `ttl` so that requests replaying
+// the key with the same operation get the first response back instead of running it again.
+// Requests with a key that is still running wait for it in `running` first.
+pub(crate) struct IdempotencyCache {
+    ttl: RwLock<Duration>,
+    entries: Mutex<HashMap<IdempotencyKey, (Instant, Response)>>,
+    running: Mutex<HashMap<IdempotencyKey, Arc<AsyncMutex<()>>>>,
+    lookups: HitCounter,
+}
+
+// A client's key with a hash of the schema it ran against and of the query and variables it was
+// sent with, so that a key reused for a different operation, by a role-restricted variant, or
+// after a reload does not replay the wrong response.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct IdempotencyKey {
+    key: String,
+    operation: u64,
+}
+
+impl IdempotencyKey {
+    // The cache key of a request, or `None` when the operation it runs is not a mutation.
+    // `schema` identifies the definition and roles the request runs against.
+    pub(crate) fn for_mutation(
+        key: String,
+        schema: impl Hash,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Option<&Value>,
+        doc: &ExecutableDocument,
+    ) -> Option<Self> {
+        let (_name, operation) = select_operation(doc, operation_name)?;
+        if operation.node.ty != OperationType::Mutation {
+            return None;
+        }
+        let mut hasher = DefaultHasher::new();
+        schema.hash(&mut hasher);
+        query.hash(&mut hasher);
+        operation_name.hash(&mut hasher);
+        if let Some(variables) = variables {
+            hash_value(variables, &mut hasher);
+        }
+        Some(IdempotencyKey {
+            key,
+            operation: hasher.finish(),
+        })
+    }
+}
+
+// Hashes a value with the keys of its objects in sorted order, so that variables sent with their
+// fields in another order hash alike.
+fn hash_value(value: &Value, hasher: &mut DefaultHasher) {
+    mem::discriminant(value).hash(hasher);
+    match value {
+        Value::Null => {}
+        Value::Number(number) => number.to_string().hash(hasher),
+        Value::String(text) => text.hash(hasher),
+        Value::Boolean(flag) => flag.hash(hasher),
+        Value::Binary(bytes) => bytes.hash(hasher),
+        Value::Enum(name) => name.as_str().hash(hasher),
+        Value::List(items) => {
+            items.len().hash(hasher);
+            for item in items {
+                hash_value(item, hasher);
+            }
+        }
+        Value::Object(map) => {
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_by_key(|(name, _)| *name);
+            fields.len().hash(hasher);
+            for (name, value) in fields {
+                name.as_str().hash(hasher);
+                hash_value(value, hasher);
+            }
+        }
+    }
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        IdempotencyCache {
+            ttl: RwLock::new(DEFAULT_TTL),
+            entries: Mutex::new(HashMap::new()),
+            running: Mutex::new(HashMap::new()),
+            lookups: HitCounter::default(),
+        }
+    }
+}
+
+impl IdempotencyCache {
+    pub(crate) fn set_ttl(&self, ttl: Duration) {
+        *self.ttl.write().unwrap_or_else(PoisonError::into_inner) = ttl;
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        *self.ttl.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // The lock requests with `key` hold from looking up its response until they store their own,
+    // so that a duplicate sent while the first is running replays its response once it is
+    // stored. Locks no request holds or waits on are dropped.
+    pub(crate) fn reserve(&self, key: &IdempotencyKey) -> Arc<AsyncMutex<()>> {
+        let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+        running.retain(|_, lock| Arc::strong_count(lock) > 1);
+        running.entry(key.clone()).or_default().clone()
+    }
+
+    // A copy of the response stored under `key`, marked with `extensions.idempotent_replay`.
+    pub(crate) fn replay(&self, key: &IdempotencyKey) -> Option<Response> {
+        let ttl = self.ttl();
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.retain(|_, (stored, _)| stored.elapsed() < ttl);
+        let stored = entries.get(key);
+        self.lookups.record(stored.is_some());
+        let mut response = copy_response(&stored?.1);
+        response
+            .extensions
+            .insert("idempotent_replay".to_owned(), Value::Boolean(true));
+        Some(response)
+    }
+
+    pub(crate) fn store(&self, key: IdempotencyKey, response: &Response) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, (Instant::now(), copy_response(response)));
+    }
+
+    pub(crate) fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        self.lookups.to_py(py, entries)
+    }
+}
+
+fn copy_response(response: &Response) -> Response {
+    let mut copy = Response::new(response.data.clone());
+    copy.extensions = response.extensions.clone();
+    copy.errors = response.errors.clone();
+    copy.cache_control = response.cache_control;
+    copy.http_headers = response.http_headers.clone();
+    copy
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_graphql::Value;
+use async_graphql::dynamic::TypeRef;
+use async_graphql::registry::{MetaType, MetaTypeName, Registry};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+
+use crate::types::ConvertCtx;
+use crate::values::value_to_py_bound;
+
+// Global IDs are `base64("<type name>:<id>")`, as in the Relay server specification.
+pub(crate) fn encode_global_id(type_name: &str, id: &str) -> String {
+    STANDARD.encode(format!("{type_name}:{id}"))
+}
+
+pub(crate) fn decode_global_id(global_id: &str) -> Option<(String, String)> {
+    let decoded = String::from_utf8(STANDARD.decode(global_id).ok()?).ok()?;
+    let (type_name, id) = decoded.split_once(':')?;
+    Some((type_name.to_string(), id.to_string()))
+}
+
+// A Relay global ID decoded into the node type and raw id it was encoded from, as resolvers of
+// schemas built with `typed_ids=True` receive `ID` arguments naming nodes. `str()` encodes it
+// back.
+#[pyclass(module = "grommet._core", name = "GlobalID", frozen, eq, hash)]
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) struct GlobalId {
+    #[pyo3(get, name = "type")]
+    type_name: String,
+    #[pyo3(get)]
+    id: String,
+}
+
+#[pymethods]
+impl GlobalId {
+    #[new]
+    fn new(type_name: String, id: String) -> Self {
+        GlobalId { type_name, id }
+    }
+
+    fn __str__(&self) -> String {
+        encode_global_id(&self.type_name, &self.id)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GlobalID(type={:?}, id={:?})", self.type_name, self.id)
+    }
+}
+
+// The node types whose global IDs `ID` arguments are decoded from, attached to requests of
+// schemas built with `typed_ids=True` that have Relay node types.
+#[derive(Clone)]
+pub(crate) struct TypedIds(Arc<HashSet<String>>);
+
+impl TypedIds {
+    pub(crate) fn new(node_types: impl IntoIterator<Item = String>) -> Self {
+        TypedIds(Arc::new(node_types.into_iter().collect()))
+    }
+}
+
+// The input object types of a built schema holding `ID` fields at any depth, whose values convert
+// field by field so that nested ids reach resolvers as `ID` arguments do.
+#[derive(Clone)]
+pub(crate) struct IdInputs(Arc<HashSet<String>>);
+
+impl IdInputs {
+    pub(crate) fn of(registry: &Registry) -> Option<Self> {
+        let mut inputs = HashSet::new();
+        loop {
+            let found = inputs.len();
+            for (name, ty) in &registry.types {
+                if let MetaType::InputObject { input_fields, .. } = ty
+                    && !inputs.contains(name)
+                    && input_fields.values().any(|field| {
+                        let named = MetaTypeName::concrete_typename(&field.ty);
+                        named == TypeRef::ID || inputs.contains(named)
+                    })
+                {
+                    inputs.insert(name.clone());
+                }
+            }
+            if inputs.len() == found {
+                break;
+            }
+        }
+        (!inputs.is_empty()).then(|| IdInputs(Arc::new(inputs)))
+    }
+
+    pub(crate) fn holds_ids(&self, ty: &str) -> bool {
+        self.0.contains(MetaTypeName::concrete_typename(ty))
+    }
+}
+
+// An `ID` argument as resolvers receive it: strings as they are and integers as strings, with
+// global IDs of node types decoded into `GlobalID`s under typed ids. Lists convert item by item.
+pub(crate) fn id_argument_to_py<'py>(
+    py: Python<'py>,
+    value: &Value,
+    convert: &ConvertCtx<'_>,
+) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Number(id) => Ok(PyString::new(py, &id.to_string()).into_any()),
+        Value::String(id) => {
+            let node = convert.typed_ids.and_then(|typed| {
+                decode_global_id(id).filter(|(type_name, _)| typed.0.contains(type_name))
+            });
+            match node {
+                Some((type_name, id)) => Ok(Bound::new(py, GlobalId { type_name, id })?.into_any()),
+                None => Ok(PyString::new(py, id).into_any()),
+            }
+        }
+        Value::List(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(id_argument_to_py(py, item, convert)?)?;
+            }
+            Ok(list.into_any())
+        }
+        other => value_to_py_bound(py, other, convert.values),
+    }
+}
+
+// A value of input type `ty` holding ids: input objects convert field by field and lists item by
+// item, with `ID` values converting as `ID` arguments do.
+pub(crate) fn id_input_to_py<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+    ty: &str,
+    value: &Value,
+    convert: &ConvertCtx<'_>,
+) -> PyResult<Bound<'py, PyAny>> {
+    match (MetaTypeName::create(ty), value) {
+        (MetaTypeName::NonNull(inner), _) => id_input_to_py(py, registry, inner, value, convert),
+        (MetaTypeName::List(inner), Value::List(items)) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(id_input_to_py(py, registry, inner, item, convert)?)?;
+            }
+            Ok(list.into_any())
+        }
+        (MetaTypeName::Named(TypeRef::ID), _) => id_argument_to_py(py, value, convert),
+        (MetaTypeName::Named(name), Value::Object(map)) => {
+            let Some(MetaType::InputObject { input_fields, .. }) = registry.types.get(name) else {
+                return value_to_py_bound(py, value, convert.values);
+            };
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                let item = match input_fields.get(key.as_str()) {
+                    Some(field) => id_input_to_py(py, registry, &field.ty, item, convert)?,
+                    None => value_to_py_bound(py, item, convert.values)?,
+                };
+                dict.set_item(key.as_str(), item)?;
+            }
+            Ok(dict.into_any())
+        }
+        _ => value_to_py_bound(py, value, convert.values),
+    }
+}
@@ -0,0 +1,84 @@
+use async_graphql::dynamic::ResolverContext;
+use async_graphql::{QueryPathSegment, Value};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use crate::document::OperationSummary;
+use crate::types::{ContextValue, ConvertCtx, SchemaBundle};
+use crate::values::value_to_py_bound;
+
+// Details of the field being resolved, for resolvers that declare a `grommet.Info` parameter.
+// Mirrors the attributes of strawberry's `Info` so resolvers written against it keep working.
+#[pyclass(module = "grommet._core", name = "Info", frozen)]
+pub(crate) struct Info {
+    #[pyo3(get)]
+    field_name: String,
+    #[pyo3(get)]
+    context: Py<PyAny>,
+    #[pyo3(get)]
+    root_value: Py<PyAny>,
+    #[pyo3(get)]
+    path: Py<PyList>,
+    #[pyo3(get)]
+    schema: Py<PyAny>,
+    #[pyo3(get)]
+    variable_values: Py<PyAny>,
+    #[pyo3(get)]
+    operation_name: Option<String>,
+}
+
+#[pymethods]
+impl Info {
+    fn __repr__(&self) -> String {
+        format!("Info(field_name={:?})", self.field_name)
+    }
+}
+
+// The path from the operation root to the field, with list indices as integers.
+fn path_list<'py>(py: Python<'py>, ctx: &ResolverContext<'_>) -> PyResult<Bound<'py, PyList>> {
+    let path = PyList::empty(py);
+    if let Some(node) = ctx.path_node {
+        let mut segments: Vec<_> = std::iter::once(&node)
+            .chain(node.parents())
+            .map(|node| node.segment)
+            .collect();
+        segments.reverse();
+        for segment in segments {
+            match segment {
+                QueryPathSegment::Name(name) => path.append(name)?,
+                QueryPathSegment::Index(index) => path.append(index)?,
+            }
+        }
+    }
+    Ok(path)
+}
+
+pub(crate) fn build_info<'py>(
+    py: Python<'py>,
+    ctx: &ResolverContext<'_>,
+) -> PyResult<Bound<'py, Info>> {
+    let variables = Value::Object(
+        ctx.query_env
+            .variables
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect(),
+    );
+    let info = Info {
+        field_name: ctx.field().name().to_string(),
+        context: ctx
+            .data_opt::<ContextValue>()
+            .map_or_else(|| py.None(), |context| context.0.clone_ref(py)),
+        root_value: py.None(),
+        path: path_list(py, ctx)?.unbind(),
+        schema: ctx
+            .data_opt::<SchemaBundle>()
+            .map_or_else(|| py.None(), |bundle| bundle.0.clone_ref(py)),
+        variable_values: value_to_py_bound(py, &variables, ConvertCtx::from_request(ctx).values)?
+            .unbind(),
+        operation_name: ctx
+            .data_opt::<OperationSummary>()
+            .and_then(|summary| summary.name.clone()),
+    };
+    Bound::new(py, info)
+}
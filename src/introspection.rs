@@ -0,0 +1,196 @@
+use async_graphql::dynamic::Schema;
+use async_graphql::parser::types::{ExecutableDocument, Field, SelectionSet};
+use async_graphql::{Request, Response, Value};
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+
+use crate::check::single_operation;
+use crate::errors::py_value_error;
+use crate::ordering::collect_fields;
+
+// The introspection query of graphql-js' `getIntrospectionQuery`, with descriptions, scalar
+// `specifiedByURL`, repeatable directives, and deprecated input values requested.
+const INTROSPECTION_QUERY: &str = r"
+query IntrospectionQuery {
+  __schema {
+    description
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      isRepeatable
+      locations
+      args(includeDeprecated: true) { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  specifiedByURL
+  isOneOf
+  fields(includeDeprecated: true) {
+    name
+    description
+    args(includeDeprecated: true) { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields(includeDeprecated: true) { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+  isDeprecated
+  deprecationReason
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+";
+
+// Introspection result of the schema as JSON, in the `{"__schema": ...}` shape tools such as
+// graphql-codegen read.
+pub(crate) async fn introspection_json(schema: &Schema, minified: bool) -> PyResult<Vec<u8>> {
+    let response = schema.execute(Request::new(INTROSPECTION_QUERY)).await;
+    if !response.errors.is_empty() {
+        let messages: Vec<String> = response
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect();
+        return Err(py_value_error(messages.join("\n")));
+    }
+    let json = if minified {
+        serde_json::to_vec(&response.data)
+    } else {
+        serde_json::to_vec_pretty(&response.data)
+    };
+    json.map_err(|err| py_value_error(err.to_string()))
+}
+
+// How introspection results are cut down before conversion, so public APIs can serve them
+// cheaply: descriptions are dropped, or shortened to `max_description_length` characters.
+#[derive(Clone, Copy)]
+pub(crate) struct IntrospectionTrim {
+    pub(crate) omit_descriptions: bool,
+    pub(crate) max_description_length: Option<usize>,
+}
+
+impl IntrospectionTrim {
+    // Trims the `description` fields selected under the operation's root `__schema` and `__type`
+    // fields, however they are aliased or spread in through fragments.
+    pub(crate) fn apply(self, doc: &ExecutableDocument, response: &mut Response) {
+        let Some(operation) = single_operation(doc) else {
+            return;
+        };
+        let Value::Object(data) = &mut response.data else {
+            return;
+        };
+        for (key, fields) in selected_fields(doc, &[&operation.selection_set.node]) {
+            if matches!(fields[0].name.node.as_str(), "__schema" | "__type")
+                && let Some(value) = data.get_mut(key)
+            {
+                self.trim(doc, &fields, value);
+            }
+        }
+    }
+
+    fn trim(self, doc: &ExecutableDocument, fields: &[&Field], value: &mut Value) {
+        match value {
+            Value::List(items) => items
+                .iter_mut()
+                .for_each(|item| self.trim(doc, fields, item)),
+            Value::Object(object) => {
+                let selection_sets: Vec<_> = fields
+                    .iter()
+                    .map(|field| &field.selection_set.node)
+                    .collect();
+                for (key, fields) in selected_fields(doc, &selection_sets) {
+                    let Some(value) = object.get_mut(key) else {
+                        continue;
+                    };
+                    if fields[0].name.node.as_str() == "description" {
+                        self.trim_description(value);
+                    } else {
+                        self.trim(doc, &fields, value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn trim_description(self, description: &mut Value) {
+        if self.omit_descriptions {
+            *description = Value::Null;
+        } else if let (Value::String(text), Some(max)) = (description, self.max_description_length)
+            && let Some((end, _)) = text.char_indices().nth(max)
+        {
+            text.truncate(end);
+            text.push('…');
+        }
+    }
+}
+
+fn selected_fields<'a>(
+    doc: &'a ExecutableDocument,
+    selection_sets: &[&'a SelectionSet],
+) -> IndexMap<&'a str, Vec<&'a Field>> {
+    let mut fields = IndexMap::new();
+    for selection_set in selection_sets {
+        collect_fields(doc, selection_set, &mut fields);
+    }
+    fields
+}
@@ -1,23 +1,94 @@
 #![forbid(unsafe_code)]
 
 mod api;
+mod batch;
+mod builder;
+mod caches;
+mod call;
+mod capture;
+mod check;
+mod codegen;
+mod constraints;
+mod coverage;
+mod definition;
+mod diff;
+mod document;
 mod errors;
+mod execution;
+mod explain;
+#[cfg(feature = "orjson")]
+mod fastpath;
+mod fingerprint;
+mod http;
+mod idempotency;
+mod ids;
+mod info;
+mod introspection;
+mod limits;
+mod mock;
+mod msgpack;
+mod operations;
+mod options;
+mod ordering;
+mod rate_limit;
+mod reflection;
+mod registry;
+mod relay;
+mod remote;
+mod replay;
+mod request;
 mod resolver;
+mod response;
+mod roles;
 mod schema_types;
+mod shadow;
+mod signature;
+mod stream;
+mod subscriptions;
 mod types;
 mod values;
+mod visibility;
 
 use pyo3::prelude::*;
 
-use crate::api::{SchemaWrapper, SubscriptionStream};
-use crate::values::OperationResult;
+use crate::api::SchemaWrapper;
+use crate::builder::{FieldSelection, QueryBuilder};
+use crate::definition::validate_definition;
+use crate::diff::compare_responses;
+use crate::errors::add_exceptions;
+use crate::http::parse_http_get;
+use crate::ids::GlobalId;
+use crate::info::Info;
+use crate::registry::{ResolverRegistry, SchemaRegistry};
+use crate::replay::{Complete, Event};
+use crate::response::ResponseHandle;
+use crate::signature::py_operation_signature;
+use crate::stream::SubscriptionStream;
+use crate::values::{ExecutionResult, GraphQLError, OperationResult, SubscriptionPayload};
 
 // pyo3 module entrypoint for the python extension
 #[pymodule(gil_used = false)]
 #[doc(hidden)]
 pub fn _core(_py: Python<'_>, module: &Bound<'_, PyModule>) -> PyResult<()> {
+    add_exceptions(module)?;
     module.add_class::<SchemaWrapper>()?;
+    module.add_class::<SchemaRegistry>()?;
+    module.add_class::<ResolverRegistry>()?;
+    module.add_class::<QueryBuilder>()?;
+    module.add_class::<FieldSelection>()?;
     module.add_class::<SubscriptionStream>()?;
     module.add_class::<OperationResult>()?;
+    module.add_class::<ExecutionResult>()?;
+    module.add_class::<SubscriptionPayload>()?;
+    module.add_class::<GraphQLError>()?;
+    module.add_class::<ResponseHandle>()?;
+    module.add_class::<Event>()?;
+    module.add_class::<Complete>()?;
+    module.add_class::<Info>()?;
+    module.add_class::<GlobalId>()?;
+    module.add_function(wrap_pyfunction!(compare_responses, module)?)?;
+    module.add_function(wrap_pyfunction!(parse_http_get, module)?)?;
+    module.add_function(wrap_pyfunction!(py_operation_signature, module)?)?;
+    module.add_function(wrap_pyfunction!(validate_definition, module)?)?;
     Ok(())
 }
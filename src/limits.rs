@@ -0,0 +1,204 @@
+use async_graphql::{ErrorExtensionValues, Response, ServerError, Value};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+
+use crate::errors::is_response_too_large;
+use crate::types::ResponseBudget;
+use crate::values::payload_bytes;
+
+const BAD_REQUEST_STATUS: i32 = 400;
+
+// Size limits checked before a request is parsed or executed, and on what it returns: lists
+// resolvers return and the size of the response are checked as fields are converted, and the
+// response once more before it is handed back. `None` disables a limit.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RequestLimits {
+    pub(crate) max_query_bytes: Option<usize>,
+    pub(crate) max_variables: Option<usize>,
+    pub(crate) max_variable_bytes: Option<usize>,
+    pub(crate) max_response_bytes: Option<usize>,
+    pub(crate) max_list_items: Option<usize>,
+}
+
+fn bad_request(message: String) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "BAD_REQUEST");
+    extensions.set("status", BAD_REQUEST_STATUS);
+    let mut error = ServerError::new(message, None);
+    error.extensions = Some(extensions);
+    error
+}
+
+// The estimated size of a Python value without its nested values, which are queued on
+// `pending` unless the value alone already exceeds `budget`.
+fn shallow_payload_bytes<'py>(
+    value: &Bound<'py, PyAny>,
+    budget: usize,
+    pending: &mut Vec<Bound<'py, PyAny>>,
+) -> usize {
+    if value.is_none() {
+        4
+    } else if let Ok(flag) = value.cast::<PyBool>() {
+        if flag.is_true() { 4 } else { 5 }
+    } else if value.is_instance_of::<PyInt>() || value.is_instance_of::<PyFloat>() {
+        value.str().map_or(0, |text| text.len().unwrap_or(0))
+    } else if let Ok(text) = value.cast::<PyString>() {
+        text.len().unwrap_or(0) + 2
+    } else if let Ok(map) = value.cast::<PyDict>() {
+        let mut size = 2 + map.len().saturating_sub(1);
+        for (key, item) in map.iter() {
+            if size > budget {
+                break;
+            }
+            size += key
+                .cast::<PyString>()
+                .map_or(0, |key| key.len().unwrap_or(0))
+                + 3;
+            pending.push(item);
+        }
+        size
+    } else if let Ok(items) = value.cast::<PyList>() {
+        let size = 2 + items.len().saturating_sub(1);
+        if size <= budget {
+            pending.extend(items.iter());
+        }
+        size
+    } else if let Ok(items) = value.cast::<PyTuple>() {
+        let size = 2 + items.len().saturating_sub(1);
+        if size <= budget {
+            pending.extend(items.iter());
+        }
+        size
+    } else {
+        0
+    }
+}
+
+// A query as given to `execute`: text, or the UTF-8 encoded bytes of a request body, decoded
+// here so HTTP handlers need not decode it first.
+#[derive(FromPyObject)]
+pub(crate) enum QuerySource {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl QuerySource {
+    pub(crate) fn decode(self) -> Result<String, ServerError> {
+        match self {
+            QuerySource::Text(query) => Ok(query),
+            QuerySource::Bytes(bytes) => String::from_utf8(bytes)
+                .map_err(|err| bad_request(format!("Query is not valid UTF-8: {err}"))),
+        }
+    }
+}
+
+// Decode an encoded JSON variables payload, which must be an object or `null`.
+pub(crate) fn parse_variables(payload: &[u8]) -> Result<Value, ServerError> {
+    match serde_json::from_slice(payload) {
+        Ok(variables @ (Value::Object(_) | Value::Null)) => Ok(variables),
+        Ok(_) => Err(bad_request("Variables must be a JSON object.".to_string())),
+        Err(err) => Err(bad_request(format!("Invalid variables JSON: {err}"))),
+    }
+}
+
+impl RequestLimits {
+    pub(crate) fn to_py<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let limits = PyDict::new(py);
+        limits.set_item("max_query_bytes", self.max_query_bytes)?;
+        limits.set_item("max_variables", self.max_variables)?;
+        limits.set_item("max_variable_bytes", self.max_variable_bytes)?;
+        limits.set_item("max_response_bytes", self.max_response_bytes)?;
+        limits.set_item("max_list_items", self.max_list_items)?;
+        Ok(limits)
+    }
+
+    pub(crate) fn check_query(&self, query: &str) -> Result<(), ServerError> {
+        match self.max_query_bytes {
+            Some(limit) if query.len() > limit => Err(bad_request(format!(
+                "Query is {} bytes, exceeding the limit of {limit} bytes.",
+                query.len()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_variables(&self, variables: &Value) -> Result<(), ServerError> {
+        if let Value::Object(map) = variables {
+            self.check_variable_count(map.len())?;
+        }
+        if let Some(limit) = self.max_variable_bytes {
+            let size = payload_bytes(variables);
+            if size > limit {
+                return Err(bad_request(format!(
+                    "Variables payload is {size} bytes, exceeding the limit of {limit} bytes."
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_variable_count(&self, count: usize) -> Result<(), ServerError> {
+        match self.max_variables {
+            Some(limit) if count > limit => Err(bad_request(format!(
+                "Request has {count} variables, exceeding the limit of {limit}."
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    // Checks a Python variables payload before it is converted, so an oversized one is rejected
+    // without being materialized. Sizes are estimated as `check_variables` does for the plain
+    // containers and scalars, counting stops at the limit, and any other values are left to
+    // the exact check once converted.
+    pub(crate) fn check_py_variables(
+        &self,
+        variables: &Bound<'_, PyAny>,
+    ) -> Result<(), ServerError> {
+        if let Ok(map) = variables.cast::<PyDict>() {
+            self.check_variable_count(map.len())?;
+        }
+        let Some(limit) = self.max_variable_bytes else {
+            return Ok(());
+        };
+        let mut size = 0;
+        let mut pending = vec![variables.clone()];
+        while let Some(value) = pending.pop() {
+            size += shallow_payload_bytes(&value, limit.saturating_sub(size), &mut pending);
+            if size > limit {
+                return Err(bad_request(format!(
+                    "Variables payload exceeds the limit of {limit} bytes."
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // Drops the data of a response over the size limit, which fails with a
+    // `RESPONSE_TOO_LARGE` error instead. A response whose fields already passed the limit
+    // while resolving reports it once, in place of the errors of those fields.
+    pub(crate) fn check_response(&self, response: &mut Response, budget: Option<&ResponseBudget>) {
+        let Some(limit) = self.max_response_bytes else {
+            return;
+        };
+        let message = if budget.is_some_and(ResponseBudget::exceeded) {
+            Python::attach(|py| {
+                response
+                    .errors
+                    .retain(|error| !is_response_too_large(py, error));
+            });
+            format!("Response exceeds the limit of {limit} bytes.")
+        } else {
+            let size = payload_bytes(&response.data);
+            if size <= limit {
+                return;
+            }
+            format!("Response is {size} bytes, exceeding the limit of {limit} bytes.")
+        };
+        let mut extensions = ErrorExtensionValues::default();
+        extensions.set("code", "RESPONSE_TOO_LARGE");
+        let mut error = ServerError::new(message, None);
+        error.extensions = Some(extensions);
+        response.data = Value::Null;
+        response.errors.push(error);
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_graphql::dynamic::{FieldValue, ResolverContext, TypeRef};
+use async_graphql::registry::{MetaType, Registry};
+use async_graphql::{Error, Name, Value};
+use pyo3::exceptions::PyNotImplementedError;
+use pyo3::prelude::*;
+
+use crate::resolver::field_path;
+use crate::types::FieldContext;
+
+// Settings of an `execute_mock` request. Each generated value is drawn from a generator seeded
+// by the seed and the value's path, so a seed reproduces the same data however the fields are
+// scheduled.
+#[derive(Clone)]
+pub(crate) struct MockData {
+    seed: u64,
+    list_length: usize,
+}
+
+// The parent value of fields under a generated object; their resolvers have nothing to read.
+struct MockObject;
+
+impl MockData {
+    pub(crate) fn new(seed: Option<u64>, list_length: usize) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_nanos() as u64)
+        });
+        MockData { seed, list_length }
+    }
+
+    // Generated data for the fields of a generated object, which have no Python object to
+    // resolve from. `None` when the field resolves as usual.
+    pub(crate) fn resolve(
+        ctx: &ResolverContext<'_>,
+        field_ctx: &FieldContext,
+    ) -> Option<FieldValue<'static>> {
+        let mock = ctx.data_opt::<MockData>()?;
+        ctx.parent_value.downcast_ref::<MockObject>()?;
+        Some(mock.generate_field(ctx, field_ctx))
+    }
+
+    // Generated data in place of a stub resolver that raised `NotImplementedError`; other
+    // errors fail the field as usual.
+    pub(crate) fn stub(
+        ctx: &ResolverContext<'_>,
+        field_ctx: &FieldContext,
+        err: Error,
+    ) -> Result<FieldValue<'static>, Error> {
+        let Some(mock) = ctx.data_opt::<MockData>() else {
+            return Err(err);
+        };
+        let source = err
+            .source
+            .as_ref()
+            .and_then(|source| source.downcast_ref::<PyErr>());
+        let unimplemented = Python::attach(|py| {
+            source.is_some_and(|source| source.is_instance_of::<PyNotImplementedError>(py))
+        });
+        if !unimplemented {
+            return Err(err);
+        }
+        Ok(mock.generate_field(ctx, field_ctx))
+    }
+
+    fn generate_field(
+        &self,
+        ctx: &ResolverContext<'_>,
+        field_ctx: &FieldContext,
+    ) -> FieldValue<'static> {
+        self.generate(
+            &ctx.schema_env.registry,
+            ctx.field().name(),
+            &field_path(ctx),
+            &field_ctx.output_type,
+        )
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+        field_name: &str,
+        path: &str,
+        output_type: &TypeRef,
+    ) -> FieldValue<'static> {
+        let draw = self.draw(path);
+        match output_type {
+            TypeRef::NonNull(inner) => self.generate(registry, field_name, path, inner),
+            TypeRef::List(inner) => FieldValue::list((0..self.list_length).map(|index| {
+                self.generate(registry, field_name, &format!("{path}.{index}"), inner)
+            })),
+            TypeRef::Named(name) => match registry.types.get(name.as_ref()) {
+                Some(MetaType::Object { .. }) => FieldValue::owned_any(MockObject),
+                Some(
+                    MetaType::Interface { possible_types, .. }
+                    | MetaType::Union { possible_types, .. },
+                ) => {
+                    let mut names: Vec<&String> = possible_types.iter().collect();
+                    names.sort();
+                    match names.get(pick(draw, names.len())) {
+                        Some(name) => FieldValue::owned_any(MockObject).with_type(name.to_string()),
+                        None => FieldValue::NULL,
+                    }
+                }
+                Some(MetaType::Enum { enum_values, .. }) => {
+                    match enum_values.keys().nth(pick(draw, enum_values.len())) {
+                        Some(value) => FieldValue::value(Value::Enum(Name::new(value))),
+                        None => FieldValue::NULL,
+                    }
+                }
+                _ => FieldValue::value(scalar_value(name, field_name, draw)),
+            },
+        }
+    }
+
+    // A pseudo-random number for the value at `path`: FNV-1a over the seed and path, mixed
+    // with the SplitMix64 finalizer.
+    fn draw(&self, path: &str) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in self.seed.to_le_bytes().iter().chain(path.as_bytes()) {
+            hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+fn pick(draw: u64, len: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (draw % len as u64) as usize
+    }
+}
+
+// Builtin scalars get values of their kind; custom scalars get strings, as most serialize to
+// one.
+fn scalar_value(type_name: &str, field_name: &str, draw: u64) -> Value {
+    match type_name {
+        "Int" => Value::from(draw % 1000),
+        "Float" => Value::from((draw % 100_000) as f64 / 100.0),
+        "Boolean" => Value::Boolean(draw.is_multiple_of(2)),
+        "ID" => Value::String((draw % 100_000).to_string()),
+        _ => Value::String(format!("{field_name} {}", draw % 1000)),
+    }
+}
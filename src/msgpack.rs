@@ -0,0 +1,104 @@
+use serde_json::Value;
+
+// Minimal MessagePack encoder for JSON-shaped values, so responses can be emitted in either
+// wire format from the same serialized tree.
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize, fix: Option<(u8, usize)>, markers: [u8; 3]) {
+    match fix {
+        Some((prefix, limit)) if len < limit => out.push(prefix | len as u8),
+        _ if len <= u8::MAX as usize && markers[0] != 0 => {
+            out.push(markers[0]);
+            out.push(len as u8);
+        }
+        _ if len <= u16::MAX as usize => {
+            out.push(markers[1]);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        _ => {
+            out.push(markers[2]);
+            out.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+    }
+}
+
+fn write_int(out: &mut Vec<u8>, value: i64) {
+    if (0..=0x7f).contains(&value) {
+        out.push(value as u8);
+    } else if (-32..0).contains(&value) {
+        out.push(value as i8 as u8);
+    } else if value >= 0 {
+        write_uint(out, value as u64);
+    } else if value >= i64::from(i8::MIN) {
+        out.push(0xd0);
+        out.push(value as i8 as u8);
+    } else if value >= i64::from(i16::MIN) {
+        out.push(0xd1);
+        out.extend_from_slice(&(value as i16).to_be_bytes());
+    } else if value >= i64::from(i32::MIN) {
+        out.push(0xd2);
+        out.extend_from_slice(&(value as i32).to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_uint(out: &mut Vec<u8>, value: u64) {
+    if value <= 0x7f {
+        out.push(value as u8);
+    } else if value <= u64::from(u8::MAX) {
+        out.push(0xcc);
+        out.push(value as u8);
+    } else if value <= u64::from(u16::MAX) {
+        out.push(0xcd);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= u64::from(u32::MAX) {
+        out.push(0xce);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(0xcf);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_str(out: &mut Vec<u8>, text: &str) {
+    write_len(out, text.len(), Some((0xa0, 32)), [0xd9, 0xda, 0xdb]);
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(flag) => out.push(if *flag { 0xc3 } else { 0xc2 }),
+        Value::Number(number) => {
+            if let Some(int) = number.as_i64() {
+                write_int(out, int);
+            } else if let Some(uint) = number.as_u64() {
+                write_uint(out, uint);
+            } else {
+                out.push(0xcb);
+                let float = number.as_f64().unwrap_or_default();
+                out.extend_from_slice(&float.to_be_bytes());
+            }
+        }
+        Value::String(text) => write_str(out, text),
+        Value::Array(items) => {
+            write_len(out, items.len(), Some((0x90, 16)), [0, 0xdc, 0xdd]);
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        Value::Object(map) => {
+            write_len(out, map.len(), Some((0x80, 16)), [0, 0xde, 0xdf]);
+            for (key, item) in map {
+                write_str(out, key);
+                write_value(out, item);
+            }
+        }
+    }
+}
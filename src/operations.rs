@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use async_graphql::Response;
+use async_graphql::futures_util::future::join_all;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::api::SchemaWrapper;
+use crate::call::call_query;
+use crate::errors::{assign_error_codes, call_failed};
+use crate::execution::{Entry, Prepared};
+use crate::fingerprint::response_fingerprint;
+use crate::limits::QuerySource;
+use crate::mock::MockData;
+use crate::request::{ManagedContexts, RequestOptions, VariablesSource, clone_context};
+use crate::response::response_into_py;
+use crate::values::{response_to_py, value_to_py_bound};
+
+impl SchemaWrapper {
+    // Runs a request for schema-first development, resolving unimplemented fields to generated
+    // data of their type.
+    pub(crate) async fn mock(
+        &self,
+        query: String,
+        variables: Option<Py<PyAny>>,
+        context: Option<Py<PyAny>>,
+        seed: Option<u64>,
+        list_length: usize,
+    ) -> PyResult<Py<PyAny>> {
+        let response = self
+            .respond(
+                QuerySource::Text(query),
+                VariablesSource::Python(variables),
+                context,
+                RequestOptions::default(),
+                Entry::Mock(MockData::new(seed, list_length)),
+            )
+            .await?;
+        Python::attach(|py| response_into_py(py, response, false, self.result_format(false)))
+    }
+
+    // The SHA-256 hex digest of the canonical JSON of a response's data and errors.
+    pub(crate) async fn fingerprint(
+        &self,
+        query: String,
+        variables: Option<Py<PyAny>>,
+        context: Option<Py<PyAny>>,
+    ) -> PyResult<String> {
+        let mut response = self
+            .respond(
+                QuerySource::Text(query),
+                VariablesSource::Python(variables),
+                context,
+                RequestOptions::default(),
+                Entry::Single,
+            )
+            .await?;
+        Python::attach(|py| {
+            assign_error_codes(py, &mut response.errors);
+            response_fingerprint(py, &response)
+        })
+    }
+
+    // Runs a query root field for its value, raising the response's first error instead.
+    pub(crate) async fn call_field(
+        &self,
+        field_name: String,
+        context: Option<Py<PyAny>>,
+        args: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let arg_names = Python::attach(|py| match &args {
+            Some(args) => args.bind(py).keys().extract::<Vec<String>>(),
+            None => Ok(Vec::new()),
+        })?;
+        let query = call_query(self.schema().registry(), &field_name, &arg_names)?;
+        let variables = VariablesSource::Python(args.map(Py::into_any));
+        let response = self
+            .respond(
+                QuerySource::Text(query),
+                variables,
+                context,
+                RequestOptions::default(),
+                Entry::Single,
+            )
+            .await?;
+        Python::attach(|py| {
+            if let Some(error) = response.errors.first() {
+                return Err(call_failed(py, error));
+            }
+            match &response.data {
+                async_graphql::Value::Object(data) => match data.get(field_name.as_str()) {
+                    Some(value) => {
+                        Ok(value_to_py_bound(py, value, self.options.value_settings())?.unbind())
+                    }
+                    None => Ok(py.None()),
+                },
+                _ => Ok(py.None()),
+            }
+        })
+    }
+
+    // Runs the named operations of a document concurrently against its shared parse, each
+    // through `run` as a request of its own.
+    pub(crate) async fn run_document(
+        &self,
+        query: String,
+        operations: Vec<String>,
+        variables: Option<HashMap<String, Py<PyAny>>>,
+        context: Option<Py<PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let contexts = ManagedContexts::new(self.options.manage_context, &context);
+        let result = async {
+            let limits = self.options.limits();
+            let checked = limits.check_query(&query);
+            let parsed = checked.clone().and_then(|()| self.parse_document(&query));
+            let mut variables = variables.unwrap_or_default();
+            let mut requests = Vec::with_capacity(operations.len());
+            for name in operations {
+                let request = match &checked {
+                    Ok(()) => self
+                        .checked_variables(&limits, variables.remove(&name))?
+                        .map(|variables| Prepared {
+                            query: query.clone(),
+                            variables,
+                            parsed: Some(parsed.clone()),
+                        }),
+                    Err(error) => Err(error.clone()),
+                };
+                requests.push((name, request));
+            }
+
+            let contexts = &contexts;
+            let responses = join_all(requests.into_iter().map(|(name, request)| {
+                let context = clone_context(&context);
+                let options = RequestOptions::operation(name.clone());
+                async move {
+                    let response = match request {
+                        Ok(request) => {
+                            self.run_single(request, context, options, Entry::Single, contexts)
+                                .await?
+                        }
+                        Err(error) => Response::from_errors(vec![error]),
+                    };
+                    Ok::<_, PyErr>((name, response))
+                }
+            }))
+            .await;
+
+            let format = self.result_format(false);
+            Python::attach(|py| {
+                let results = PyDict::new(py);
+                for response in responses {
+                    let (name, response) = response?;
+                    results.set_item(name, response_to_py(py, response, format)?)?;
+                }
+                Ok(results.into_any().unbind())
+            })
+        }
+        .await;
+        contexts.finish(result).await
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use async_graphql::parser::types::ExecutableDocument;
+use async_graphql::registry::{MetaType, Registry};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::api::sorted;
+use crate::caches::HitCounter;
+use crate::capture::CallCapture;
+use crate::coverage::Coverage;
+use crate::errors::{
+    ambiguous_resolver_key, mutation_field_requires_resolver, underivable_resolver_key,
+    unknown_interface_binding, unknown_interface_implementor, unknown_resolver_key,
+    unknown_schema_option, unknown_serializer_type,
+};
+use crate::idempotency::IdempotencyCache;
+use crate::introspection::IntrospectionTrim;
+use crate::limits::RequestLimits;
+use crate::rate_limit::RateLimits;
+use crate::reflection::python_resolver_keys;
+use crate::registry::ResolverRegistry;
+use crate::remote::RemoteExecutor;
+use crate::replay::ReplayBuffer;
+use crate::schema_types::SdlHeader;
+use crate::shadow::ShadowTraffic;
+use crate::subscriptions::ActiveSubscriptions;
+use crate::types::{
+    DefaultResolver, InterfaceBindings, NodeResolvers, PyObj, SerialLocks, Serializers,
+    SourceLookup,
+};
+use crate::values::ValueSettings;
+
+// Runtime settings shared by a schema handle and every variant derived from it.
+#[derive(Default)]
+pub(crate) struct SchemaOptions {
+    pub(crate) limits: RwLock<RequestLimits>,
+    pub(crate) result_hook: RwLock<Option<PyObj>>,
+    pub(crate) node_resolvers: RwLock<Option<NodeResolvers>>,
+    pub(crate) replay: Arc<std::sync::Mutex<ReplayBuffer>>,
+    pub(crate) sdl_header: RwLock<SdlHeader>,
+    pub(crate) coverage: std::sync::Mutex<Coverage>,
+    pub(crate) debug: AtomicBool,
+    pub(crate) source_lookup: RwLock<Option<SourceLookup>>,
+    pub(crate) subscriptions: ActiveSubscriptions,
+    pub(crate) default_resolver: Option<DefaultResolver>,
+    pub(crate) serializers: Option<Serializers>,
+    pub(crate) interfaces: Option<InterfaceBindings>,
+    // Whether results are `ExecutionResult`s rather than `OperationResult`s.
+    pub(crate) execution_result: bool,
+    // Whether request contexts are closed once their request completes.
+    pub(crate) manage_context: bool,
+    // Whether `ID` arguments naming Relay nodes reach resolvers as `GlobalID`s.
+    pub(crate) typed_ids: bool,
+    // Whether undescribed fields and types take the first line of their docstring instead.
+    pub(crate) docstring_descriptions: bool,
+    // Whether every field of the mutation root must have a resolver rather than read its source.
+    pub(crate) require_mutation_resolvers: bool,
+    pub(crate) values: RwLock<ValueSettings>,
+    pub(crate) call_capture: RwLock<Option<CallCapture>>,
+    pub(crate) rate_limits: RwLock<Option<Arc<RateLimits>>>,
+    pub(crate) remote: RwLock<Option<RemoteExecutor>>,
+    pub(crate) pre_execute: RwLock<Option<PyObj>>,
+    pub(crate) idempotency: IdempotencyCache,
+    pub(crate) serial_locks: SerialLocks,
+    pub(crate) introspection_trim: RwLock<Option<IntrospectionTrim>>,
+    pub(crate) shadow: RwLock<Option<ShadowTraffic>>,
+    // Parsed operations registered by `warm_up`, keyed by their source.
+    pub(crate) documents: RwLock<HashMap<String, Arc<ExecutableDocument>>>,
+    pub(crate) document_lookups: HitCounter,
+}
+
+impl SchemaOptions {
+    // Options given to the constructor as keyword arguments.
+    pub(crate) fn from_py(py: Python<'_>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut parsed = SchemaOptions::default();
+        for (key, value) in options.into_iter().flatten() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "default_resolver" | "serializers" | "interfaces" if value.is_none() => {}
+                "default_resolver" => parsed.default_resolver = Some(default_resolver(py, &value)?),
+                "serializers" => {
+                    let serializers = value
+                        .extract::<HashMap<String, Py<PyAny>>>()?
+                        .into_iter()
+                        .map(|(type_name, serializer)| (type_name, PyObj::new(serializer)))
+                        .collect();
+                    parsed.serializers = Some(Serializers(Arc::new(serializers)));
+                }
+                "interfaces" => {
+                    let mut bindings = HashMap::new();
+                    for (interface, implementors) in
+                        value.extract::<HashMap<String, Bound<'_, PyDict>>>()?
+                    {
+                        let implementors = implementors
+                            .iter()
+                            .map(|(type_name, class)| {
+                                Ok((type_name.extract()?, PyObj::new(class.unbind())))
+                            })
+                            .collect::<PyResult<Vec<_>>>()?;
+                        bindings.insert(interface, implementors);
+                    }
+                    parsed.interfaces = Some(InterfaceBindings(Arc::new(bindings)));
+                }
+                "execution_result" => parsed.execution_result = value.extract()?,
+                "manage_context" => parsed.manage_context = value.extract()?,
+                "typed_ids" => parsed.typed_ids = value.extract()?,
+                "docstring_descriptions" => parsed.docstring_descriptions = value.extract()?,
+                "require_mutation_resolvers" => {
+                    parsed.require_mutation_resolvers = value.extract()?;
+                }
+                _ => return Err(unknown_schema_option(&key)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    // Rejects default resolvers, serializers and interface bindings naming what the schema
+    // does not have.
+    pub(crate) fn check_bindings(&self, registry: &Registry) -> PyResult<()> {
+        let types = &registry.types;
+        if let Some(resolvers) = &self.default_resolver {
+            for key in resolvers.0.keys() {
+                let known = match key.split_once('.') {
+                    None => key == "*",
+                    Some((type_name, "*")) => {
+                        types.get(type_name).is_some_and(|ty| ty.fields().is_some())
+                    }
+                    Some((type_name, field_name)) => types
+                        .get(type_name)
+                        .and_then(|ty| ty.field_by_name(field_name))
+                        .is_some(),
+                };
+                if !known {
+                    return Err(unknown_resolver_key(key));
+                }
+            }
+        }
+        if let Some(serializers) = &self.serializers {
+            for type_name in serializers.0.keys() {
+                if !matches!(types.get(type_name), Some(MetaType::Object { .. })) {
+                    return Err(unknown_serializer_type(type_name));
+                }
+            }
+        }
+        if let Some(interfaces) = &self.interfaces {
+            for (interface, implementors) in interfaces.0.iter() {
+                let Some(MetaType::Interface { possible_types, .. }) = types.get(interface) else {
+                    return Err(unknown_interface_binding(interface));
+                };
+                for (type_name, _) in implementors {
+                    if !possible_types.contains(type_name) {
+                        return Err(unknown_interface_implementor(interface, type_name));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Rejects mutation root fields without a resolver of their own when required, since a
+    // mutation read from its source is almost always a mistake.
+    pub(crate) fn check_mutation_resolvers(
+        &self,
+        registry: &Registry,
+        bundle: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        let Some(mutation) = registry
+            .mutation_type
+            .as_deref()
+            .filter(|_| self.require_mutation_resolvers)
+        else {
+            return Ok(());
+        };
+        let resolvers = python_resolver_keys(bundle)?;
+        let fields = registry.types.get(mutation).and_then(MetaType::fields);
+        for field_name in fields.into_iter().flat_map(|fields| fields.keys()) {
+            if !field_name.starts_with("__")
+                && !resolvers.contains(&format!("{mutation}.{field_name}"))
+            {
+                return Err(mutation_field_requires_resolver(mutation, field_name));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn value_settings(&self) -> ValueSettings {
+        *self.values.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn limits(&self) -> RequestLimits {
+        *self.limits.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn result_hook(&self) -> Option<PyObj> {
+        self.result_hook
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn node_resolvers(&self) -> Option<NodeResolvers> {
+        self.node_resolvers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn source_lookup(&self) -> Option<SourceLookup> {
+        self.source_lookup
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn shadow(&self) -> Option<ShadowTraffic> {
+        self.shadow
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn introspection_trim(&self) -> Option<IntrospectionTrim> {
+        *self
+            .introspection_trim
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn rate_limits(&self) -> Option<Arc<RateLimits>> {
+        self.rate_limits
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn remote(&self) -> Option<RemoteExecutor> {
+        self.remote
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn call_capture(&self) -> Option<CallCapture> {
+        self.call_capture
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn debug(&self) -> bool {
+        self.debug.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn pre_execute(&self) -> Option<PyObj> {
+        self.pre_execute
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    // The constructor bindings for `debug_info`, callables by repr.
+    pub(crate) fn bindings_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let bindings = PyDict::new(py);
+        let callables = |callables: Option<&Arc<HashMap<String, PyObj>>>| {
+            let reprs = PyDict::new(py);
+            for (key, callable) in callables.into_iter().flat_map(|callables| callables.iter()) {
+                reprs.set_item(key, callable.bind(py).repr()?)?;
+            }
+            Ok::<_, PyErr>(reprs)
+        };
+        let default_resolver = self.default_resolver.as_ref().map(|resolver| &resolver.0);
+        bindings.set_item("default_resolver", callables(default_resolver)?)?;
+        let serializers = self.serializers.as_ref().map(|serializers| &serializers.0);
+        bindings.set_item("serializers", callables(serializers)?)?;
+        let interfaces = PyDict::new(py);
+        for (interface, implementors) in self
+            .interfaces
+            .iter()
+            .flat_map(|bindings| bindings.0.iter())
+        {
+            let names: Vec<&str> = implementors.iter().map(|(name, _)| name.as_str()).collect();
+            interfaces.set_item(interface, names)?;
+        }
+        bindings.set_item("interfaces", interfaces)?;
+        Ok(bindings)
+    }
+
+    // The runtime settings for `debug_info`, hooks only by whether they are set.
+    pub(crate) fn settings_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let settings = PyDict::new(py);
+        settings.set_item("debug", self.debug())?;
+        settings.set_item("execution_result", self.execution_result)?;
+        settings.set_item("manage_context", self.manage_context)?;
+        settings.set_item("typed_ids", self.typed_ids)?;
+        settings.set_item("docstring_descriptions", self.docstring_descriptions)?;
+        settings.set_item(
+            "require_mutation_resolvers",
+            self.require_mutation_resolvers,
+        )?;
+        let values = self.value_settings();
+        settings.set_item("max_value_depth", values.max_depth)?;
+        settings.set_item("gil_yield_interval", values.gil_yield_interval)?;
+        settings.set_item("string_policy", values.strings.name())?;
+        settings.set_item(
+            "coverage",
+            self.coverage
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .is_enabled(),
+        )?;
+        settings.set_item(
+            "replay_buffer",
+            self.replay
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .capacity(),
+        )?;
+        settings.set_item("idempotency_ttl_s", self.idempotency.ttl().as_secs_f64())?;
+        let source_lookup = self
+            .source_lookup()
+            .map(|lookup| {
+                let entry = PyDict::new(py);
+                let order: Vec<&str> = lookup.order.iter().map(|kind| kind.name()).collect();
+                entry.set_item("order", order)?;
+                entry.set_item("strict", lookup.strict)?;
+                Ok::<_, PyErr>(entry)
+            })
+            .transpose()?;
+        settings.set_item("source_lookup", source_lookup)?;
+        let trim = self
+            .introspection_trim()
+            .map(|trim| {
+                let entry = PyDict::new(py);
+                entry.set_item("omit_descriptions", trim.omit_descriptions)?;
+                entry.set_item("max_description_length", trim.max_description_length)?;
+                Ok::<_, PyErr>(entry)
+            })
+            .transpose()?;
+        settings.set_item("introspection_trim", trim)?;
+        let remote = self.remote();
+        settings.set_item("remote", remote.as_ref().map(RemoteExecutor::redacted_url))?;
+        settings.set_item("rate_limits", self.rate_limits().is_some())?;
+        settings.set_item("shadow", self.shadow().is_some())?;
+        settings.set_item("result_hook", self.result_hook().is_some())?;
+        settings.set_item("pre_execute", self.pre_execute().is_some())?;
+        settings.set_item("call_capture", self.call_capture().is_some())?;
+        let node_resolvers = self.node_resolvers();
+        settings.set_item(
+            "node_resolvers",
+            node_resolvers
+                .as_ref()
+                .map(|resolvers| sorted(resolvers.0.keys())),
+        )?;
+        Ok(settings)
+    }
+}
+
+// A default resolver given as a callable for every data field, a dict or registry of them
+// keyed by field, or a list keyed by their qualified names.
+fn default_resolver(py: Python<'_>, resolver: &Bound<'_, PyAny>) -> PyResult<DefaultResolver> {
+    let resolvers = if let Ok(resolvers) = resolver.cast::<PyDict>() {
+        resolvers.extract::<HashMap<String, Py<PyAny>>>()?
+    } else if let Ok(registry) = resolver.cast::<ResolverRegistry>() {
+        registry.get().resolvers(py)
+    } else if let Ok(resolvers) = resolver.cast::<PyList>() {
+        keyed_by_qualname(resolvers)?
+    } else {
+        HashMap::from([("*".to_string(), resolver.clone().unbind())])
+    };
+    let resolvers = resolvers
+        .into_iter()
+        .map(|(key, resolver)| (key, PyObj::new(resolver)))
+        .collect();
+    Ok(DefaultResolver(Arc::new(resolvers)))
+}
+
+// Keys a list of resolvers by the last two parts of their `__qualname__`, so that methods of a
+// class named like a type resolve the fields they are named after: `Query.weather` is keyed
+// `Query.weather`.
+fn keyed_by_qualname(resolvers: &Bound<'_, PyList>) -> PyResult<HashMap<String, Py<PyAny>>> {
+    let mut keyed = HashMap::new();
+    for resolver in resolvers {
+        let qualname: String = resolver.getattr("__qualname__")?.extract()?;
+        let parts: Vec<&str> = qualname.rsplitn(3, '.').collect();
+        let [field_name, type_name, ..] = parts[..] else {
+            return Err(underivable_resolver_key(&qualname));
+        };
+        let key = format!("{type_name}.{field_name}");
+        if keyed.contains_key(&key) {
+            return Err(ambiguous_resolver_key(&key));
+        }
+        keyed.insert(key, resolver.unbind());
+    }
+    Ok(keyed)
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_graphql::parser::types::{
+    DocumentOperations, ExecutableDocument, Field, OperationDefinition, Selection, SelectionSet,
+};
+use async_graphql::{Response, Value};
+use indexmap::IndexMap;
+
+use crate::check::single_operation;
+
+// How the object keys of response data are ordered. The engine inserts sibling fields as they
+// finish resolving, so without reordering concurrently resolved fields come back in whatever
+// order they completed.
+#[derive(Clone)]
+pub(crate) enum KeyOrder {
+    // The order the operation selects the fields in; `operation` names the executed operation
+    // of a multi-operation document.
+    Selection {
+        doc: Arc<ExecutableDocument>,
+        operation: Option<String>,
+    },
+    // Alphabetical at every level, for canonical output.
+    Sorted,
+}
+
+impl KeyOrder {
+    pub(crate) fn apply(&self, response: &mut Response) {
+        match self {
+            KeyOrder::Sorted => sort_keys(&mut response.data),
+            KeyOrder::Selection { doc, operation } => {
+                if let Some(op) = find_operation(doc, operation.as_deref()) {
+                    order_keys(doc, &[&op.selection_set.node], &mut response.data);
+                }
+            }
+        }
+    }
+}
+
+fn find_operation<'a>(
+    doc: &'a ExecutableDocument,
+    name: Option<&str>,
+) -> Option<&'a OperationDefinition> {
+    match (&doc.operations, name) {
+        (DocumentOperations::Multiple(operations), Some(name)) => {
+            operations.get(name).map(|operation| &operation.node)
+        }
+        _ => single_operation(doc),
+    }
+}
+
+// Response keys selected by the given selection set, in first-selected order, each with the
+// fields merged under it. Fragments contribute their fields where they are spread.
+pub(crate) fn collect_fields<'a>(
+    doc: &'a ExecutableDocument,
+    selection_set: &'a SelectionSet,
+    fields: &mut IndexMap<&'a str, Vec<&'a Field>>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => fields
+                .entry(field.node.response_key().node.as_str())
+                .or_default()
+                .push(&field.node),
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = doc.fragments.get(&spread.node.fragment_name.node) {
+                    collect_fields(doc, &fragment.node.selection_set.node, fields);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_fields(doc, &fragment.node.selection_set.node, fields);
+            }
+        }
+    }
+}
+
+fn order_keys(doc: &ExecutableDocument, selection_sets: &[&SelectionSet], value: &mut Value) {
+    match value {
+        Value::List(items) => {
+            for item in items {
+                order_keys(doc, selection_sets, item);
+            }
+        }
+        Value::Object(object) => {
+            let mut fields = IndexMap::new();
+            for selection_set in selection_sets {
+                collect_fields(doc, selection_set, &mut fields);
+            }
+            // Leaf fields select nothing; object-valued scalars keep their own order.
+            if fields.is_empty() {
+                return;
+            }
+            object.sort_by_cached_key(|key, _| {
+                fields.get_index_of(key.as_str()).unwrap_or(usize::MAX)
+            });
+            for (key, value) in object.iter_mut() {
+                if let Some(fields) = fields.get(key.as_str()) {
+                    let selection_sets: Vec<_> = fields
+                        .iter()
+                        .map(|field| &field.selection_set.node)
+                        .collect();
+                    order_keys(doc, &selection_sets, value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::List(items) => items.iter_mut().for_each(sort_keys),
+        Value::Object(object) => {
+            object.sort_keys();
+            object.values_mut().for_each(sort_keys);
+        }
+        _ => {}
+    }
+}
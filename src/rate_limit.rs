@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use async_graphql::parser::types::ExecutableDocument;
+use async_graphql::registry::Registry;
+use async_graphql::{ErrorExtensionValues, ServerError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::document::{operation_field_coordinates, select_operation};
+use crate::errors::invalid_rate_limit;
+use crate::types::PyObj;
+
+const TOO_MANY_REQUESTS_STATUS: i32 = 429;
+
+// What a rule limits: requests running the named operation, or requests selecting the field
+// coordinate (`Type.field`) anywhere in their operation.
+enum RateTarget {
+    Operation(String),
+    Field(String),
+}
+
+impl RateTarget {
+    fn describe(&self) -> String {
+        match self {
+            RateTarget::Operation(name) => format!("operation \"{name}\""),
+            RateTarget::Field(coordinate) => format!("field \"{coordinate}\""),
+        }
+    }
+}
+
+// A token bucket holding up to `capacity` requests, refilled at `per_second` tokens a second.
+struct RateRule {
+    target: RateTarget,
+    capacity: f64,
+    per_second: f64,
+}
+
+impl RateRule {
+    fn from_py(rule: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let operation = rule.get_item("operation")?;
+        let field = rule.get_item("field")?;
+        let target = match (operation, field) {
+            (Some(name), None) => RateTarget::Operation(name.extract()?),
+            (None, Some(coordinate)) => RateTarget::Field(coordinate.extract()?),
+            _ => {
+                return Err(invalid_rate_limit(
+                    "must name exactly one of \"operation\" or \"field\"",
+                ));
+            }
+        };
+        let capacity: u32 = match rule.get_item("capacity")? {
+            Some(capacity) => capacity.extract()?,
+            None => return Err(invalid_rate_limit("must set \"capacity\"")),
+        };
+        let per_second: f64 = match rule.get_item("per_second")? {
+            Some(per_second) => per_second.extract()?,
+            None => return Err(invalid_rate_limit("must set \"per_second\"")),
+        };
+        if capacity == 0 || per_second.is_nan() || per_second <= 0.0 {
+            return Err(invalid_rate_limit(
+                "needs a positive \"capacity\" and \"per_second\"",
+            ));
+        }
+        Ok(RateRule {
+            target,
+            capacity: f64::from(capacity),
+            per_second,
+        })
+    }
+}
+
+// The fewest buckets kept before idle ones are swept.
+const MIN_SWEEP: usize = 64;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    // Whether the bucket has refilled by `now`, so that dropping it changes nothing.
+    fn refilled(&self, rule: &RateRule, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens + elapsed * rule.per_second >= rule.capacity
+    }
+}
+
+// The buckets in use, swept of refilled ones whenever they have doubled since the last sweep,
+// so keys seen once do not accumulate.
+struct Buckets {
+    entries: HashMap<(usize, Option<String>), Bucket>,
+    sweep_at: usize,
+}
+
+// Rate limiting rules checked before a request executes, with one bucket per rule and per key
+// the optional `key(context)` callable extracts, e.g. a user id. Requests without a key share
+// a bucket.
+pub(crate) struct RateLimits {
+    rules: Vec<RateRule>,
+    key: Option<PyObj>,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimits {
+    pub(crate) fn new(rules: &[Bound<'_, PyDict>], key: Option<PyObj>) -> PyResult<Self> {
+        Ok(RateLimits {
+            rules: rules
+                .iter()
+                .map(RateRule::from_py)
+                .collect::<PyResult<_>>()?,
+            key,
+            buckets: Mutex::new(Buckets {
+                entries: HashMap::new(),
+                sweep_at: MIN_SWEEP,
+            }),
+        })
+    }
+
+    // Takes a token from the bucket of every rule the operation the document runs matches, or
+    // none of them when one is empty, in which case the request is rejected with a
+    // `RATE_LIMITED` error whose `retryAfter` extension is the whole seconds until it would be
+    // allowed. The operation is the one named `operation_name`, or the document's only one; a
+    // document that runs none is not charged.
+    pub(crate) fn check(
+        &self,
+        py: Python<'_>,
+        registry: &Registry,
+        doc: &ExecutableDocument,
+        operation_name: Option<&str>,
+        context: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Result<(), ServerError>> {
+        let Some((name, operation)) = select_operation(doc, operation_name) else {
+            return Ok(Ok(()));
+        };
+        let fields: HashSet<String> = operation_field_coordinates(registry, doc, &operation.node)
+            .into_iter()
+            .map(|coordinate| format!("{}.{}", coordinate.type_name, coordinate.field_name))
+            .collect();
+        let matched: Vec<usize> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| match &rule.target {
+                RateTarget::Operation(target) => name.is_some_and(|name| name == target.as_str()),
+                RateTarget::Field(coordinate) => fields.contains(coordinate),
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if matched.is_empty() {
+            return Ok(Ok(()));
+        }
+        let key = match &self.key {
+            Some(key) => {
+                let context = context.map_or_else(|| py.None().into_bound(py), Clone::clone);
+                let key = key.bind(py).call1((context,))?;
+                (!key.is_none())
+                    .then(|| key.str().map(|key| key.to_string()))
+                    .transpose()?
+            }
+            None => None,
+        };
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.entries.len() >= buckets.sweep_at {
+            buckets
+                .entries
+                .retain(|(index, _), bucket| !bucket.refilled(&self.rules[*index], now));
+            buckets.sweep_at = (buckets.entries.len() * 2).max(MIN_SWEEP);
+        }
+        let buckets = &mut buckets.entries;
+        let mut exceeded: Option<(usize, f64)> = None;
+        for &index in &matched {
+            let rule = &self.rules[index];
+            let bucket = buckets
+                .entry((index, key.clone()))
+                .or_insert_with(|| Bucket {
+                    tokens: rule.capacity,
+                    updated: now,
+                });
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * rule.per_second).min(rule.capacity);
+            bucket.updated = now;
+            if bucket.tokens < 1.0 {
+                let wait = (1.0 - bucket.tokens) / rule.per_second;
+                if exceeded.is_none_or(|(_, longest)| wait > longest) {
+                    exceeded = Some((index, wait));
+                }
+            }
+        }
+        if let Some((index, wait)) = exceeded {
+            return Ok(Err(rate_limited(&self.rules[index].target, wait)));
+        }
+        for &index in &matched {
+            if let Some(bucket) = buckets.get_mut(&(index, key.clone())) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        Ok(Ok(()))
+    }
+}
+
+fn rate_limited(target: &RateTarget, wait: f64) -> ServerError {
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", "RATE_LIMITED");
+    extensions.set("status", TOO_MANY_REQUESTS_STATUS);
+    extensions.set("retryAfter", wait.ceil().max(1.0) as u64);
+    let mut error = ServerError::new(
+        format!("Rate limit exceeded for {}.", target.describe()),
+        None,
+    );
+    error.extensions = Some(extensions);
+    error
+}
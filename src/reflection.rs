@@ -0,0 +1,162 @@
+use std::collections::BTreeSet;
+
+use async_graphql::registry::{MetaInputValue, MetaType, Registry};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::errors::py_value_error;
+
+fn type_kind(ty: &MetaType) -> &'static str {
+    match ty {
+        MetaType::Scalar { .. } => "scalar",
+        MetaType::Object { .. } => "object",
+        MetaType::Interface { .. } => "interface",
+        MetaType::Union { .. } => "union",
+        MetaType::Enum { .. } => "enum",
+        MetaType::InputObject { .. } => "input_object",
+    }
+}
+
+fn type_description(ty: &MetaType) -> Option<&str> {
+    match ty {
+        MetaType::Scalar { description, .. }
+        | MetaType::Object { description, .. }
+        | MetaType::Interface { description, .. }
+        | MetaType::Union { description, .. }
+        | MetaType::Enum { description, .. }
+        | MetaType::InputObject { description, .. } => description.as_deref(),
+    }
+}
+
+// The schema's types in name order, without the introspection types.
+pub(crate) fn schema_types<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    registry
+        .types
+        .iter()
+        .filter(|(name, _)| !name.starts_with("__"))
+        .map(|(name, ty)| {
+            let entry = PyDict::new(py);
+            entry.set_item("name", name)?;
+            entry.set_item("kind", type_kind(ty))?;
+            entry.set_item("description", type_description(ty))?;
+            Ok(entry)
+        })
+        .collect()
+}
+
+// The scalars, enums, and input objects of the schema by kind. Scalars map to the repr of the
+// Python type that `bindings` (Python type to scalar name) binds to them, or `None` when no
+// Python type maps onto them.
+pub(crate) fn type_registries<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+    bindings: &Bound<'py, PyDict>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let scalars = PyDict::new(py);
+    for (python_type, name) in bindings {
+        scalars.set_item(name, python_type.repr()?)?;
+    }
+    let scalar_types = PyDict::new(py);
+    let (mut enums, mut inputs) = (Vec::new(), Vec::new());
+    for (name, ty) in registry
+        .types
+        .iter()
+        .filter(|(name, _)| !name.starts_with("__"))
+    {
+        match ty {
+            MetaType::Scalar { .. } => scalar_types.set_item(name, scalars.get_item(name)?)?,
+            MetaType::Enum { .. } => enums.push(name),
+            MetaType::InputObject { .. } => inputs.push(name),
+            _ => {}
+        }
+    }
+    let registries = PyDict::new(py);
+    registries.set_item("scalars", scalar_types)?;
+    registries.set_item("enums", enums)?;
+    registries.set_item("inputs", inputs)?;
+    Ok(registries)
+}
+
+fn input_value<'py>(py: Python<'py>, value: &MetaInputValue) -> PyResult<Bound<'py, PyDict>> {
+    let entry = PyDict::new(py);
+    entry.set_item("name", &value.name)?;
+    entry.set_item("type", &value.ty)?;
+    entry.set_item("description", value.description.as_deref())?;
+    entry.set_item("default", value.default_value.as_deref())?;
+    Ok(entry)
+}
+
+// The fields of an object, interface, or input object in declaration order, with their type
+// references (e.g. `[Int!]!`) and arguments. `resolver` tells whether a Python resolver
+// produces the field. Other kinds of types have no fields.
+pub(crate) fn type_fields<'py>(
+    py: Python<'py>,
+    registry: &Registry,
+    resolvers: &BTreeSet<String>,
+    type_name: &str,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let Some(ty) = registry.types.get(type_name) else {
+        return Err(py_value_error(format!("Unknown type \"{type_name}\".")));
+    };
+    if let MetaType::InputObject { input_fields, .. } = ty {
+        return input_fields
+            .values()
+            .map(|field| {
+                let entry = input_value(py, field)?;
+                entry.set_item("args", PyList::empty(py))?;
+                entry.set_item("resolver", false)?;
+                Ok(entry)
+            })
+            .collect();
+    }
+    let Some(fields) = ty.fields() else {
+        return Ok(Vec::new());
+    };
+    fields
+        .values()
+        .filter(|field| !field.name.starts_with("__"))
+        .map(|field| {
+            let entry = PyDict::new(py);
+            entry.set_item("name", &field.name)?;
+            entry.set_item("type", &field.ty)?;
+            entry.set_item("description", field.description.as_deref())?;
+            let args = field
+                .args
+                .values()
+                .map(|arg| input_value(py, arg))
+                .collect::<PyResult<Vec<_>>>()?;
+            entry.set_item("args", args)?;
+            let coordinate = format!("{type_name}.{}", field.name);
+            entry.set_item("resolver", resolvers.contains(&coordinate))?;
+            Ok(entry)
+        })
+        .collect()
+}
+
+// Coordinates (`Type.field`) of the fields and subscriptions backed by Python resolvers rather
+// than attribute reads.
+pub(crate) fn python_resolver_keys(bundle: &Bound<'_, PyAny>) -> PyResult<BTreeSet<String>> {
+    let mut keys = BTreeSet::new();
+    for compiled_type in bundle.getattr("types")?.try_iter()? {
+        let compiled_type = compiled_type?;
+        if !compiled_type.hasattr("object_fields")? {
+            continue;
+        }
+        let type_name: String = compiled_type.getattr("meta")?.getattr("name")?.extract()?;
+        let fields = compiled_type
+            .getattr("object_fields")?
+            .try_iter()?
+            .chain(compiled_type.getattr("subscription_fields")?.try_iter()?);
+        for field in fields {
+            let field = field?;
+            if field.hasattr("func")? {
+                let field_name: String = field.getattr("name")?.extract()?;
+                keys.insert(format!("{type_name}.{field_name}"));
+            }
+        }
+    }
+    Ok(keys)
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+use pyo3::types::PyCFunction;
+
+use crate::api::SchemaWrapper;
+use crate::errors::duplicate_resolver_registration;
+use crate::types::PyObj;
+
+// Named schema handles that can be replaced while requests are running. Executions keep the
+// handle they started with, so swapping in a new schema never affects in-flight operations.
+#[pyclass(module = "grommet._core", name = "SchemaRegistry", frozen)]
+pub(crate) struct SchemaRegistry {
+    schemas: RwLock<HashMap<String, Py<SchemaWrapper>>>,
+}
+
+#[pymethods]
+impl SchemaRegistry {
+    #[new]
+    fn new() -> Self {
+        SchemaRegistry {
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    // Install `schema` under `name` in one step, returning the handle it replaced.
+    fn swap(&self, name: String, schema: Py<SchemaWrapper>) -> Option<Py<SchemaWrapper>> {
+        self.schemas
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, schema)
+    }
+
+    fn get(&self, py: Python<'_>, name: &str) -> PyResult<Py<SchemaWrapper>> {
+        self.schemas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .map(|schema| schema.clone_ref(py))
+            .ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
+
+    fn remove(&self, name: &str) -> Option<Py<SchemaWrapper>> {
+        self.schemas
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name)
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .schemas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.schemas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(name)
+    }
+
+    fn __len__(&self) -> usize {
+        self.schemas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+// Default resolvers collected by decorators, keyed by field coordinate (`Type.field`), that a
+// schema takes as its `default_resolver` mapping. Schemas copy the registrations they are built
+// with, so one registry serves every rebuild of a schema during development.
+#[pyclass(module = "grommet._core", name = "ResolverRegistry", frozen)]
+pub(crate) struct ResolverRegistry {
+    resolvers: Arc<RwLock<HashMap<String, PyObj>>>,
+}
+
+impl ResolverRegistry {
+    pub(crate) fn resolvers(&self, py: Python<'_>) -> HashMap<String, Py<PyAny>> {
+        self.resolvers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(key, resolver)| (key.clone(), resolver.clone_ref(py)))
+            .collect()
+    }
+}
+
+// Whether two resolvers are the same definition, as when a reloaded module registers its
+// functions again.
+fn same_definition(registered: &Bound<'_, PyAny>, resolver: &Bound<'_, PyAny>) -> bool {
+    ["__module__", "__qualname__"].into_iter().all(|attr| {
+        match (registered.getattr(attr), resolver.getattr(attr)) {
+            (Ok(registered), Ok(resolver)) => registered.eq(resolver).unwrap_or(false),
+            _ => false,
+        }
+    })
+}
+
+#[pymethods]
+impl ResolverRegistry {
+    #[new]
+    fn new() -> Self {
+        ResolverRegistry {
+            resolvers: Arc::default(),
+        }
+    }
+
+    // A decorator registering its function as the resolver of `type_name.field_name` and
+    // returning it unchanged. A second resolver for the same field raises, unless it is the
+    // same definition registered again.
+    fn register<'py>(
+        &self,
+        py: Python<'py>,
+        type_name: &str,
+        field_name: &str,
+    ) -> PyResult<Bound<'py, PyCFunction>> {
+        let key = format!("{type_name}.{field_name}");
+        let resolvers = self.resolvers.clone();
+        PyCFunction::new_closure(
+            py,
+            Some(c"grommet_register_resolver"),
+            None,
+            move |args, _kwargs| -> PyResult<Py<PyAny>> {
+                let resolver = args.get_item(0)?;
+                let mut resolvers = resolvers.write().unwrap_or_else(PoisonError::into_inner);
+                if let Some(registered) = resolvers.get(&key)
+                    && !same_definition(&registered.bind(args.py()), &resolver)
+                {
+                    return Err(duplicate_resolver_registration(&key));
+                }
+                resolvers.insert(key.clone(), PyObj::new(resolver.clone().unbind()));
+                Ok(resolver.unbind())
+            },
+        )
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .resolvers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.resolvers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.resolvers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
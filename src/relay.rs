@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_graphql::dynamic::{
+    Field, FieldFuture, FieldValue, InputValue, Interface, InterfaceField, ResolverContext, TypeRef,
+};
+use async_graphql::{Error, Value};
+use pyo3::prelude::*;
+
+use crate::errors::{py_err_to_error, py_value_error};
+use crate::ids::{decode_global_id, encode_global_id};
+use crate::resolver::{
+    BoxFut, awaitable_into_future, convert_field_value, field_path, resolve_field,
+    resolve_field_sync_fast,
+};
+use crate::roles::RoleFilter;
+use crate::schema_types::decode_type_kind;
+use crate::types::{ContextValue, ConvertCtx, FieldContext, NodeResolvers};
+
+pub(crate) const NODE_INTERFACE: &str = "Node";
+const NODE_FIELD: &str = "node";
+const ID_FIELD: &str = "id";
+
+fn global_id_value(
+    type_name: &str,
+    value: Option<FieldValue<'_>>,
+) -> Result<Option<FieldValue<'static>>, Error> {
+    let id = match value.as_ref().and_then(FieldValue::as_value) {
+        None | Some(Value::Null) => return Ok(Some(FieldValue::NULL)),
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Number(id)) => id.to_string(),
+        Some(_) => {
+            return Err(Error::new(format!(
+                "The id of Relay node type \"{type_name}\" must be a string or integer."
+            )));
+        }
+    };
+    Ok(Some(FieldValue::value(encode_global_id(type_name, &id))))
+}
+
+// The `id` field of a Relay node type: resolves the raw id as usual and exposes it encoded as
+// a global `ID!`.
+pub(crate) fn global_id_field(
+    type_name: &str,
+    field_ctx: Arc<FieldContext>,
+    is_async: bool,
+) -> Field {
+    let type_name: Arc<str> = Arc::from(type_name);
+    Field::new(ID_FIELD, TypeRef::named_nn(TypeRef::ID), move |ctx| {
+        let type_name = type_name.clone();
+        if is_async {
+            let field_ctx = field_ctx.clone();
+            FieldFuture::new(async move {
+                let value = resolve_field(ctx, field_ctx).await?;
+                global_id_value(&type_name, value)
+            })
+        } else {
+            let result = resolve_field_sync_fast(&ctx, &field_ctx)
+                .and_then(|value| global_id_value(&type_name, Some(value)));
+            match result {
+                Ok(value) => FieldFuture::Value(value),
+                Err(err) => FieldFuture::new(async move { Err::<Option<FieldValue<'_>>, _>(err) }),
+            }
+        }
+    })
+}
+
+// Names of the visible object types flagged `relay_node`. Fails when the query root already
+// declares the `node` field the Relay support would add.
+pub(crate) fn relay_node_types(
+    py: Python<'_>,
+    query: &str,
+    types: &[Py<PyAny>],
+    filter: &RoleFilter,
+) -> PyResult<HashSet<String>> {
+    let mut node_types = HashSet::new();
+    let mut query_fields = Vec::new();
+    for compiled_type in types {
+        let compiled_type = compiled_type.bind(py);
+        let meta = compiled_type.getattr("meta")?;
+        if decode_type_kind(&meta)? != "object" {
+            continue;
+        }
+        let name: String = meta.getattr("name")?.extract()?;
+        if name == query {
+            for field in compiled_type
+                .getattr("object_fields")?
+                .extract::<Vec<Bound<'_, PyAny>>>()?
+            {
+                query_fields.push(field.getattr("name")?.extract::<String>()?);
+            }
+        }
+        if meta.getattr("relay_node")?.extract::<bool>()? && filter.allows_type(&name) {
+            node_types.insert(name);
+        }
+    }
+    if !node_types.is_empty() && query_fields.iter().any(|field| field == NODE_FIELD) {
+        return Err(py_value_error(format!(
+            "Type \"{query}\" already defines a \"{NODE_FIELD}\" field; Relay node types \
+             need it for global object identification."
+        )));
+    }
+    Ok(node_types)
+}
+
+// Fetch the object behind a Relay global ID with the fetcher registered for its type, called
+// as `fetcher(id, context)`.
+async fn resolve_node(
+    ctx: ResolverContext<'_>,
+    node_types: Arc<HashSet<String>>,
+) -> Result<Option<FieldValue<'_>>, Error> {
+    let global_id = ctx.args.try_get("id")?.string()?;
+    let (type_name, id) = decode_global_id(global_id)
+        .filter(|(type_name, _)| node_types.contains(type_name))
+        .ok_or_else(|| Error::new(format!("Invalid global ID \"{global_id}\".")))?;
+    let fetcher = ctx
+        .data_opt::<NodeResolvers>()
+        .and_then(|resolvers| resolvers.0.get(&type_name))
+        .ok_or_else(|| {
+            Error::new(format!(
+                "No node resolver is registered for type \"{type_name}\"."
+            ))
+        })?;
+    let context = ctx
+        .data_opt::<ContextValue>()
+        .map(|context| context.0.clone());
+
+    let future: BoxFut = Python::attach(|py| {
+        let context = context.map_or_else(|| py.None(), |context| context.clone_ref(py));
+        let value = fetcher.bind(py).call1((id, context))?;
+        if value.hasattr("__await__")? {
+            Ok(awaitable_into_future(value))
+        } else {
+            Ok(Box::pin(std::future::ready(Ok(value.unbind()))) as BoxFut)
+        }
+    })
+    .map_err(py_err_to_error)?;
+    let value = future.await.map_err(py_err_to_error)?;
+    let node_type = TypeRef::named(NODE_INTERFACE);
+    let path = field_path(&ctx);
+    let field_value = Python::attach(|py| {
+        convert_field_value(
+            py,
+            &path,
+            &ConvertCtx::from_request(&ctx),
+            value.bind(py),
+            &node_type,
+        )
+    })
+    .map_err(py_err_to_error)?;
+    Ok(Some(field_value))
+}
+
+pub(crate) fn node_interface() -> Interface {
+    Interface::new(NODE_INTERFACE)
+        .description("An object with a globally unique ID.")
+        .field(InterfaceField::new(
+            ID_FIELD,
+            TypeRef::named_nn(TypeRef::ID),
+        ))
+}
+
+pub(crate) fn node_field(node_types: HashSet<String>) -> Field {
+    let node_types = Arc::new(node_types);
+    Field::new(NODE_FIELD, TypeRef::named(NODE_INTERFACE), move |ctx| {
+        let node_types = node_types.clone();
+        FieldFuture::new(async move { resolve_node(ctx, node_types).await })
+    })
+    .description("Fetches an object given its global ID.")
+    .argument(InputValue::new(ID_FIELD, TypeRef::named_nn(TypeRef::ID)))
+}
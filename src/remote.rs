@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_graphql::dynamic::{FieldValue, ResolverContext, TypeRef};
+use async_graphql::parser::types::{Directive, Field, OperationType, Selection, SelectionSet};
+use async_graphql::registry::{MetaType, Registry};
+use async_graphql::{Error, Name, Positioned, Value};
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+use crate::errors::py_err_to_error;
+use crate::resolver::awaitable_into_future;
+use crate::types::{DefaultResolver, FieldContext, PyObj};
+
+// An upstream GraphQL service that root data fields without a resolver are forwarded to, each
+// as an operation of its own whose data is stitched into the local response. Requests are sent
+// by `transport(url, body, headers)`, awaited for the response body.
+#[derive(Clone)]
+pub(crate) struct RemoteExecutor(Arc<RemoteTarget>);
+
+struct RemoteTarget {
+    url: String,
+    headers: HashMap<String, String>,
+    transport: PyObj,
+}
+
+// The parent value of fields under an object the upstream service returned, keyed by response
+// key.
+struct RemoteObject(IndexMap<Name, Value>);
+
+impl RemoteExecutor {
+    pub(crate) fn new(url: String, headers: HashMap<String, String>, transport: PyObj) -> Self {
+        RemoteExecutor(Arc::new(RemoteTarget {
+            url,
+            headers,
+            transport,
+        }))
+    }
+
+    // The upstream URL with the credentials of its user info and the query string masked, for
+    // error messages and `debug_info`.
+    pub(crate) fn redacted_url(&self) -> String {
+        let url = self.0.url.as_str();
+        let (url, query) = match url.split_once('?') {
+            Some((url, _)) => (url, "?***"),
+            None => (url, ""),
+        };
+        let start = url.find("://").map_or(0, |at| at + 3);
+        let end = url[start..].find('/').map_or(url.len(), |at| start + at);
+        match url[start..end].rfind('@') {
+            Some(at) => format!("{}***{}{query}", &url[..start], &url[start + at..]),
+            None => format!("{url}{query}"),
+        }
+    }
+
+    // The executor to forward a root field to: when one is configured and no default resolver
+    // covers the field. `None` when the field resolves locally.
+    pub(crate) fn forwarding(ctx: &ResolverContext<'_>, field_ctx: &FieldContext) -> Option<Self> {
+        let remote = ctx.data_opt::<RemoteExecutor>()?;
+        let is_root = ctx.path_node.is_some_and(|node| node.parent.is_none());
+        let resolved = ctx
+            .data_opt::<DefaultResolver>()
+            .is_some_and(|resolvers| resolvers.lookup(&field_ctx.coordinate).is_some());
+        (is_root && !resolved).then(|| remote.clone())
+    }
+
+    // Upstream data for the fields of an object the upstream service returned. `None` when the
+    // field resolves as usual.
+    pub(crate) fn resolve(
+        ctx: &ResolverContext<'_>,
+        field_ctx: &FieldContext,
+    ) -> Option<FieldValue<'static>> {
+        let object = ctx.parent_value.downcast_ref::<RemoteObject>()?;
+        let key = ctx.item.node.response_key().node.as_str();
+        let value = object.0.get(key).cloned().unwrap_or(Value::Null);
+        Some(remote_value(
+            &ctx.schema_env.registry,
+            value,
+            &field_ctx.output_type,
+        ))
+    }
+
+    pub(crate) async fn forward(
+        &self,
+        ctx: &ResolverContext<'_>,
+        field_ctx: &FieldContext,
+    ) -> Result<FieldValue<'static>, Error> {
+        let body = serde_json::json!({ "query": forwarded_operation(ctx)? }).to_string();
+        let target = &self.0;
+        let url = self.redacted_url();
+        let sent = Python::attach(|py| {
+            let headers = PyDict::new(py);
+            for (name, value) in &target.headers {
+                headers.set_item(name, value)?;
+            }
+            let sent = target
+                .transport
+                .bind(py)
+                .call1((&target.url, body, headers))?;
+            Ok(awaitable_into_future(sent))
+        })
+        .map_err(py_err_to_error)?;
+        let response = sent.await.map_err(py_err_to_error)?;
+        let text = Python::attach(|py| {
+            let response = response.bind(py);
+            match response.cast::<PyBytes>() {
+                Ok(bytes) => Ok(String::from_utf8_lossy(bytes.as_bytes()).into_owned()),
+                Err(_) => response.extract::<String>(),
+            }
+        })
+        .map_err(py_err_to_error)?;
+
+        let payload: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|err| Error::new(format!("Invalid response from {url}: {err}")))?;
+        if let Some(errors) = payload.get("errors").and_then(|errors| errors.as_array())
+            && !errors.is_empty()
+        {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|error| error.get("message").and_then(|message| message.as_str()))
+                .collect();
+            return Err(Error::new(format!(
+                "Upstream service {url} failed: {}",
+                messages.join("; ")
+            )));
+        }
+        let key = ctx.item.node.response_key().node.as_str();
+        let data = payload
+            .get("data")
+            .and_then(|data| data.get(key))
+            .cloned()
+            .unwrap_or_default();
+        let value = Value::from_json(data)
+            .map_err(|err| Error::new(format!("Invalid response from {url}: {err}")))?;
+        Ok(remote_value(
+            &ctx.schema_env.registry,
+            value,
+            &field_ctx.output_type,
+        ))
+    }
+}
+
+// Upstream objects become parents their fields resolve from; for abstract types, the
+// `__typename` selected with every object picks the concrete type.
+fn remote_value(registry: &Registry, value: Value, type_ref: &TypeRef) -> FieldValue<'static> {
+    match (type_ref, value) {
+        (_, Value::Null) => FieldValue::NULL,
+        (TypeRef::NonNull(inner), value) => remote_value(registry, value, inner),
+        (TypeRef::List(inner), Value::List(items)) => FieldValue::list(
+            items
+                .into_iter()
+                .map(|item| remote_value(registry, item, inner)),
+        ),
+        (TypeRef::Named(name), Value::Object(map)) => match registry.types.get(name.as_ref()) {
+            Some(MetaType::Object { .. }) => FieldValue::owned_any(RemoteObject(map)),
+            Some(MetaType::Interface { .. } | MetaType::Union { .. }) => {
+                let type_name = match map.get("__typename") {
+                    Some(Value::String(type_name)) => Some(type_name.clone()),
+                    _ => None,
+                };
+                let object = FieldValue::owned_any(RemoteObject(map));
+                match type_name {
+                    Some(type_name) => object.with_type(type_name),
+                    None => object,
+                }
+            }
+            _ => FieldValue::value(Value::Object(map)),
+        },
+        (_, value) => FieldValue::value(value),
+    }
+}
+
+// The root field being resolved as an operation of its own: variables are inlined, skipped
+// selections dropped, and fragment spreads inlined, so the upstream service sees no more of
+// the request than the field.
+fn forwarded_operation(ctx: &ResolverContext<'_>) -> Result<String, Error> {
+    let printer = Printer { ctx };
+    let mut out = String::from(match ctx.query_env.operation.node.ty {
+        OperationType::Mutation => "mutation { ",
+        _ => "query { ",
+    });
+    printer.field(&ctx.item.node, &mut out)?;
+    out.push_str(" }");
+    Ok(out)
+}
+
+struct Printer<'a, 'ctx> {
+    ctx: &'a ResolverContext<'ctx>,
+}
+
+impl Printer<'_, '_> {
+    // A variable's value in the request, else its default; unset variables are null.
+    fn variable(&self, name: Name) -> Result<Value, Error> {
+        let env = self.ctx.query_env;
+        let definition = env
+            .operation
+            .node
+            .variable_definitions
+            .iter()
+            .find(|definition| definition.node.name.node == name);
+        Ok(definition
+            .and_then(|definition| {
+                env.variables
+                    .get(&name)
+                    .or_else(|| definition.node.default_value())
+            })
+            .cloned()
+            .unwrap_or(Value::Null))
+    }
+
+    // Whether `@skip` and `@include` keep a selection, decided as the local executor does: from
+    // the request's variables alone, so both sides select the same fields.
+    fn included(&self, directives: &[Positioned<Directive>]) -> bool {
+        directives.iter().all(|directive| {
+            let expected = match directive.node.name.node.as_str() {
+                "skip" => false,
+                "include" => true,
+                _ => return true,
+            };
+            let Some(condition) = directive.node.get_argument("if") else {
+                return true;
+            };
+            let condition = condition
+                .node
+                .clone()
+                .into_const_with(|name| self.ctx.query_env.variables.get(&name).cloned().ok_or(()))
+                .unwrap_or_default();
+            (condition == Value::Boolean(true)) == expected
+        })
+    }
+
+    fn field(&self, field: &Field, out: &mut String) -> Result<(), Error> {
+        if let Some(alias) = &field.alias {
+            let _ = write!(out, "{}: ", alias.node);
+        }
+        out.push_str(&field.name.node);
+        if !field.arguments.is_empty() {
+            out.push('(');
+            for (index, (name, value)) in field.arguments.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                let value = value
+                    .node
+                    .clone()
+                    .into_const_with(|name| self.variable(name))?;
+                let _ = write!(out, "{}: {value}", name.node);
+            }
+            out.push(')');
+        }
+        self.selection_set(&field.selection_set.node, out)
+    }
+
+    fn selection_set(&self, selection_set: &SelectionSet, out: &mut String) -> Result<(), Error> {
+        if selection_set.items.is_empty() {
+            return Ok(());
+        }
+        out.push_str(" { __typename");
+        self.selections(selection_set, out)?;
+        out.push_str(" }");
+        Ok(())
+    }
+
+    fn selections(&self, selection_set: &SelectionSet, out: &mut String) -> Result<(), Error> {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    if self.included(&field.node.directives) {
+                        out.push(' ');
+                        self.field(&field.node, out)?;
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self
+                        .ctx
+                        .query_env
+                        .fragments
+                        .get(&spread.node.fragment_name.node)
+                    else {
+                        continue;
+                    };
+                    if self.included(&spread.node.directives) {
+                        let condition = &fragment.node.type_condition.node.on.node;
+                        let _ = write!(out, " ... on {condition} {{");
+                        self.selections(&fragment.node.selection_set.node, out)?;
+                        out.push_str(" }");
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    if self.included(&fragment.node.directives) {
+                        out.push_str(" ...");
+                        if let Some(condition) = &fragment.node.type_condition {
+                            let _ = write!(out, " on {}", condition.node.on.node);
+                        }
+                        out.push_str(" {");
+                        self.selections(&fragment.node.selection_set.node, out)?;
+                        out.push_str(" }");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
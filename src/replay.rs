@@ -0,0 +1,168 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use pyo3::prelude::*;
+
+use crate::types::PyObj;
+
+// A subscription event tagged with a resume cursor. Subscription resolvers yield these instead
+// of bare values when clients should be able to resume after the event.
+#[pyclass(module = "grommet._core", name = "Event", frozen)]
+pub(crate) struct Event {
+    #[pyo3(get)]
+    value: Py<PyAny>,
+    #[pyo3(get)]
+    cursor: String,
+}
+
+#[pymethods]
+impl Event {
+    #[new]
+    fn new(value: Py<PyAny>, cursor: String) -> Self {
+        Event { value, cursor }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Event(cursor={:?})", self.cursor)
+    }
+}
+
+// The last value of a subscription. Resolvers yield it to deliver a final payload, after which
+// the stream closes their generator and ends.
+#[pyclass(module = "grommet._core", name = "Complete", frozen)]
+pub(crate) struct Complete {
+    #[pyo3(get)]
+    value: Py<PyAny>,
+}
+
+#[pymethods]
+impl Complete {
+    #[new]
+    fn new(value: Py<PyAny>) -> Self {
+        Complete { value }
+    }
+
+    fn __repr__(&self) -> String {
+        "Complete(...)".to_owned()
+    }
+}
+
+// Unwraps a yielded `Complete` value, reporting whether it ends the subscription.
+pub(crate) fn split_complete<'py>(value: &Bound<'py, PyAny>) -> (Bound<'py, PyAny>, bool) {
+    match value.cast::<Complete>() {
+        Ok(complete) => (complete.get().value.bind(value.py()).clone(), true),
+        Err(_) => (value.clone(), false),
+    }
+}
+
+// Split a yielded subscription value into its cursor, if any, and the value to convert.
+pub(crate) fn split_event(value: &Bound<'_, PyAny>) -> (Option<String>, PyObj) {
+    match value.cast::<Event>() {
+        Ok(event) => {
+            let event = event.get();
+            (
+                Some(event.cursor.clone()),
+                PyObj::new(event.value.clone_ref(value.py())),
+            )
+        }
+        Err(_) => (None, PyObj::new(value.clone().unbind())),
+    }
+}
+
+// Recent cursor-tagged events, kept per subscription field and arguments. Events delivered to
+// several subscribers are recorded once.
+#[derive(Default)]
+pub(crate) struct ReplayBuffer {
+    capacity: usize,
+    events: HashMap<String, VecDeque<(String, PyObj)>>,
+}
+
+impl ReplayBuffer {
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.events.retain(|_, events| {
+            while events.len() > capacity {
+                events.pop_front();
+            }
+            !events.is_empty()
+        });
+    }
+
+    fn record(&mut self, key: &str, cursor: &str, value: &PyObj) {
+        if self.capacity == 0 {
+            return;
+        }
+        let events = self.events.entry(key.to_string()).or_default();
+        if events.iter().any(|(recorded, _)| recorded == cursor) {
+            return;
+        }
+        events.push_back((cursor.to_string(), value.clone()));
+        while events.len() > self.capacity {
+            events.pop_front();
+        }
+    }
+
+    // Events recorded after `cursor`. When the cursor has already been evicted, every buffered
+    // event is returned rather than silently skipping the gap.
+    fn events_after(&self, key: &str, cursor: &str) -> Vec<(String, PyObj)> {
+        let Some(events) = self.events.get(key) else {
+            return Vec::new();
+        };
+        let start = events
+            .iter()
+            .position(|(recorded, _)| recorded == cursor)
+            .map_or(0, |index| index + 1);
+        events.iter().skip(start).cloned().collect()
+    }
+}
+
+// Request data for subscription operations: the schema's buffer, the cursor a client resumes
+// from, and the last cursor delivered on this stream.
+#[derive(Clone)]
+pub(crate) struct SubscriptionReplay {
+    pub(crate) buffer: Arc<Mutex<ReplayBuffer>>,
+    pub(crate) resume_from: Option<String>,
+    pub(crate) last_cursor: Arc<Mutex<Option<String>>>,
+}
+
+// Replay state bound to one subscription field and its arguments.
+pub(crate) struct FieldReplay {
+    replay: SubscriptionReplay,
+    key: String,
+}
+
+impl FieldReplay {
+    pub(crate) fn new(replay: SubscriptionReplay, key: String) -> Self {
+        FieldReplay { replay, key }
+    }
+
+    fn buffer(&self) -> std::sync::MutexGuard<'_, ReplayBuffer> {
+        self.replay
+            .buffer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn missed_events(&self) -> Vec<(String, PyObj)> {
+        match &self.replay.resume_from {
+            Some(cursor) => self.buffer().events_after(&self.key, cursor),
+            None => Vec::new(),
+        }
+    }
+
+    pub(crate) fn record(&self, cursor: &str, value: &PyObj) {
+        self.buffer().record(&self.key, cursor, value);
+    }
+
+    pub(crate) fn delivered(&self, cursor: &str) {
+        *self
+            .replay
+            .last_cursor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(cursor.to_string());
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::mem;
+use std::num::NonZeroUsize;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+
+use async_graphql::Request;
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict};
+
+use crate::errors::{invalid_stream_timeout, unknown_request_option};
+use crate::resolver::awaitable_into_future;
+use crate::values::{ValueSettings, py_to_value};
+
+// Per-subscription options: how long the stream may stay open in total, and wait for its next
+// event, before the core closes it, labels to find it by among the active subscriptions,
+// whether it delivers its events as `SubscriptionPayload`s, and how many values of an event's
+// data to convert per turn of the event loop, when its conversion should not block the loop.
+#[derive(Clone, Default)]
+pub(crate) struct StreamOptions {
+    max_lifetime: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    pub(crate) labels: Vec<String>,
+    pub(crate) envelope: bool,
+    pub(crate) chunk_size: Option<NonZeroUsize>,
+}
+
+impl StreamOptions {
+    // The time left to wait for the next event of a stream opened at `started`, with the reason
+    // to close it once that runs out.
+    pub(crate) fn remaining(&self, started: Instant) -> Option<(Duration, &'static str)> {
+        let lifetime = self.max_lifetime.map(|max_lifetime| {
+            (
+                max_lifetime.saturating_sub(started.elapsed()),
+                "max_lifetime",
+            )
+        });
+        let idle = self.idle_timeout.map(|idle| (idle, "idle_timeout"));
+        match (lifetime, idle) {
+            (Some(lifetime), Some(idle)) => Some(if idle.0 < lifetime.0 { idle } else { lifetime }),
+            (lifetime, idle) => lifetime.or(idle),
+        }
+    }
+}
+
+// A stream time limit given in seconds, which must be positive.
+fn stream_timeout(option: &str, seconds: Option<f64>) -> PyResult<Option<Duration>> {
+    seconds
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .ok()
+                .filter(|duration| !duration.is_zero())
+                .ok_or_else(|| invalid_stream_timeout(option, seconds))
+        })
+        .transpose()
+}
+
+// Request knobs passed to `execute` as an `options` dict. `disable_introspection` and
+// `introspection_only` set the request's introspection mode, `extensions` its extensions and
+// `operation_name` the operation of the document it runs; `parse_only` stops after parsing the
+// query, reporting only syntax errors. The others are described where `run` applies them.
+#[derive(Default)]
+pub(crate) struct RequestOptions {
+    disable_introspection: bool,
+    introspection_only: bool,
+    pub(crate) operation_name: Option<String>,
+    pub(crate) parse_only: bool,
+    // Whether response errors are `GraphQLError`s instead of dicts.
+    pub(crate) error_objects: bool,
+    extensions: HashMap<String, async_graphql::Value>,
+    pub(crate) visibility: Option<Py<PyAny>>,
+    // Whether the result is a lazily converted `ResponseHandle`.
+    pub(crate) raw: bool,
+    pub(crate) resume_from: Option<String>,
+    pub(crate) coercion_warnings: bool,
+    pub(crate) sort_keys: bool,
+    pub(crate) read_only: bool,
+    pub(crate) idempotency_key: Option<String>,
+    pub(crate) stream: StreamOptions,
+}
+
+impl RequestOptions {
+    pub(crate) fn from_py(options: Option<Py<PyDict>>, settings: ValueSettings) -> PyResult<Self> {
+        let mut parsed = Self::default();
+        let Some(options) = options else {
+            return Ok(parsed);
+        };
+        Python::attach(|py| {
+            let (mut max_lifetime_s, mut idle_timeout_s) = (None, None);
+            for (key, value) in options.bind(py) {
+                let key: String = key.extract()?;
+                match key.as_str() {
+                    "disable_introspection" => parsed.disable_introspection = value.extract()?,
+                    "introspection_only" => parsed.introspection_only = value.extract()?,
+                    "operation_name" => parsed.operation_name = value.extract()?,
+                    "parse_only" => parsed.parse_only = value.extract()?,
+                    "error_objects" => parsed.error_objects = value.extract()?,
+                    "extensions" => {
+                        let extensions: HashMap<String, Bound<'_, PyAny>> = value.extract()?;
+                        for (name, value) in extensions {
+                            parsed
+                                .extensions
+                                .insert(name, py_to_value(py, &value, settings)?);
+                        }
+                    }
+                    "visibility" => parsed.visibility = (!value.is_none()).then(|| value.unbind()),
+                    "raw" => parsed.raw = value.extract()?,
+                    "resume_from" => parsed.resume_from = value.extract()?,
+                    "coercion_warnings" => parsed.coercion_warnings = value.extract()?,
+                    "sort_keys" => parsed.sort_keys = value.extract()?,
+                    "read_only" => parsed.read_only = value.extract()?,
+                    "idempotency_key" => parsed.idempotency_key = value.extract()?,
+                    "max_lifetime_s" => max_lifetime_s = value.extract()?,
+                    "idle_timeout_s" => idle_timeout_s = value.extract()?,
+                    "labels" => {
+                        parsed.stream.labels =
+                            value.extract::<Option<Vec<String>>>()?.unwrap_or_default();
+                    }
+                    "envelope" => parsed.stream.envelope = value.extract()?,
+                    "chunk_size" => parsed.stream.chunk_size = value.extract()?,
+                    _ => return Err(unknown_request_option(&key)),
+                }
+            }
+            parsed.stream.max_lifetime = stream_timeout("max_lifetime_s", max_lifetime_s)?;
+            parsed.stream.idle_timeout = stream_timeout("idle_timeout_s", idle_timeout_s)?;
+            Ok(parsed)
+        })
+    }
+
+    // The options of one operation run by `execute_document`.
+    pub(crate) fn operation(name: String) -> Self {
+        RequestOptions {
+            operation_name: Some(name),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn apply(&self, mut request: Request) -> Request {
+        if self.disable_introspection {
+            request = request.disable_introspection();
+        }
+        if self.introspection_only {
+            request = request.only_introspection();
+        }
+        if let Some(name) = &self.operation_name {
+            request = request.operation_name(name.clone());
+        }
+        request.extensions.0.extend(self.extensions.clone());
+        request
+    }
+}
+
+// Variables as given to `execute`, or as the encoded JSON object given to `execute_raw`.
+pub(crate) enum VariablesSource {
+    Python(Option<Py<PyAny>>),
+    Json(Option<Vec<u8>>),
+}
+
+// The contexts a request closes once it is done when the schema manages contexts: the one it
+// was given and any `pre_execute` replaced it with. A subscription hands them to its stream,
+// which closes them when it ends.
+pub(crate) struct ManagedContexts(Option<std::sync::Mutex<Vec<Py<PyAny>>>>);
+
+impl ManagedContexts {
+    pub(crate) fn new(managed: bool, context: &Option<Py<PyAny>>) -> Self {
+        let contexts = ManagedContexts(managed.then(Default::default));
+        contexts.add(context);
+        contexts
+    }
+
+    pub(crate) fn add(&self, context: &Option<Py<PyAny>>) {
+        let (Some(contexts), Some(context)) = (&self.0, clone_context(context)) else {
+            return;
+        };
+        let mut contexts = contexts.lock().unwrap_or_else(PoisonError::into_inner);
+        if !contexts.iter().any(|held| held.is(&context)) {
+            contexts.push(context);
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<Py<PyAny>> {
+        self.0.as_ref().map_or_else(Vec::new, |contexts| {
+            mem::take(&mut *contexts.lock().unwrap_or_else(PoisonError::into_inner))
+        })
+    }
+
+    // Closes the contexts still held, then returns the request's result, or the first error
+    // closing them raised.
+    pub(crate) async fn finish<T>(&self, result: PyResult<T>) -> PyResult<T> {
+        let closed = close_contexts(self.take()).await;
+        let result = result?;
+        closed?;
+        Ok(result)
+    }
+}
+
+// Closes every context, each awaiting `__aexit__(None, None, None)` if it defines one, or
+// calling (and awaiting) `close`. The first error raised is returned once all were closed.
+pub(crate) async fn close_contexts(contexts: Vec<Py<PyAny>>) -> PyResult<()> {
+    let mut result = Ok(());
+    for context in contexts {
+        let closed = close_context(context).await;
+        result = result.and(closed);
+    }
+    result
+}
+
+async fn close_context(context: Py<PyAny>) -> PyResult<()> {
+    let future = Python::attach(|py| {
+        begin_close(context.bind(py)).map(|closed| closed.map(awaitable_into_future))
+    })?;
+    if let Some(future) = future {
+        future.await?;
+    }
+    Ok(())
+}
+
+// Calls `__aexit__(None, None, None)` or `close` on a context, returning what is left to await.
+fn begin_close<'py>(context: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = context.py();
+    let closed = if context.hasattr("__aexit__")? {
+        context.call_method1("__aexit__", (py.None(), py.None(), py.None()))?
+    } else if context.hasattr("close")? {
+        context.call_method0("close")?
+    } else {
+        return Ok(None);
+    };
+    Ok(closed.hasattr("__await__")?.then_some(closed))
+}
+
+// Closes contexts on `event_loop` from outside it, as when their stream is dropped, starting
+// each close that needs awaiting as a task of the loop. Errors are left to the loop to report.
+pub(crate) fn schedule_close(
+    py: Python<'_>,
+    contexts: Vec<Py<PyAny>>,
+    event_loop: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    if event_loop.call_method0("is_closed")?.is_truthy()? {
+        return Ok(());
+    }
+    let create_task = event_loop.getattr("create_task")?.unbind();
+    let close = PyCFunction::new_closure(
+        py,
+        Some(c"grommet_close_contexts"),
+        None,
+        move |args, _kwargs| -> PyResult<()> {
+            let create_task = create_task.bind(args.py());
+            let mut result = Ok(());
+            for context in &contexts {
+                let closed = begin_close(context.bind(args.py())).and_then(|closed| {
+                    closed.map_or(Ok(()), |closed| create_task.call1((closed,)).map(drop))
+                });
+                result = result.and(closed);
+            }
+            result
+        },
+    )?;
+    event_loop.call_method1("call_soon_threadsafe", (close,))?;
+    Ok(())
+}
+
+pub(crate) fn clone_context(context: &Option<Py<PyAny>>) -> Option<Py<PyAny>> {
+    Python::attach(|py| context.as_ref().map(|context| context.clone_ref(py)))
+}
@@ -1,23 +1,41 @@
+use std::collections::HashSet;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll, Waker};
 
-use async_graphql::Error;
 use async_graphql::dynamic::{FieldValue, ResolverContext, TypeRef};
-use async_graphql::futures_util::stream::{self, BoxStream, StreamExt};
-use pyo3::exceptions::PyStopAsyncIteration;
+use async_graphql::futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use async_graphql::{Error, PathSegment, Pos, QueryPathSegment, Value};
+use pyo3::exceptions::{
+    PyAttributeError, PyIndexError, PyKeyError, PyStopAsyncIteration, PyTypeError,
+};
 use pyo3::prelude::*;
-use pyo3::types::{PyAnyMethods, PyCFunction, PyDict, PyTupleMethods};
+use pyo3::types::{PyAnyMethods, PyCFunction, PyDict, PyList, PyTupleMethods};
 
-use crate::errors::{py_err_to_error, subscription_requires_async_iterator};
-use crate::types::{ContextValue, FieldContext, PyObj, ResolverEntry};
+use crate::batch::{BatchKey, BatchLoader};
+use crate::capture::{CallCapture, CapturedCall, summarize};
+use crate::errors::{
+    ResponseTooLarge, VALIDATION_ERROR, batch_size_mismatch, field_value_error, missing_source,
+    py_err_to_error, subscription_requires_async_iterator, with_code,
+};
+use crate::ids::{IdInputs, id_argument_to_py, id_input_to_py};
+use crate::info::build_info;
+use crate::mock::MockData;
+use crate::remote::RemoteExecutor;
+use crate::replay::{FieldReplay, SubscriptionReplay, split_complete, split_event};
+use crate::types::{
+    ContextValue, ConvertCtx, DefaultResolver, ErrorPolicy, FieldContext, PyObj, RequestStats,
+    ResponseBudget, ResultHook, SerialLocks, SourceKind, SourceLookup, Stat, SubscriptionErrors,
+};
 use crate::values::{py_to_field_value_for_type, value_to_py_bound};
 
-type BoxFut = Pin<Box<dyn Future<Output = PyResult<Py<PyAny>>> + Send>>;
+pub(crate) type BoxFut = Pin<Box<dyn Future<Output = PyResult<Py<PyAny>>> + Send>>;
 
 struct AwaitableState {
     started: bool,
+    // Whether the awaitable runs on the helper loop rather than the running loop.
+    helper: bool,
     task: Option<Py<PyAny>>,
     result: Option<PyResult<Py<PyAny>>>,
     waker: Option<Waker>,
@@ -34,6 +52,7 @@ impl PythonAwaitableFuture {
             awaitable,
             state: Arc::new(Mutex::new(AwaitableState {
                 started: false,
+                helper: false,
                 task: None,
                 result: None,
                 waker: None,
@@ -44,15 +63,26 @@ impl PythonAwaitableFuture {
     fn start(&self) -> PyResult<()> {
         let callback_state = Arc::clone(&self.state);
 
-        let task = Python::attach(|py| -> PyResult<Py<PyAny>> {
+        let (task, helper) = Python::attach(|py| -> PyResult<(Py<PyAny>, bool)> {
             let asyncio = py.import("asyncio")?;
-            let task = match asyncio.call_method1("create_task", (self.awaitable.bind(py),)) {
+            // Without a running loop on this thread there is nothing to schedule a task on, as
+            // when an operation's coroutine is driven by hand, so the awaitable runs on the
+            // core's helper loop instead.
+            let helper = asyncio.call_method0("get_running_loop").is_err();
+            let scheduled = if helper {
+                py.import("grommet._helper_loop")?
+                    .call_method1("submit", (self.awaitable.bind(py),))
+            } else {
+                asyncio.call_method1("create_task", (self.awaitable.bind(py),))
+            };
+            let task = match scheduled {
                 Ok(task) => task,
                 Err(err) => {
                     let _ = self.awaitable.bind(py).call_method0("close");
                     return Err(err);
                 }
             };
+            // Tasks and the helper loop's futures both report through a done callback.
             let callback = PyCFunction::new_closure(
                 py,
                 Some(c"grommet_awaitable_done"),
@@ -83,11 +113,14 @@ impl PythonAwaitableFuture {
                 },
             )?;
             task.call_method1("add_done_callback", (callback,))?;
-            Ok(task.unbind())
+            Ok((task.unbind(), helper))
         })?;
 
         let mut shared = self.state.lock().expect("awaitable state poisoned");
-        shared.task = Some(task);
+        shared.helper = helper;
+        if shared.result.is_none() {
+            shared.task = Some(task);
+        }
         Ok(())
     }
 }
@@ -111,14 +144,21 @@ impl Future for PythonAwaitableFuture {
         };
 
         if should_start && let Err(err) = self.start() {
-            let mut shared = self.state.lock().expect("awaitable state poisoned");
-            shared.result = Some(Err(err));
-            if let Some(waker) = shared.waker.take() {
-                waker.wake();
-            }
+            return Poll::Ready(Err(err));
         }
 
-        Poll::Pending
+        let mut shared = self.state.lock().expect("awaitable state poisoned");
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                // Without a running loop nothing would wake the operation when the helper loop
+                // finishes, so it yields at once and is polled again when next resumed.
+                if shared.helper {
+                    cx.waker().wake_by_ref();
+                }
+                Poll::Pending
+            }
+        }
     }
 }
 
@@ -136,22 +176,119 @@ impl Drop for PythonAwaitableFuture {
     }
 }
 
-fn awaitable_into_future(awaitable: Bound<'_, PyAny>) -> BoxFut {
+pub(crate) fn awaitable_into_future(awaitable: Bound<'_, PyAny>) -> BoxFut {
     Box::pin(PythonAwaitableFuture::new(awaitable.unbind()))
 }
 
+fn count(ctx: &ResolverContext<'_>, stat: Stat) {
+    if let Some(stats) = ctx.data_opt::<RequestStats>() {
+        stats.count(stat);
+    }
+}
+
+// `Python::attach` for field resolution, counted in the request's stats.
+fn attach<R>(ctx: &ResolverContext<'_>, f: impl for<'py> FnOnce(Python<'py>) -> R) -> R {
+    count(ctx, Stat::GilAcquisitions);
+    Python::attach(f)
+}
+
+// A field counts its key against the response size limit before it resolves, so nothing more
+// resolves once the response is over the limit.
+fn charge_response_key(ctx: &ResolverContext<'_>) -> Result<(), Error> {
+    match ctx.data_opt::<ResponseBudget>() {
+        Some(budget) => budget
+            .charge(ctx.item.node.response_key().node.len() + 3)
+            .map_err(py_err_to_error),
+        None => Ok(()),
+    }
+}
+
+// Declared argument constraints are enforced before the resolver sees any value.
+fn validate_arguments(ctx: &ResolverContext<'_>, field_ctx: &FieldContext) -> Result<(), Error> {
+    match &field_ctx.argument_checks {
+        Some(checks) => checks
+            .validate(&ctx.args, &ConvertCtx::from_request(ctx))
+            .map_err(|err| with_code(err, VALIDATION_ERROR)),
+        None => Ok(()),
+    }
+}
+
+// Give the configured result hook a chance to replace a resolver's value before conversion.
+fn apply_result_hook<'py>(
+    ctx: &ResolverContext<'_>,
+    field_ctx: &FieldContext,
+    value: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    match ctx.data_opt::<ResultHook>() {
+        Some(hook) => hook
+            .0
+            .bind(value.py())
+            .call1((field_ctx.output_type.type_name(), value)),
+        None => Ok(value),
+    }
+}
+
+// Path of the field being resolved, e.g. `users.0.name`.
+pub(crate) fn field_path(ctx: &ResolverContext<'_>) -> String {
+    ctx.path_node
+        .map_or_else(|| ctx.field().name().to_string(), |node| node.to_string())
+}
+
+// Conversion failures name the field path and its declared type, so they point at the resolver
+// that produced the value. Lossy conversions are recorded when the request collects warnings.
+pub(crate) fn convert_field_value(
+    py: Python<'_>,
+    path: &str,
+    convert: &ConvertCtx<'_>,
+    value: &Bound<'_, PyAny>,
+    output_type: &TypeRef,
+) -> PyResult<FieldValue<'static>> {
+    let mut lossy = Vec::new();
+    let field_value = py_to_field_value_for_type(py, value, output_type, convert, &mut lossy)
+        .map_err(|err| {
+            if err.is_instance_of::<ResponseTooLarge>(py) {
+                err
+            } else {
+                field_value_error(py, path, output_type, &err)
+            }
+        })?;
+    if let Some(warnings) = convert.warnings
+        && !lossy.is_empty()
+    {
+        warnings.record(path, lossy);
+    }
+    Ok(field_value)
+}
+
 // Synchronous fast-path for all sync fields (data fields via attrgetter and sync resolvers).
 // Single GIL block: call func + convert. No async overhead, no task scheduling.
 pub(crate) fn resolve_field_sync_fast<'a>(
     ctx: &ResolverContext<'a>,
     field_ctx: &FieldContext,
 ) -> Result<FieldValue<'a>, Error> {
-    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
-    Python::attach(|py| {
-        let result = call_resolver_sync(py, ctx, entry)?;
-        py_to_field_value_for_type(py, result.bind(py), &field_ctx.output_type)
+    charge_response_key(ctx)?;
+    validate_arguments(ctx, field_ctx)?;
+    if let Some(value) = MockData::resolve(ctx, field_ctx) {
+        return Ok(value);
+    }
+    if let Some(value) = RemoteExecutor::resolve(ctx, field_ctx) {
+        return Ok(value);
+    }
+    attach(ctx, |py| {
+        let result = call_resolver_sync(py, ctx, field_ctx);
+        capture_call(ctx, field_ctx, result.as_ref().map_err(ToString::to_string))?;
+        let result = apply_result_hook(ctx, field_ctx, result?.into_bound(py))?;
+        convert_field_value(
+            py,
+            &field_path(ctx),
+            &ConvertCtx::from_request(ctx),
+            &result,
+            &field_ctx.output_type,
+        )
     })
     .map_err(py_err_to_error)
+    .or_else(|err| MockData::stub(ctx, field_ctx, err))
+    .map_err(|err| record_subscription_error(ctx, err))
 }
 
 // Async field resolution for fields with resolvers.
@@ -159,23 +296,102 @@ pub(crate) async fn resolve_field(
     ctx: ResolverContext<'_>,
     field_ctx: Arc<FieldContext>,
 ) -> Result<Option<FieldValue<'_>>, Error> {
-    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
-    let value = resolve_with_resolver(&ctx, entry).await?;
-    let field_value =
-        Python::attach(|py| py_to_field_value_for_type(py, value.bind(py), &field_ctx.output_type))
-            .map_err(py_err_to_error)?;
-    Ok(Some(field_value))
+    let result = resolve_field_value(&ctx, &field_ctx).await;
+    result
+        .map(Some)
+        .map_err(|err| record_subscription_error(&ctx, err))
+}
+
+async fn resolve_field_value(
+    ctx: &ResolverContext<'_>,
+    field_ctx: &FieldContext,
+) -> Result<FieldValue<'static>, Error> {
+    charge_response_key(ctx)?;
+    validate_arguments(ctx, field_ctx)?;
+    if let Some(value) = MockData::resolve(ctx, field_ctx) {
+        return Ok(value);
+    }
+    if let Some(value) = RemoteExecutor::resolve(ctx, field_ctx) {
+        return Ok(value);
+    }
+    let value = match resolve_with_resolver(ctx, field_ctx).await {
+        Ok(value) => value,
+        Err(err) => return MockData::stub(ctx, field_ctx, err),
+    };
+    attach(ctx, |py| {
+        let value = apply_result_hook(ctx, field_ctx, value.into_bound(py))?;
+        convert_field_value(
+            py,
+            &field_path(ctx),
+            &ConvertCtx::from_request(ctx),
+            &value,
+            &field_ctx.output_type,
+        )
+    })
+    .map_err(py_err_to_error)
+}
+
+// Within subscription events, keeps a copy of a field error that carries its path and location.
+fn record_subscription_error(ctx: &ResolverContext<'_>, err: Error) -> Error {
+    if let Some(errors) = ctx.data_opt::<SubscriptionErrors>() {
+        errors.record(ctx.set_error_path(err.clone().into_server_error(ctx.item.pos)));
+    }
+    err
 }
 
 pub(crate) async fn resolve_subscription_stream<'a>(
     ctx: ResolverContext<'a>,
     field_ctx: Arc<FieldContext>,
+    policy: ErrorPolicy,
 ) -> Result<BoxStream<'a, Result<FieldValue<'a>, Error>>, Error> {
-    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
-    let value = resolve_with_resolver(&ctx, entry).await?;
+    validate_arguments(&ctx, &field_ctx)?;
+    if let Some(errors) = ctx.data_opt::<SubscriptionErrors>() {
+        errors.attach(ctx.query_env);
+    }
+    let replay = ctx.data_opt::<SubscriptionReplay>().map(|replay| {
+        let args = Value::Object(ctx.args.as_index_map().clone());
+        FieldReplay::new(replay.clone(), format!("{}{args}", ctx.field().name()))
+    });
+    let value = resolve_with_resolver(&ctx, &field_ctx).await?;
     let iterator =
-        Python::attach(|py| subscription_iterator(value.bind(py))).map_err(py_err_to_error)?;
-    subscription_stream(iterator, field_ctx.output_type.clone())
+        attach(&ctx, |py| subscription_iterator(value.bind(py))).map_err(py_err_to_error)?;
+    subscription_stream(
+        iterator,
+        field_path(&ctx),
+        ConvertCtx::from_request(&ctx),
+        field_ctx.output_type.clone(),
+        replay,
+        EventFailure {
+            policy,
+            errors: ctx.data_opt::<SubscriptionErrors>().cloned(),
+            pos: ctx.item.pos,
+            response_key: ctx.item.node.response_key().node.to_string(),
+        },
+    )
+}
+
+// Handles a subscription event whose value could not be produced or converted, following the
+// field's `on_error` policy.
+struct EventFailure {
+    policy: ErrorPolicy,
+    errors: Option<SubscriptionErrors>,
+    pos: Pos,
+    response_key: String,
+}
+
+impl EventFailure {
+    fn handle<'a>(&self, err: Error) -> Option<Result<FieldValue<'a>, Error>> {
+        match (self.policy, &self.errors) {
+            (ErrorPolicy::Skip, _) => None,
+            (ErrorPolicy::Emit, Some(errors)) => {
+                let mut error = err.into_server_error(self.pos);
+                error.path = vec![PathSegment::Field(self.response_key.clone())];
+                errors.emit(error);
+                Some(Ok(FieldValue::NULL))
+            }
+            _ => Some(Err(err)),
+        }
+    }
 }
 
 fn subscription_iterator(value_ref: &Bound<'_, PyAny>) -> PyResult<PyObj> {
@@ -189,50 +405,188 @@ fn subscription_iterator(value_ref: &Bound<'_, PyAny>) -> PyResult<PyObj> {
     }
 }
 
+// Events missed since the client's resume cursor are delivered first; live events that were
+// already replayed are skipped. A yielded `Complete` value is delivered as the last event.
+// Errors raised by the iterator end the stream unless the policy skips or emits them.
 fn subscription_stream<'a>(
     iterator: PyObj,
+    path: String,
+    convert: ConvertCtx<'a>,
     output_type: TypeRef,
+    replay: Option<FieldReplay>,
+    failure: EventFailure,
 ) -> Result<BoxStream<'a, Result<FieldValue<'a>, Error>>, Error> {
-    let stream = stream::try_unfold(iterator, move |iterator| {
-        let output_type = output_type.clone();
-        async move {
-            let next_fut: BoxFut = Python::attach(|py| {
-                let anext = iterator.bind(py).call_method0("__anext__")?;
+    let policy = failure.policy;
+    let iterator = OpenIterator::new(iterator);
+    let live = stream::unfold(Some((iterator, false)), move |state| async move {
+        let (iterator, complete) = state?;
+        if complete {
+            return match close_iterator(&iterator.finish()).await {
+                Ok(()) => None,
+                Err(err) => Some((Err(py_err_to_error(err)), None)),
+            };
+        }
+        loop {
+            let next = match Python::attach(|py| -> PyResult<BoxFut> {
+                let anext = iterator.get().bind(py).call_method0("__anext__")?;
                 Ok(awaitable_into_future(anext))
-            })
-            .map_err(py_err_to_error)?;
-
-            match next_fut.await {
+            }) {
+                Ok(next_fut) => next_fut.await,
+                Err(err) => Err(err),
+            };
+            let err = match next {
                 Ok(value) => {
-                    let value = Python::attach(|py| {
-                        py_to_field_value_for_type(py, value.bind(py), &output_type)
-                    })
-                    .map_err(py_err_to_error)?;
-                    let value: FieldValue<'a> = value;
-                    Ok(Some((value, iterator)))
+                    let (event, complete) = Python::attach(|py| {
+                        let (value, complete) = split_complete(value.bind(py));
+                        (split_event(&value), complete)
+                    });
+                    return Some((Ok((event, false)), Some((iterator, complete))));
                 }
-                Err(err) => {
-                    let is_stop =
-                        Python::attach(|py| err.is_instance_of::<PyStopAsyncIteration>(py));
-                    if is_stop {
-                        Ok(None)
-                    } else {
-                        Err(py_err_to_error(err))
-                    }
+                Err(err) => err,
+            };
+            if Python::attach(|py| err.is_instance_of::<PyStopAsyncIteration>(py)) {
+                iterator.finish();
+                return None;
+            }
+            match policy {
+                ErrorPolicy::Skip => continue,
+                ErrorPolicy::Emit => {
+                    return Some((Err(py_err_to_error(err)), Some((iterator, false))));
+                }
+                ErrorPolicy::Stop => {
+                    iterator.finish();
+                    return Some((Err(py_err_to_error(err)), None));
                 }
             }
         }
     });
 
+    let missed = replay
+        .as_ref()
+        .map(FieldReplay::missed_events)
+        .unwrap_or_default();
+    let replayed: HashSet<String> = missed.iter().map(|(cursor, _)| cursor.clone()).collect();
+    let missed = stream::iter(
+        missed
+            .into_iter()
+            .map(|(cursor, value)| Ok(((Some(cursor), value), true))),
+    );
+
+    let stream = missed
+        .chain(live)
+        .try_filter(move |((cursor, _), is_replay)| {
+            let skip = !is_replay && cursor.as_ref().is_some_and(|c| replayed.contains(c));
+            std::future::ready(!skip)
+        })
+        .filter_map(move |event| {
+            let field_value = event.and_then(|((cursor, value), is_replay)| {
+                if let (Some(replay), Some(cursor)) = (&replay, &cursor) {
+                    if !is_replay {
+                        replay.record(cursor, &value);
+                    }
+                    replay.delivered(cursor);
+                }
+                Python::attach(|py| {
+                    convert_field_value(py, &path, &convert, &value.bind(py), &output_type)
+                })
+                .map_err(py_err_to_error)
+            });
+            std::future::ready(match field_value {
+                Ok(field_value) => Some(Ok(field_value)),
+                Err(err) => failure.handle(err),
+            })
+        });
+
     Ok(stream.boxed())
 }
 
+// The event loop running the current task, if any.
+pub(crate) fn running_loop() -> Option<PyObj> {
+    Python::attach(|py| {
+        py.import("asyncio")
+            .and_then(|asyncio| asyncio.call_method0("get_running_loop"))
+            .ok()
+            .map(|event_loop| PyObj::new(event_loop.unbind()))
+    })
+}
+
+// A subscription iterator that has not finished. Dropping it unfinished, as when its stream is
+// dropped before the iterator ran out, schedules `aclose()` on the event loop the iterator
+// started on, so its cleanup still runs.
+struct OpenIterator {
+    iterator: Option<PyObj>,
+    event_loop: Option<PyObj>,
+}
+
+impl OpenIterator {
+    fn new(iterator: PyObj) -> Self {
+        OpenIterator {
+            iterator: Some(iterator),
+            event_loop: running_loop(),
+        }
+    }
+
+    fn get(&self) -> &PyObj {
+        self.iterator.as_ref().expect("an open iterator")
+    }
+
+    // The iterator, which needs no cleanup once it ran out or is closed by the caller.
+    fn finish(mut self) -> PyObj {
+        self.iterator.take().expect("an open iterator")
+    }
+}
+
+impl Drop for OpenIterator {
+    fn drop(&mut self) {
+        let (Some(iterator), Some(event_loop)) = (self.iterator.take(), self.event_loop.take())
+        else {
+            return;
+        };
+        Python::try_attach(|py| {
+            let iterator = iterator.bind(py);
+            let event_loop = event_loop.bind(py);
+            let schedule = || -> PyResult<()> {
+                if !iterator.hasattr("aclose")?
+                    || event_loop.call_method0("is_closed")?.is_truthy()?
+                {
+                    return Ok(());
+                }
+                let create_task = event_loop.getattr("create_task")?;
+                let close = iterator.call_method0("aclose")?;
+                event_loop.call_method1("call_soon_threadsafe", (create_task, close))?;
+                Ok(())
+            };
+            if let Err(err) = schedule() {
+                err.write_unraisable(py, Some(&iterator));
+            }
+        });
+    }
+}
+
+// Lets the resolver's generator run its cleanup once it yielded a `Complete` value.
+async fn close_iterator(iterator: &PyObj) -> PyResult<()> {
+    let close = Python::attach(|py| -> PyResult<Option<BoxFut>> {
+        let iterator = iterator.bind(py);
+        if !iterator.hasattr("aclose")? {
+            return Ok(None);
+        }
+        Ok(Some(awaitable_into_future(
+            iterator.call_method0("aclose")?,
+        )))
+    })?;
+    if let Some(close) = close {
+        close.await?;
+    }
+    Ok(())
+}
+
 // Resolve a field that has an async resolver entry.
 // Merges call_resolver + into_future into a single GIL block for async resolvers.
 async fn resolve_with_resolver(
     ctx: &ResolverContext<'_>,
-    entry: &ResolverEntry,
+    field_ctx: &FieldContext,
 ) -> Result<Py<PyAny>, Error> {
+    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
     // Lazy state extraction: only look up state when the resolver needs context
     let context = if entry.needs_context {
         ctx.data::<ContextValue>().ok().map(|s| s.0.clone())
@@ -241,53 +595,267 @@ async fn resolve_with_resolver(
     };
     let parent = ctx.parent_value.try_downcast_ref::<PyObj>().ok().cloned();
 
-    if entry.is_async_gen {
+    let value = if entry.batched {
+        resolve_batched(ctx, field_ctx, parent, context).await
+    } else if entry.is_async_gen {
         // Async generators (subscriptions): call resolver, return generator directly
-        Python::attach(|py| call_resolver(py, ctx, entry, parent.as_ref(), context.as_ref()))
-            .map_err(py_err_to_error)
-    } else {
-        // Async coroutine: call resolver + set up future in one GIL block
-        let future: BoxFut = Python::attach(|py| {
-            let coroutine = call_resolver(py, ctx, entry, parent.as_ref(), context.as_ref())?;
-            let bound = coroutine.into_bound(py);
-            Ok(awaitable_into_future(bound))
+        attach(ctx, |py| {
+            call_resolver(py, ctx, field_ctx, parent.as_ref(), context.as_ref())
         })
-        .map_err(py_err_to_error)?;
-        future.await.map_err(py_err_to_error)
+        .map_err(py_err_to_error)
+    } else {
+        let serial = ctx
+            .data_opt::<SerialLocks>()
+            .filter(|_| entry.serial)
+            .map(|locks| locks.get(&field_ctx.coordinate));
+        let _turn = match &serial {
+            Some(serial) => Some(serial.lock().await),
+            None => None,
+        };
+        let mut attempt = 1;
+        loop {
+            // Async coroutine: call resolver + set up future in one GIL block
+            let future: BoxFut = attach(ctx, |py| {
+                let coroutine =
+                    call_resolver(py, ctx, field_ctx, parent.as_ref(), context.as_ref())?;
+                let bound = coroutine.into_bound(py);
+                Ok(awaitable_into_future(bound))
+            })
+            .map_err(py_err_to_error)?;
+            count(ctx, Stat::Awaits);
+            let err = match future.await {
+                Ok(value) => break Ok(value),
+                Err(err) => err,
+            };
+            let Some(retry) = &entry.retry else {
+                break Err(py_err_to_error(err));
+            };
+            let backoff = attach(ctx, |py| {
+                if !retry.retries(py, &err, attempt)? {
+                    return Ok(None);
+                }
+                let sleep = py
+                    .import("asyncio")?
+                    .call_method1("sleep", (retry.backoff_seconds(attempt),))?;
+                Ok(Some(awaitable_into_future(sleep)))
+            })
+            .map_err(py_err_to_error)?;
+            match backoff {
+                Some(backoff) => backoff.await.map_err(py_err_to_error)?,
+                None => break Err(retried_error(err, attempt)),
+            };
+            attempt += 1;
+        }
+    };
+    capture_call(
+        ctx,
+        field_ctx,
+        value.as_ref().map_err(|err| err.message.clone()),
+    )
+    .map_err(py_err_to_error)?;
+    value
+}
+
+// The error of a field with a retry policy, carrying how many times its resolver was called.
+fn retried_error(err: PyErr, attempts: u32) -> Error {
+    let mut error = py_err_to_error(err);
+    error
+        .extensions
+        .get_or_insert_with(Default::default)
+        .set("attempts", attempts);
+    error
+}
+
+// Records a resolver invocation when the request captures them. Data fields read their parent
+// rather than calling a resolver, so they are left out.
+fn capture_call(
+    ctx: &ResolverContext<'_>,
+    field_ctx: &FieldContext,
+    result: Result<&Py<PyAny>, String>,
+) -> PyResult<()> {
+    let Some(capture) = ctx.data_opt::<CallCapture>() else {
+        return Ok(());
+    };
+    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
+    if entry.source_lookup {
+        return Ok(());
     }
+    Python::attach(|py| {
+        let parent = match ctx.parent_value.try_downcast_ref::<PyObj>() {
+            Ok(parent) => summarize(&parent.bind(py))?,
+            Err(_) => summarize(py.None().bind(py))?,
+        };
+        let result = match result {
+            Ok(value) => Ok(summarize(value.bind(py))?),
+            Err(message) => Err(message),
+        };
+        capture.record(CapturedCall {
+            key: field_ctx.coordinate.clone(),
+            path: field_path(ctx),
+            kwargs: build_kwargs(py, ctx, field_ctx)?.unbind(),
+            parent,
+            result,
+        });
+        Ok(())
+    })
+}
+
+// Batched fields are joined by every sibling parent at the same depth with the same arguments.
+fn batch_key(ctx: &ResolverContext<'_>, field_ctx: &FieldContext) -> BatchKey {
+    let depth = ctx.path_node.map_or(0, |node| {
+        node.parents()
+            .filter(|parent| matches!(parent.segment, QueryPathSegment::Name(_)))
+            .count()
+    });
+    let args = Value::Object(ctx.args.as_index_map().clone()).to_string();
+    (std::ptr::from_ref(field_ctx).addr(), depth, args)
+}
+
+// Batch resolvers are called once per batch as `func(parents, context, kwargs)` and return one
+// value per parent, in the order of the parents.
+async fn resolve_batched(
+    ctx: &ResolverContext<'_>,
+    field_ctx: &FieldContext,
+    parent: Option<PyObj>,
+    context: Option<PyObj>,
+) -> Result<Py<PyAny>, Error> {
+    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
+    let loader = ctx.data::<BatchLoader>()?;
+    let stats = ctx.data_opt::<RequestStats>().cloned();
+    let count_batch = move |stat| {
+        if let Some(stats) = &stats {
+            stats.count(stat);
+        }
+    };
+    let member = attach(ctx, |py| {
+        let parent = parent.unwrap_or_else(|| PyObj::new(py.None()));
+        let kwargs = build_kwargs(py, ctx, field_ctx)?.unbind();
+        let func = entry.func.clone();
+        loader.join(py, batch_key(ctx, field_ctx), parent, move |parents| {
+            Box::pin(async move {
+                let parents = parents.collect().await;
+                count_batch(Stat::ResolverCalls);
+                count_batch(Stat::GilAcquisitions);
+                let future: BoxFut = Python::attach(|py| {
+                    let parents = PyList::new(py, parents.iter().map(|parent| parent.bind(py)))?;
+                    let context =
+                        context.map_or_else(|| py.None(), |context| context.clone_ref(py));
+                    let coroutine = func.bind(py).call1((parents, context, kwargs))?;
+                    Ok(awaitable_into_future(coroutine))
+                })
+                .map_err(py_err_to_error)?;
+                count_batch(Stat::Awaits);
+                let values = future.await.map_err(py_err_to_error)?;
+                count_batch(Stat::GilAcquisitions);
+                Python::attach(|py| {
+                    let values: Vec<Py<PyAny>> = values.extract(py)?;
+                    if values.len() != parents.len() {
+                        return Err(batch_size_mismatch(parents.len(), values.len()));
+                    }
+                    Ok(values.into_iter().map(PyObj::new).collect())
+                })
+                .map_err(py_err_to_error)
+            })
+        })
+    })
+    .map_err(py_err_to_error)?;
+    let value = member.value().await?;
+    Ok(attach(ctx, |py| value.clone_ref(py)))
 }
 
 // Synchronous resolver call for the sync fast-path. Single GIL block, vectorcall-optimized.
 fn call_resolver_sync(
     py: Python<'_>,
     ctx: &ResolverContext<'_>,
-    entry: &ResolverEntry,
+    field_ctx: &FieldContext,
 ) -> PyResult<Py<PyAny>> {
+    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
     let parent = ctx.parent_value.try_downcast_ref::<PyObj>().ok().cloned();
     let context = if entry.needs_context {
         ctx.data::<ContextValue>().ok().map(|s| s.0.clone())
     } else {
         None
     };
-    call_resolver(py, ctx, entry, parent.as_ref(), context.as_ref())
+    call_resolver(py, ctx, field_ctx, parent.as_ref(), context.as_ref())
 }
 
-fn build_kwargs<'py>(py: Python<'py>, ctx: &ResolverContext<'_>) -> PyResult<Bound<'py, PyDict>> {
+// Paginated page sizes above their maximum reach the resolver clamped to it, and `ID`
+// arguments as strings or, with typed ids, `GlobalID`s.
+fn build_kwargs<'py>(
+    py: Python<'py>,
+    ctx: &ResolverContext<'_>,
+    field_ctx: &FieldContext,
+) -> PyResult<Bound<'py, PyDict>> {
+    let checks = field_ctx.argument_checks.as_deref();
+    let id_arguments = field_ctx
+        .resolver
+        .as_ref()
+        .map_or(&[][..], |entry| entry.id_arguments.as_slice());
+    let convert = ConvertCtx::from_request(ctx);
+    let registry = &ctx.schema_env.registry;
+    let id_inputs = ctx.data_opt::<IdInputs>();
     let kwargs = PyDict::new(py);
     for (name, value) in ctx.args.iter() {
-        let py_value = value_to_py_bound(py, value.as_value())?;
+        let value = value.as_value();
+        if id_arguments.iter().any(|id| id == name.as_str()) {
+            let py_value = id_argument_to_py(py, value, &convert)?;
+            kwargs.set_item(name.as_str(), py_value)?;
+            continue;
+        }
+        let clamped = checks.and_then(|checks| checks.clamp(name.as_str(), value));
+        let value = clamped.as_ref().unwrap_or(value);
+        let id_input = id_inputs.and_then(|inputs| {
+            argument_type(ctx, field_ctx, name.as_str()).filter(|ty| inputs.holds_ids(ty))
+        });
+        let py_value = match id_input {
+            Some(ty) => id_input_to_py(py, registry, ty, value, &convert)?,
+            None => value_to_py_bound(py, value, convert.values)?,
+        };
         kwargs.set_item(name.as_str(), py_value)?;
     }
     Ok(kwargs)
 }
 
+// The declared type of an argument of the resolving field, e.g. `[UserInput!]!`.
+fn argument_type<'a>(
+    ctx: &ResolverContext<'a>,
+    field_ctx: &FieldContext,
+    name: &str,
+) -> Option<&'a str> {
+    let (type_name, field_name) = field_ctx.coordinate.split_once('.')?;
+    let field = ctx
+        .schema_env
+        .registry
+        .types
+        .get(type_name)?
+        .field_by_name(field_name)?;
+    Some(field.args.get(name)?.ty.as_str())
+}
+
 fn call_resolver(
     py: Python<'_>,
     ctx: &ResolverContext<'_>,
-    entry: &ResolverEntry,
+    field_ctx: &FieldContext,
     parent: Option<&PyObj>,
     context: Option<&PyObj>,
 ) -> PyResult<Py<PyAny>> {
+    let entry = field_ctx.resolver.as_ref().expect("resolver missing");
+    if entry.source_lookup
+        && let Some(resolver) = ctx
+            .data_opt::<DefaultResolver>()
+            .and_then(|resolvers| resolvers.lookup(&field_ctx.coordinate))
+    {
+        count(ctx, Stat::SourceLookups);
+        let info = build_info(py, ctx)?;
+        let parent = parent.map_or_else(|| py.None(), |parent| parent.clone_ref(py));
+        return Ok(resolver.bind(py).call1((parent, info))?.unbind());
+    }
+    if entry.source_lookup
+        && let (Some(lookup), Some(parent)) = (ctx.data_opt::<SourceLookup>(), parent)
+    {
+        count(ctx, Stat::SourceLookups);
+        return lookup_source(lookup, &parent.bind(py), ctx.field().name());
+    }
     let parent_obj: Py<PyAny> = match parent {
         Some(p) => p.clone_ref(py),
         None => py.None(),
@@ -300,7 +868,55 @@ fn call_resolver(
     } else {
         py.None()
     };
-    let kwargs = build_kwargs(py, ctx)?;
+    let kwargs = build_kwargs(py, ctx, field_ctx)?;
     let func = entry.func.bind(py);
+    count(
+        ctx,
+        if entry.source_lookup {
+            Stat::SourceLookups
+        } else {
+            Stat::ResolverCalls
+        },
+    );
+    if entry.needs_info {
+        let info = build_info(py, ctx)?;
+        return Ok(func
+            .call1((parent_obj, context_obj, kwargs, info))?
+            .unbind());
+    }
     Ok(func.call1((parent_obj, context_obj, kwargs))?.unbind())
 }
+
+fn lookup_source(
+    lookup: &SourceLookup,
+    parent: &Bound<'_, PyAny>,
+    name: &str,
+) -> PyResult<Py<PyAny>> {
+    let py = parent.py();
+    for kind in lookup.order.iter() {
+        let found = match kind {
+            SourceKind::Attr => parent
+                .getattr(name)
+                .map_err(|err| (!err.is_instance_of::<PyAttributeError>(py)).then_some(err)),
+            SourceKind::Item if parent.hasattr("__getitem__")? => {
+                parent.get_item(name).map_err(|err| {
+                    let missing = err.is_instance_of::<PyKeyError>(py)
+                        || err.is_instance_of::<PyIndexError>(py)
+                        || err.is_instance_of::<PyTypeError>(py);
+                    (!missing).then_some(err)
+                })
+            }
+            SourceKind::Item => continue,
+        };
+        match found {
+            Ok(value) => return Ok(value.unbind()),
+            Err(Some(err)) => return Err(err),
+            Err(None) => {}
+        }
+    }
+    if lookup.strict {
+        Err(missing_source(name, parent))
+    } else {
+        Ok(py.None())
+    }
+}
@@ -0,0 +1,99 @@
+use async_graphql::Response;
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::PyBytes;
+
+use crate::errors::{assign_error_codes, py_value_error, status_hint};
+use crate::msgpack;
+use crate::values::{ResultFormat, errors_to_py, extensions_to_py, payload_to_py, response_to_py};
+
+// Holds the engine response as-is. Each accessor converts only the part it returns, once, so
+// transports that serialize directly never pay for building Python objects.
+#[pyclass(module = "grommet._core", name = "ResponseHandle", frozen)]
+pub(crate) struct ResponseHandle {
+    response: Response,
+    data: PyOnceLock<Py<PyAny>>,
+    errors: PyOnceLock<Py<PyAny>>,
+    extensions: PyOnceLock<Py<PyAny>>,
+    format: ResultFormat,
+}
+
+impl ResponseHandle {
+    fn new(py: Python<'_>, mut response: Response, format: ResultFormat) -> Self {
+        assign_error_codes(py, &mut response.errors);
+        ResponseHandle {
+            response,
+            format,
+            data: PyOnceLock::new(),
+            errors: PyOnceLock::new(),
+            extensions: PyOnceLock::new(),
+        }
+    }
+
+    pub(crate) fn response(&self) -> &Response {
+        &self.response
+    }
+}
+
+#[pymethods]
+impl ResponseHandle {
+    fn data(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.data
+            .get_or_try_init(py, || {
+                Ok(payload_to_py(py, &self.response.data, self.format.values)?.unbind())
+            })
+            .map(|data| data.clone_ref(py))
+    }
+
+    fn errors(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.errors
+            .get_or_try_init(py, || errors_to_py(py, &self.response.errors, self.format))
+            .map(|errors| errors.clone_ref(py))
+    }
+
+    fn extensions(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.extensions
+            .get_or_try_init(py, || {
+                extensions_to_py(py, &self.response.extensions, self.format.values)
+            })
+            .map(|extensions| extensions.clone_ref(py))
+    }
+
+    // The HTTP status to answer the response with: 200, 400 when the request failed before
+    // executing, or 500 when an engine invariant failed.
+    fn status_hint(&self) -> u16 {
+        status_hint(&self.response)
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.response).map_err(|err| py_value_error(err.to_string()))
+    }
+
+    fn to_msgpack<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let value =
+            serde_json::to_value(&self.response).map_err(|err| py_value_error(err.to_string()))?;
+        Ok(PyBytes::new(py, &msgpack::encode(&value)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ResponseHandle(ok={}, errors={})",
+            self.response.is_ok(),
+            self.response.errors.len()
+        )
+    }
+}
+
+// Convert a finished response into either the eager `OperationResult` or a lazy handle.
+pub(crate) fn response_into_py(
+    py: Python<'_>,
+    response: Response,
+    raw: bool,
+    format: ResultFormat,
+) -> PyResult<Py<PyAny>> {
+    if raw {
+        Ok(Py::new(py, ResponseHandle::new(py, response, format))?.into_any())
+    } else {
+        response_to_py(py, response, format)
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+
+use async_graphql::dynamic::TypeRef;
+use pyo3::prelude::*;
+use pyo3::types::PyAnyMethods;
+
+use crate::schema_types::{decode_type_kind, type_spec_to_type_ref};
+
+// Decides which `requires_role` tagged types and fields are registered for a schema variant.
+// An unrestricted filter keeps everything, which is how the base schema is built.
+pub(crate) struct RoleFilter {
+    roles: Option<HashSet<String>>,
+    excluded_types: HashSet<String>,
+}
+
+impl RoleFilter {
+    pub(crate) fn unrestricted() -> Self {
+        RoleFilter {
+            roles: None,
+            excluded_types: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn new(
+        py: Python<'_>,
+        roles: HashSet<String>,
+        types: &[Py<PyAny>],
+    ) -> PyResult<Self> {
+        let mut filter = RoleFilter {
+            roles: Some(roles),
+            excluded_types: HashSet::new(),
+        };
+
+        let mut unions = Vec::new();
+        for compiled_type in types {
+            let compiled_type = compiled_type.bind(py);
+            let meta = compiled_type.getattr("meta")?;
+            let name: String = meta.getattr("name")?.extract()?;
+            let requires_role: Option<String> = meta.getattr("requires_role")?.extract()?;
+            if !filter.allows_role(requires_role.as_deref()) {
+                filter.excluded_types.insert(name);
+            } else if decode_type_kind(&meta)? == "union" {
+                let possible_types: Vec<String> =
+                    compiled_type.getattr("possible_types")?.extract()?;
+                unions.push((name, possible_types));
+            }
+        }
+
+        // A union left without any visible member cannot be registered either.
+        for (name, possible_types) in unions {
+            if possible_types
+                .iter()
+                .all(|member| !filter.allows_type(member))
+            {
+                filter.excluded_types.insert(name);
+            }
+        }
+
+        Ok(filter)
+    }
+
+    pub(crate) fn allows_role(&self, requires_role: Option<&str>) -> bool {
+        match (&self.roles, requires_role) {
+            (Some(roles), Some(role)) => roles.contains(role),
+            _ => true,
+        }
+    }
+
+    pub(crate) fn allows_type(&self, type_name: &str) -> bool {
+        !self.excluded_types.contains(type_name)
+    }
+
+    fn allows_type_ref(&self, type_ref: &TypeRef) -> bool {
+        self.allows_type(type_ref.type_name())
+    }
+
+    fn allows_type_spec(&self, type_spec: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.allows_type_ref(&type_spec_to_type_ref(type_spec)?))
+    }
+
+    // A field is dropped when its own role is missing or when its output type or any argument
+    // type was excluded from the variant.
+    pub(crate) fn allows_field(&self, py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if self.roles.is_none() {
+            return Ok(true);
+        }
+        let requires_role: Option<String> = field.getattr("requires_role")?.extract()?;
+        if !self.allows_role(requires_role.as_deref())
+            || !self.allows_type_spec(&field.getattr("type_spec")?)?
+        {
+            return Ok(false);
+        }
+        if field.hasattr("args")? {
+            let args: Vec<Py<PyAny>> = field.getattr("args")?.extract()?;
+            for arg in &args {
+                if !self.allows_type_spec(&arg.bind(py).getattr("type_spec")?)? {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+}
@@ -1,20 +1,29 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use async_graphql::Value;
 use async_graphql::dynamic::{
     Field, FieldFuture, FieldValue, InputObject, InputValue, Interface, InterfaceField, Object,
     Schema, SchemaBuilder, Subscription, SubscriptionField, SubscriptionFieldFuture, TypeRef,
     Union,
 };
+use async_graphql::parser::parse_query;
 use pyo3::prelude::*;
 use pyo3::types::PyAnyMethods;
 
-use crate::errors::{py_type_error, py_value_error};
+use crate::check::{ParseErrors, ValidateOnly};
+use crate::constraints::{ArgumentChecks, InputConstraints};
+use crate::errors::{invalid_default_literal, py_type_error, schema_build_error};
+use crate::relay::{NODE_INTERFACE, global_id_field, node_field, node_interface, relay_node_types};
+use crate::remote::RemoteExecutor;
 use crate::resolver::{resolve_field, resolve_field_sync_fast, resolve_subscription_stream};
-use crate::types::{FieldContext, PyObj, ResolverEntry};
+use crate::roles::RoleFilter;
+use crate::types::{ErrorPolicy, FieldContext, PyObj, ResolverEntry, RetryPolicy};
 use crate::values::pyobj_to_value;
 
 const UNSUPPORTED_REGISTRATION_TYPE: &str =
     "Schema bundle contains an unsupported type registration object";
+const SCHEMA_DEFINITION_PREFIX: &str = "schema {";
 
 pub(crate) fn type_spec_to_type_ref(spec: &Bound<'_, PyAny>) -> PyResult<TypeRef> {
     let kind: String = spec.getattr("kind")?.extract()?;
@@ -39,75 +48,227 @@ fn unsupported_registration_type() -> PyErr {
 }
 
 fn build_field_context(
-    func: Py<PyAny>,
-    needs_context: bool,
-    is_async_gen: bool,
+    coordinate: String,
+    resolver: ResolverEntry,
     output_type: &TypeRef,
-) -> PyResult<Arc<FieldContext>> {
-    Ok(Arc::new(FieldContext {
-        resolver: Some(ResolverEntry {
-            func: PyObj::new(func),
-            needs_context,
-            is_async_gen,
-        }),
+    argument_checks: Option<ArgumentChecks>,
+) -> Arc<FieldContext> {
+    Arc::new(FieldContext {
+        coordinate,
+        resolver: Some(resolver),
         output_type: output_type.clone(),
-    }))
+        argument_checks: argument_checks.map(Arc::new),
+    })
+}
+
+// The call shape of a compiled resolver field (`@grommet.field` or `@grommet.subscription`).
+fn resolver_entry(field: &Bound<'_, PyAny>, is_async_gen: bool) -> PyResult<ResolverEntry> {
+    Ok(ResolverEntry {
+        func: PyObj::new(field.getattr("func")?.extract()?),
+        needs_context: field.getattr("needs_context")?.extract()?,
+        is_async_gen,
+        batched: field.getattr("batch")?.extract()?,
+        source_lookup: false,
+        needs_info: field.getattr("needs_info")?.extract()?,
+        retry: retry_policy(field)?,
+        id_arguments: id_arguments(field)?,
+        serial: field.getattr("serial")?.extract()?,
+    })
+}
+
+fn id_arguments(field: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    let mut names = Vec::new();
+    for arg in field.getattr("args")?.try_iter()? {
+        let arg = arg?;
+        let mut spec = arg.getattr("type_spec")?;
+        while spec.getattr("kind")?.extract::<String>()? == "list" {
+            spec = spec.getattr("of_type")?;
+        }
+        if spec.getattr("name")?.extract::<String>()? == TypeRef::ID {
+            names.push(arg.getattr("name")?.extract()?);
+        }
+    }
+    Ok(names)
+}
+
+fn retry_policy(field: &Bound<'_, PyAny>) -> PyResult<Option<Arc<RetryPolicy>>> {
+    let retries: u32 = field.getattr("retries")?.extract()?;
+    if retries == 0 {
+        return Ok(None);
+    }
+    Ok(Some(Arc::new(RetryPolicy {
+        retries,
+        backoff_ms: field.getattr("backoff_ms")?.extract()?,
+        on: field.getattr("retry_on")?.extract()?,
+    })))
 }
 
-fn default_value_from_payload<'py>(
-    payload: &Bound<'py, PyAny>,
-) -> PyResult<Option<Bound<'py, PyAny>>> {
+fn default_value_from_payload(payload: &Bound<'_, PyAny>) -> PyResult<Option<Value>> {
     let has_default: bool = payload.getattr("has_default")?.extract()?;
     if has_default {
-        Ok(Some(payload.getattr("default")?))
+        let default = PyObj::new(payload.getattr("default")?.unbind());
+        Ok(Some(pyobj_to_value(&default)?))
     } else {
         Ok(None)
     }
 }
 
+// Parses a GraphQL literal such as `[1, 2]` or `RED` as the default of a variable of
+// `type_ref`, since the parser only reads values within documents. Outside strings, the literal
+// may not hold the tokens that would end the variable and go on with the document.
+fn parse_default_literal(literal: &str, type_ref: &TypeRef) -> PyResult<Value> {
+    if let Some(token) = stray_token(literal) {
+        return Err(invalid_default_literal(
+            literal,
+            &format!("unexpected \"{token}\" outside a string"),
+        ));
+    }
+    let doc = parse_query(format!(
+        "query ($value: {type_ref} = {literal}) {{ __typename }}"
+    ))
+    .map_err(|err| invalid_default_literal(literal, &err.to_string()))?;
+    doc.operations
+        .iter()
+        .next()
+        .and_then(|(_name, operation)| operation.node.variable_definitions.first().cloned())
+        .and_then(|variable| variable.node.default_value)
+        .map(|value| value.node)
+        .ok_or_else(|| invalid_default_literal(literal, "expected a single value"))
+}
+
+// The first `)`, `#`, `@` or `$` of a literal outside its strings and block strings.
+fn stray_token(literal: &str) -> Option<char> {
+    let mut rest = literal;
+    while let Some(char) = rest.chars().next() {
+        rest = if let Some(block) = rest.strip_prefix("\"\"\"") {
+            after_block_string(block)
+        } else if let Some(string) = rest.strip_prefix('"') {
+            after_string(string)
+        } else if matches!(char, ')' | '#' | '@' | '$') {
+            return Some(char);
+        } else {
+            &rest[char.len_utf8()..]
+        };
+    }
+    None
+}
+
+// What follows the block string whose content starts `block`, which `\"""` does not end.
+fn after_block_string(block: &str) -> &str {
+    let mut rest = block;
+    while let Some(at) = rest.find("\"\"\"") {
+        let escaped = rest[..at].ends_with('\\');
+        rest = &rest[at + 3..];
+        if !escaped {
+            return rest;
+        }
+    }
+    ""
+}
+
+// What follows the string whose content starts `string`.
+fn after_string(string: &str) -> &str {
+    let mut chars = string.char_indices();
+    while let Some((at, char)) = chars.next() {
+        match char {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return &string[at + 1..],
+            _ => {}
+        }
+    }
+    ""
+}
+
 fn build_input_value(
     name: String,
     type_spec: &Bound<'_, PyAny>,
-    default_value: Option<&Bound<'_, PyAny>>,
+    default_value: Option<Value>,
     description: Option<&str>,
+    deprecation: Option<&str>,
 ) -> PyResult<InputValue> {
     let type_ref = type_spec_to_type_ref(type_spec)?;
     let mut iv = InputValue::new(name, type_ref);
     if let Some(default_value) = default_value {
-        let py_obj = PyObj::new(default_value.clone().unbind());
-        iv = iv.default_value(pyobj_to_value(&py_obj)?);
+        iv = iv.default_value(default_value);
     }
     if let Some(description) = description {
         iv = iv.description(description);
     }
+    if let Some(reason) = deprecation {
+        iv = iv.deprecation(Some(reason));
+    }
     Ok(iv)
 }
 
 fn build_argument_input_value(arg: &Bound<'_, PyAny>) -> PyResult<InputValue> {
     let name: String = arg.getattr("name")?.extract()?;
     let type_spec = arg.getattr("type_spec")?;
-    let default_value = default_value_from_payload(arg)?;
-    build_input_value(name, &type_spec, default_value.as_ref(), None)
+    // Arguments default to their `default_literal` when they have one, not a Python value.
+    let literal: Option<String> = arg.getattr("default_literal")?.extract()?;
+    let default_value = match literal {
+        Some(literal) => Some(parse_default_literal(
+            &literal,
+            &type_spec_to_type_ref(&type_spec)?,
+        )?),
+        None => default_value_from_payload(arg)?,
+    };
+    let deprecation: Option<String> = arg.getattr("deprecation")?.extract()?;
+    build_input_value(
+        name,
+        &type_spec,
+        default_value,
+        None,
+        deprecation.as_deref(),
+    )
+}
+
+// The explicit description of a field or type, falling back to the first line of its
+// resolver's or class's docstring when the schema was built with `docstring_descriptions`.
+fn description_of(item: &Bound<'_, PyAny>, docstrings: bool) -> PyResult<Option<String>> {
+    let description: Option<String> = item.getattr("description")?.extract()?;
+    if description.is_some() || !docstrings {
+        return Ok(description);
+    }
+    match item.getattr_opt("doc")? {
+        Some(doc) => doc.extract(),
+        None => Ok(None),
+    }
+}
+
+// Data fields are deprecated through their `Field` metadata; resolver fields carry none.
+fn deprecation_of(field: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    match field.getattr_opt("deprecation")? {
+        Some(reason) => reason.extract(),
+        None => Ok(None),
+    }
 }
 
 fn build_input_field_value(field: &Bound<'_, PyAny>) -> PyResult<InputValue> {
     let name: String = field.getattr("name")?.extract()?;
     let type_spec = field.getattr("type_spec")?;
     let description: Option<String> = field.getattr("description")?.extract()?;
+    let deprecation: Option<String> = field.getattr("deprecation")?.extract()?;
     let default_value = default_value_from_payload(field)?;
     build_input_value(
         name,
         &type_spec,
-        default_value.as_ref(),
+        default_value,
         description.as_deref(),
+        deprecation.as_deref(),
     )
 }
 
-fn build_interface_field(py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<InterfaceField> {
+fn build_interface_field(
+    py: Python<'_>,
+    field: &Bound<'_, PyAny>,
+    docstrings: bool,
+) -> PyResult<InterfaceField> {
     let name: String = field.getattr("name")?.extract()?;
     let type_spec = field.getattr("type_spec")?;
     let type_ref = type_spec_to_type_ref(&type_spec)?;
-    let description: Option<String> = field.getattr("description")?.extract()?;
+    let description = description_of(field, docstrings)?;
 
     let mut interface_field = InterfaceField::new(name, type_ref);
     if field.hasattr("args")? {
@@ -120,38 +281,52 @@ fn build_interface_field(py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<I
     if let Some(description) = description.as_deref() {
         interface_field = interface_field.description(description);
     }
+    if let Some(reason) = deprecation_of(field)? {
+        interface_field = interface_field.deprecation(Some(&reason));
+    }
 
     Ok(interface_field)
 }
 
-fn build_object_field(py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<Field> {
+fn build_object_field(
+    py: Python<'_>,
+    inputs: &Arc<InputConstraints>,
+    field: &Bound<'_, PyAny>,
+    type_name: &str,
+    relay_node: Option<&str>,
+    docstrings: bool,
+) -> PyResult<Field> {
     let name: String = field.getattr("name")?.extract()?;
+    let coordinate = format!("{type_name}.{name}");
+    let relay_node = relay_node.filter(|_| name == "id");
     let type_spec = field.getattr("type_spec")?;
     let type_ref = type_spec_to_type_ref(&type_spec)?;
-    let description: Option<String> = field.getattr("description")?.extract()?;
+    let description = description_of(field, docstrings)?;
     let is_data_field = field.hasattr("resolver_func")?;
 
     let mut graphql_field = if is_data_field {
         let func: Py<PyAny> = field.getattr("resolver_func")?.extract()?;
-        let field_ctx = build_field_context(func, false, false, &type_ref)?;
-        Field::new(name, type_ref, move |ctx| {
-            let result = resolve_field_sync_fast(&ctx, &field_ctx);
-            match result {
-                Ok(value) => FieldFuture::Value(Some(value)),
-                Err(err) => FieldFuture::new(async move { Err::<Option<FieldValue<'_>>, _>(err) }),
-            }
-        })
-    } else {
-        let func: Py<PyAny> = field.getattr("func")?.extract()?;
-        let needs_context: bool = field.getattr("needs_context")?.extract()?;
-        let is_async: bool = field.getattr("is_async")?.extract()?;
-        let field_ctx = build_field_context(func, needs_context, false, &type_ref)?;
-
-        let mut graphql_field = Field::new(name, type_ref, move |ctx| {
-            if is_async {
-                let field_ctx = field_ctx.clone();
-                FieldFuture::new(async move { resolve_field(ctx, field_ctx).await })
-            } else {
+        let resolver = ResolverEntry {
+            func: PyObj::new(func),
+            needs_context: false,
+            is_async_gen: false,
+            batched: false,
+            source_lookup: true,
+            needs_info: false,
+            retry: None,
+            id_arguments: Vec::new(),
+            serial: false,
+        };
+        let field_ctx = build_field_context(coordinate, resolver, &type_ref, None);
+        match relay_node {
+            Some(type_name) => global_id_field(type_name, field_ctx, false),
+            None => Field::new(name, type_ref, move |ctx| {
+                if let Some(remote) = RemoteExecutor::forwarding(&ctx, &field_ctx) {
+                    let field_ctx = field_ctx.clone();
+                    return FieldFuture::new(async move {
+                        remote.forward(&ctx, &field_ctx).await.map(Some)
+                    });
+                }
                 let result = resolve_field_sync_fast(&ctx, &field_ctx);
                 match result {
                     Ok(value) => FieldFuture::Value(Some(value)),
@@ -159,10 +334,33 @@ fn build_object_field(py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<Fiel
                         FieldFuture::new(async move { Err::<Option<FieldValue<'_>>, _>(err) })
                     }
                 }
-            }
-        });
-
+            }),
+        }
+    } else {
+        let is_async: bool = field.getattr("is_async")?.extract()?;
         let args: Vec<Py<PyAny>> = field.getattr("args")?.extract()?;
+        let checks = ArgumentChecks::from_args(py, &args, inputs)?;
+        let resolver = resolver_entry(field, false)?;
+        let field_ctx = build_field_context(coordinate, resolver, &type_ref, checks);
+
+        let mut graphql_field = match relay_node {
+            Some(type_name) => global_id_field(type_name, field_ctx, is_async),
+            None => Field::new(name, type_ref, move |ctx| {
+                if is_async {
+                    let field_ctx = field_ctx.clone();
+                    FieldFuture::new(async move { resolve_field(ctx, field_ctx).await })
+                } else {
+                    let result = resolve_field_sync_fast(&ctx, &field_ctx);
+                    match result {
+                        Ok(value) => FieldFuture::Value(Some(value)),
+                        Err(err) => {
+                            FieldFuture::new(async move { Err::<Option<FieldValue<'_>>, _>(err) })
+                        }
+                    }
+                }
+            }),
+        };
+
         for arg in &args {
             let iv = build_argument_input_value(arg.bind(py))?;
             graphql_field = graphql_field.argument(iv);
@@ -174,30 +372,38 @@ fn build_object_field(py: Python<'_>, field: &Bound<'_, PyAny>) -> PyResult<Fiel
     if let Some(description) = description.as_deref() {
         graphql_field = graphql_field.description(description);
     }
+    if let Some(reason) = deprecation_of(field)? {
+        graphql_field = graphql_field.deprecation(Some(&reason));
+    }
 
     Ok(graphql_field)
 }
 
 fn build_subscription_field(
     py: Python<'_>,
+    inputs: &Arc<InputConstraints>,
     field: &Bound<'_, PyAny>,
+    type_name: &str,
+    docstrings: bool,
 ) -> PyResult<SubscriptionField> {
     let name: String = field.getattr("name")?.extract()?;
+    let coordinate = format!("{type_name}.{name}");
     let type_spec = field.getattr("type_spec")?;
     let type_ref = type_spec_to_type_ref(&type_spec)?;
-    let func: Py<PyAny> = field.getattr("func")?.extract()?;
-    let needs_context: bool = field.getattr("needs_context")?.extract()?;
-    let description: Option<String> = field.getattr("description")?.extract()?;
-    let field_ctx = build_field_context(func, needs_context, true, &type_ref)?;
+    let description = description_of(field, docstrings)?;
+    let args: Vec<Py<PyAny>> = field.getattr("args")?.extract()?;
+    let checks = ArgumentChecks::from_args(py, &args, inputs)?;
+    let resolver = resolver_entry(field, true)?;
+    let policy = ErrorPolicy::parse(&field.getattr("on_error")?.extract::<String>()?)?;
+    let field_ctx = build_field_context(coordinate, resolver, &type_ref, checks);
 
     let mut graphql_field = SubscriptionField::new(name, type_ref, move |ctx| {
         let field_ctx = field_ctx.clone();
-        SubscriptionFieldFuture::new(
-            async move { resolve_subscription_stream(ctx, field_ctx).await },
-        )
+        SubscriptionFieldFuture::new(async move {
+            resolve_subscription_stream(ctx, field_ctx, policy).await
+        })
     });
 
-    let args: Vec<Py<PyAny>> = field.getattr("args")?.extract()?;
     for arg in &args {
         let iv = build_argument_input_value(arg.bind(py))?;
         graphql_field = graphql_field.argument(iv);
@@ -212,33 +418,51 @@ fn build_subscription_field(
 
 fn build_object_type(
     py: Python<'_>,
+    filter: &RoleFilter,
+    inputs: &Arc<InputConstraints>,
     compiled_type: &Bound<'_, PyAny>,
     type_name: &str,
     description: Option<&str>,
+    docstrings: bool,
 ) -> PyResult<Object> {
     let mut object = Object::new(type_name);
     if let Some(description) = description {
         object = object.description(description);
     }
 
+    let relay_node: bool = compiled_type
+        .getattr("meta")?
+        .getattr("relay_node")?
+        .extract()?;
+    let relay_node = relay_node.then_some(type_name);
     let fields: Vec<Py<PyAny>> = compiled_type.getattr("object_fields")?.extract()?;
     for field in &fields {
-        object = object.field(build_object_field(py, field.bind(py))?);
+        let field = field.bind(py);
+        if filter.allows_field(py, field)? {
+            object = object.field(build_object_field(
+                py, inputs, field, type_name, relay_node, docstrings,
+            )?);
+        }
     }
 
     let implements: Vec<String> = compiled_type.getattr("implements")?.extract()?;
-    for interface_name in &implements {
+    for interface_name in implements.iter().filter(|name| filter.allows_type(name)) {
         object = object.implement(interface_name);
     }
+    if relay_node.is_some() {
+        object = object.implement(NODE_INTERFACE);
+    }
 
     Ok(object)
 }
 
 fn build_interface_type(
     py: Python<'_>,
+    filter: &RoleFilter,
     compiled_type: &Bound<'_, PyAny>,
     type_name: &str,
     description: Option<&str>,
+    docstrings: bool,
 ) -> PyResult<Interface> {
     let mut interface = Interface::new(type_name);
     if let Some(description) = description {
@@ -247,11 +471,14 @@ fn build_interface_type(
 
     let fields: Vec<Py<PyAny>> = compiled_type.getattr("object_fields")?.extract()?;
     for field in &fields {
-        interface = interface.field(build_interface_field(py, field.bind(py))?);
+        let field = field.bind(py);
+        if filter.allows_field(py, field)? {
+            interface = interface.field(build_interface_field(py, field, docstrings)?);
+        }
     }
 
     let implements: Vec<String> = compiled_type.getattr("implements")?.extract()?;
-    for interface_name in &implements {
+    for interface_name in implements.iter().filter(|name| filter.allows_type(name)) {
         interface = interface.implement(interface_name);
     }
 
@@ -260,6 +487,7 @@ fn build_interface_type(
 
 fn build_input_object_type(
     py: Python<'_>,
+    filter: &RoleFilter,
     compiled_type: &Bound<'_, PyAny>,
     type_name: &str,
     description: Option<&str>,
@@ -271,7 +499,10 @@ fn build_input_object_type(
 
     let fields: Vec<Py<PyAny>> = compiled_type.getattr("input_fields")?.extract()?;
     for field in &fields {
-        input_object = input_object.field(build_input_field_value(field.bind(py))?);
+        let field = field.bind(py);
+        if filter.allows_field(py, field)? {
+            input_object = input_object.field(build_input_field_value(field)?);
+        }
     }
 
     Ok(input_object)
@@ -279,9 +510,12 @@ fn build_input_object_type(
 
 fn build_subscription_type(
     py: Python<'_>,
+    filter: &RoleFilter,
+    inputs: &Arc<InputConstraints>,
     compiled_type: &Bound<'_, PyAny>,
     type_name: &str,
     description: Option<&str>,
+    docstrings: bool,
 ) -> PyResult<Subscription> {
     let mut subscription = Subscription::new(type_name);
     if let Some(description) = description {
@@ -290,13 +524,19 @@ fn build_subscription_type(
 
     let fields: Vec<Py<PyAny>> = compiled_type.getattr("subscription_fields")?.extract()?;
     for field in &fields {
-        subscription = subscription.field(build_subscription_field(py, field.bind(py))?);
+        let field = field.bind(py);
+        if filter.allows_field(py, field)? {
+            subscription = subscription.field(build_subscription_field(
+                py, inputs, field, type_name, docstrings,
+            )?);
+        }
     }
 
     Ok(subscription)
 }
 
 fn build_union_type(
+    filter: &RoleFilter,
     compiled_union: &Bound<'_, PyAny>,
     type_name: &str,
     description: Option<&str>,
@@ -307,7 +547,10 @@ fn build_union_type(
     }
 
     let possible_types: Vec<String> = compiled_union.getattr("possible_types")?.extract()?;
-    for possible_type in &possible_types {
+    for possible_type in possible_types
+        .iter()
+        .filter(|name| filter.allows_type(name))
+    {
         union_type = union_type.possible_type(possible_type);
     }
 
@@ -322,15 +565,18 @@ pub(crate) enum RegistrableType {
     Union(Union),
 }
 
-fn decode_type_kind(meta: &Bound<'_, PyAny>) -> PyResult<String> {
+pub(crate) fn decode_type_kind(meta: &Bound<'_, PyAny>) -> PyResult<String> {
     let kind = meta.getattr("kind")?;
     kind.getattr("value")?.extract()
 }
 
 fn decode_registrable_type(
     py: Python<'_>,
+    filter: &RoleFilter,
+    inputs: &Arc<InputConstraints>,
     compiled_type: &Bound<'_, PyAny>,
-) -> PyResult<RegistrableType> {
+    docstrings: bool,
+) -> PyResult<Option<RegistrableType>> {
     let meta = compiled_type
         .getattr("meta")
         .map_err(|_| unsupported_registration_type())?;
@@ -339,57 +585,147 @@ fn decode_registrable_type(
         .getattr("name")
         .and_then(|value| value.extract())
         .map_err(|_| unsupported_registration_type())?;
-    let description: Option<String> = meta
-        .getattr("description")
-        .and_then(|value| value.extract())
-        .map_err(|_| unsupported_registration_type())?;
+    let description =
+        description_of(&meta, docstrings).map_err(|_| unsupported_registration_type())?;
+    if !filter.allows_type(&type_name) {
+        return Ok(None);
+    }
 
-    match kind.as_str() {
-        "object" => Ok(RegistrableType::Object(build_object_type(
+    let registrable = match kind.as_str() {
+        "object" => RegistrableType::Object(build_object_type(
             py,
+            filter,
+            inputs,
             compiled_type,
             &type_name,
             description.as_deref(),
-        )?)),
-        "interface" => Ok(RegistrableType::Interface(build_interface_type(
+            docstrings,
+        )?),
+        "interface" => RegistrableType::Interface(build_interface_type(
             py,
+            filter,
             compiled_type,
             &type_name,
             description.as_deref(),
-        )?)),
-        "input" => Ok(RegistrableType::InputObject(build_input_object_type(
+            docstrings,
+        )?),
+        "input" => RegistrableType::InputObject(build_input_object_type(
             py,
+            filter,
             compiled_type,
             &type_name,
             description.as_deref(),
-        )?)),
-        "subscription" => Ok(RegistrableType::Subscription(build_subscription_type(
+        )?),
+        "subscription" => RegistrableType::Subscription(build_subscription_type(
             py,
+            filter,
+            inputs,
             compiled_type,
             &type_name,
             description.as_deref(),
-        )?)),
-        "union" => Ok(RegistrableType::Union(build_union_type(
+            docstrings,
+        )?),
+        "union" => RegistrableType::Union(build_union_type(
+            filter,
             compiled_type,
             &type_name,
             description.as_deref(),
-        )?)),
-        _ => Err(unsupported_registration_type()),
+        )?),
+        _ => return Err(unsupported_registration_type()),
+    };
+    Ok(Some(registrable))
+}
+
+fn uses_default_root_names(schema: &Schema) -> bool {
+    let registry = schema.registry();
+    registry.query_type == "Query"
+        && registry
+            .mutation_type
+            .as_deref()
+            .is_none_or(|name| name == "Mutation")
+        && registry
+            .subscription_type
+            .as_deref()
+            .is_none_or(|name| name == "Subscription")
+}
+
+// Schema description and leading comment lines added to the exported SDL.
+#[derive(Clone, Default)]
+pub(crate) struct SdlHeader {
+    pub(crate) description: Option<String>,
+    pub(crate) comments: Option<String>,
+}
+
+// Only emit the `schema { ... }` definition when a root type is renamed or the schema is
+// described, as SDL-first tools assume the conventional Query/Mutation/Subscription names
+// otherwise.
+pub(crate) fn schema_sdl(schema: &Schema, header: &SdlHeader) -> String {
+    let mut sdl = schema.sdl();
+    if let Some(index) = sdl.rfind(SCHEMA_DEFINITION_PREFIX) {
+        match &header.description {
+            Some(description) => {
+                let description = description.replace("\"\"\"", "\\\"\"\"");
+                sdl.insert_str(index, &format!("\"\"\"\n{description}\n\"\"\"\n"));
+            }
+            None if uses_default_root_names(schema) => sdl.truncate(index),
+            None => {}
+        }
+    }
+    match &header.comments {
+        Some(comments) => {
+            let mut out = String::new();
+            for line in comments.lines() {
+                out.push('#');
+                if !line.is_empty() {
+                    out.push(' ');
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+            out.push_str(&sdl);
+            out
+        }
+        None => sdl,
     }
 }
 
+// Builds the schema of a compiled bundle, with only the fields `roles` may see when given.
 pub(crate) fn register_schema(
     py: Python<'_>,
-    query: &str,
-    mutation: Option<&str>,
-    subscription: Option<&str>,
-    types: Vec<Py<PyAny>>,
+    bundle: &Bound<'_, PyAny>,
+    roles: Option<&HashSet<String>>,
+    validate_only: bool,
+    docstrings: bool,
 ) -> PyResult<Schema> {
-    let mut builder: SchemaBuilder = Schema::build(query, mutation, subscription);
+    let query: String = bundle.getattr("query")?.extract()?;
+    let mutation: Option<String> = bundle.getattr("mutation")?.extract()?;
+    let subscription: Option<String> = bundle.getattr("subscription")?.extract()?;
+    let types: Vec<Py<PyAny>> = bundle.getattr("types")?.extract()?;
+    let filter = match roles {
+        Some(roles) => RoleFilter::new(py, roles.clone(), &types)?,
+        None => RoleFilter::unrestricted(),
+    };
+    let mut builder: SchemaBuilder =
+        Schema::build(&query, mutation.as_deref(), subscription.as_deref());
+    let inputs = Arc::new(InputConstraints::from_types(py, &types)?);
+    let node_types = relay_node_types(py, &query, &types, &filter)?;
+    if !node_types.is_empty() {
+        builder = builder.register(node_interface());
+    }
 
     for compiled_type in &types {
-        let registrable = decode_registrable_type(py, compiled_type.bind(py))?;
+        let Some(registrable) =
+            decode_registrable_type(py, &filter, &inputs, compiled_type.bind(py), docstrings)?
+        else {
+            continue;
+        };
         builder = match registrable {
+            RegistrableType::Object(object)
+                if object.type_name() == query && !node_types.is_empty() =>
+            {
+                builder.register(object.field(node_field(node_types.clone())))
+            }
             RegistrableType::Object(object) => builder.register(object),
             RegistrableType::Interface(interface) => builder.register(interface),
             RegistrableType::InputObject(input_object) => builder.register(input_object),
@@ -397,8 +733,13 @@ pub(crate) fn register_schema(
             RegistrableType::Union(union_type) => builder.register(union_type),
         };
     }
+    builder = builder.extension(ParseErrors);
+    if validate_only {
+        builder = builder.extension(ValidateOnly);
+    }
 
-    builder
-        .finish()
-        .map_err(|err| py_value_error(err.to_string()))
+    // Validating and indexing the registered types needs no Python objects, so other threads,
+    // such as an event loop waiting on `Schema.build_async`, run meanwhile.
+    py.detach(|| builder.finish())
+        .map_err(|err| schema_build_error(err.to_string()))
 }
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_graphql::parser::types::{ExecutableDocument, OperationType};
+use async_graphql::{Response, Value};
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict};
+
+use crate::response::ResponseHandle;
+use crate::types::PyObj;
+use crate::values::{ValueSettings, payload_to_py};
+
+// A second schema that queries run against as well, in the background, so a rewrite of its
+// resolvers can be checked against live traffic. Its responses are discarded, or compared with
+// the primary's when `compare` is set, and only the counts are kept.
+#[derive(Clone)]
+pub(crate) struct ShadowTraffic {
+    schema: PyObj,
+    compare: bool,
+    // Whether the shadow schema closes the contexts of the requests it runs.
+    manages_context: bool,
+    stats: Arc<ShadowStats>,
+}
+
+#[derive(Default)]
+struct ShadowStats {
+    mirrored: AtomicU64,
+    divergent: AtomicU64,
+    failed: AtomicU64,
+    // Shadow runs in flight, kept alive here since the event loop only holds weak references
+    // to its tasks.
+    pending: Mutex<HashMap<u64, PyObj>>,
+    next_task: AtomicU64,
+}
+
+// A request run through the primary schema, to run again on the shadow.
+pub(crate) struct MirroredRequest {
+    pub(crate) query: String,
+    pub(crate) operation_name: Option<String>,
+    pub(crate) variables: Option<Value>,
+    pub(crate) context: Option<Py<PyAny>>,
+}
+
+// What a shadow response is compared on: the data, and the messages of the errors in order.
+#[derive(PartialEq)]
+struct Outcome {
+    data: Value,
+    errors: Vec<String>,
+}
+
+impl Outcome {
+    fn of(response: &Response) -> Self {
+        Outcome {
+            data: response.data.clone(),
+            errors: response
+                .errors
+                .iter()
+                .map(|error| error.message.clone())
+                .collect(),
+        }
+    }
+}
+
+impl ShadowTraffic {
+    pub(crate) fn new(schema: PyObj, compare: bool, manages_context: bool) -> Self {
+        ShadowTraffic {
+            schema,
+            compare,
+            manages_context,
+            stats: Arc::default(),
+        }
+    }
+
+    // Requests with a context are mirrored only while neither schema manages contexts, since a
+    // managed context would be closed by one run while the other still uses it.
+    pub(crate) fn mirrors_context(&self, primary_manages_context: bool) -> bool {
+        !primary_manages_context && !self.manages_context
+    }
+
+    // Only queries are mirrored: running mutations twice would repeat their side effects, and
+    // subscriptions have no single response to compare.
+    pub(crate) fn mirrors(doc: &ExecutableDocument) -> bool {
+        doc.operations
+            .iter()
+            .all(|(_, operation)| operation.node.ty == OperationType::Query)
+    }
+
+    // Starts the request on the shadow schema as a task of the running event loop and returns
+    // without waiting for it. The shadow shares the primary request's context object, see
+    // `mirrors_context`.
+    pub(crate) fn mirror(
+        &self,
+        py: Python<'_>,
+        request: &MirroredRequest,
+        primary: &Response,
+        settings: ValueSettings,
+    ) {
+        self.stats.mirrored.fetch_add(1, Ordering::Relaxed);
+        if self.start(py, request, primary, settings).is_err() {
+            self.stats.failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn start(
+        &self,
+        py: Python<'_>,
+        request: &MirroredRequest,
+        primary: &Response,
+        settings: ValueSettings,
+    ) -> PyResult<()> {
+        let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+        let variables = request
+            .variables
+            .as_ref()
+            .map(|variables| payload_to_py(py, variables, settings))
+            .transpose()?;
+        let options = PyDict::new(py);
+        options.set_item("raw", true)?;
+        options.set_item("operation_name", &request.operation_name)?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("options", options)?;
+        let context = request.context.as_ref().map(|context| context.bind(py));
+        let run = self.schema.bind(py).call_method(
+            "execute",
+            (&request.query, variables, context),
+            Some(&kwargs),
+        )?;
+        let task = event_loop.call_method1("create_task", (run,))?;
+
+        let id = self.stats.next_task.fetch_add(1, Ordering::Relaxed);
+        let expected = self.compare.then(|| Outcome::of(primary));
+        let stats = self.stats.clone();
+        let done = PyCFunction::new_closure(
+            py,
+            Some(c"grommet_shadow_done"),
+            None,
+            move |args, _kwargs| -> PyResult<()> {
+                let task = args.get_item(0)?;
+                stats
+                    .pending
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .remove(&id);
+                if task.call_method0("cancelled")?.is_truthy()?
+                    || !task.call_method0("exception")?.is_none()
+                {
+                    stats.failed.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                if let Some(expected) = &expected {
+                    let result = task.call_method0("result")?;
+                    let handle = result.cast::<ResponseHandle>()?;
+                    if Outcome::of(handle.get().response()) != *expected {
+                        stats.divergent.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        task.call_method1("add_done_callback", (done,))?;
+        self.stats
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, PyObj::new(task.unbind()));
+        Ok(())
+    }
+
+    // `{"mirrored", "divergent", "failed", "pending"}`: the queries sent to the shadow, those
+    // whose shadow response differed from the primary's, those whose shadow run raised or could
+    // not start, and the shadow runs still in flight.
+    pub(crate) fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = PyDict::new(py);
+        stats.set_item("mirrored", self.stats.mirrored.load(Ordering::Relaxed))?;
+        stats.set_item("divergent", self.stats.divergent.load(Ordering::Relaxed))?;
+        stats.set_item("failed", self.stats.failed.load(Ordering::Relaxed))?;
+        let pending = self
+            .stats
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len();
+        stats.set_item("pending", pending)?;
+        Ok(stats)
+    }
+}
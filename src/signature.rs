@@ -0,0 +1,269 @@
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+use std::sync::LazyLock;
+
+use async_graphql::parser::types::{
+    Directive, ExecutableDocument, OperationDefinition, OperationType, Selection, SelectionSet,
+    VariableDefinition,
+};
+use async_graphql::parser::{Positioned, parse_query};
+use async_graphql::{Name, Value};
+use pyo3::prelude::*;
+use regex::Regex;
+
+use crate::errors::validation_failed;
+
+static WHITESPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").expect("valid regex"));
+static SPACE_AFTER_PUNCTUATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([^_a-zA-Z0-9]) ").expect("valid regex"));
+static SPACE_BEFORE_PUNCTUATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r" ([^_a-zA-Z0-9])").expect("valid regex"));
+
+// The Apollo usage-reporting signature of an operation, which stays the same across requests
+// that differ only in literals, aliases, the order of fields and arguments, or whitespace:
+// fragments the operation does not use are dropped, literals become `0`, `""`, `[]`, or `{}`,
+// aliases are removed, selections, arguments, directives, and variables are sorted by name, and
+// the document is printed with the whitespace that separates words only. `None` when the named
+// operation, or the document's only one when unnamed, is missing.
+pub(crate) fn operation_signature(
+    doc: &ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<String> {
+    let mut operations = doc.operations.iter();
+    let (name, operation) = match operation_name {
+        Some(operation_name) => {
+            operations.find(|(name, _)| name.is_some_and(|name| name.as_str() == operation_name))?
+        }
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => operation,
+            _ => return None,
+        },
+    };
+
+    let mut fragments = BTreeSet::new();
+    collect_fragments(doc, &operation.node.selection_set.node, &mut fragments);
+    let mut definitions: Vec<String> = fragments
+        .iter()
+        .filter_map(|name| {
+            let fragment = &doc.fragments.get(&Name::new(name))?.node;
+            Some(join(&[
+                format!(
+                    "fragment {name} on {}",
+                    fragment.type_condition.node.on.node
+                ),
+                directives(&fragment.directives),
+                selection_set(&fragment.selection_set.node),
+            ]))
+        })
+        .collect();
+    definitions.push(operation_definition(
+        name.map(Name::as_str),
+        &operation.node,
+    ));
+
+    let document = definitions.join("\n\n");
+    let printed = WHITESPACE.replace_all(&document, " ");
+    let printed = SPACE_AFTER_PUNCTUATION.replace_all(&printed, "$1");
+    Some(
+        SPACE_BEFORE_PUNCTUATION
+            .replace_all(&printed, "$1")
+            .trim()
+            .to_owned(),
+    )
+}
+
+fn collect_fragments(
+    doc: &ExecutableDocument,
+    selection_set: &SelectionSet,
+    used: &mut BTreeSet<String>,
+) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => collect_fragments(doc, &field.node.selection_set.node, used),
+            Selection::InlineFragment(inline) => {
+                collect_fragments(doc, &inline.node.selection_set.node, used);
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = &spread.node.fragment_name.node;
+                if used.insert(name.to_string())
+                    && let Some(fragment) = doc.fragments.get(name)
+                {
+                    collect_fragments(doc, &fragment.node.selection_set.node, used);
+                }
+            }
+        }
+    }
+}
+
+// Joins the non-empty parts of a printed node with spaces.
+fn join(parts: &[String]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn operation_definition(name: Option<&str>, operation: &OperationDefinition) -> String {
+    let selections = selection_set(&operation.selection_set.node);
+    if operation.ty == OperationType::Query
+        && name.is_none()
+        && operation.variable_definitions.is_empty()
+        && operation.directives.is_empty()
+    {
+        return selections;
+    }
+    let mut variables: Vec<&VariableDefinition> = operation
+        .variable_definitions
+        .iter()
+        .map(|variable| &variable.node)
+        .collect();
+    variables.sort_by(|a, b| a.name.node.cmp(&b.name.node));
+    let variables: Vec<String> = variables
+        .into_iter()
+        .map(|variable| {
+            let default = variable
+                .default_value
+                .as_ref()
+                .map(|value| format!(" = {}", hidden(&value.node)))
+                .unwrap_or_default();
+            join(&[
+                format!(
+                    "${}: {}{default}",
+                    variable.name.node, variable.var_type.node
+                ),
+                directives(&variable.directives),
+            ])
+        })
+        .collect();
+    let variables = if variables.is_empty() {
+        String::new()
+    } else {
+        format!("({})", variables.join(", "))
+    };
+    join(&[
+        operation.ty.to_string(),
+        format!("{}{variables}", name.unwrap_or_default()),
+        directives(&operation.directives),
+        selections,
+    ])
+}
+
+fn selection_set(selection_set: &SelectionSet) -> String {
+    let mut selections: Vec<(u8, &str, String)> = selection_set
+        .items
+        .iter()
+        .map(|selection| match &selection.node {
+            Selection::Field(field) => {
+                let field = &field.node;
+                let arguments = arguments(field.arguments.iter().map(|(name, value)| {
+                    let Ok(value) = value.node.clone().into_const_with(variable_sentinel);
+                    (&name.node, value)
+                }));
+                let printed = join(&[
+                    format!("{}{arguments}", field.name.node),
+                    directives(&field.directives),
+                    self::selection_set(&field.selection_set.node),
+                ]);
+                (0, field.name.node.as_str(), printed)
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.node.fragment_name.node.as_str();
+                let printed = join(&[format!("...{name}"), directives(&spread.node.directives)]);
+                (1, name, printed)
+            }
+            Selection::InlineFragment(inline) => {
+                let inline = &inline.node;
+                let condition = inline
+                    .type_condition
+                    .as_ref()
+                    .map(|condition| format!("on {}", condition.node.on.node))
+                    .unwrap_or_default();
+                let printed = join(&[
+                    "...".to_owned(),
+                    condition,
+                    directives(&inline.directives),
+                    self::selection_set(&inline.selection_set.node),
+                ]);
+                (2, "", printed)
+            }
+        })
+        .collect();
+    if selections.is_empty() {
+        return String::new();
+    }
+    selections.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    let selections: Vec<String> = selections
+        .into_iter()
+        .map(|(_, _, printed)| printed)
+        .collect();
+    format!("{{ {} }}", selections.join(" "))
+}
+
+fn directives(directives: &[Positioned<Directive>]) -> String {
+    let mut directives: Vec<&Directive> =
+        directives.iter().map(|directive| &directive.node).collect();
+    directives.sort_by(|a, b| a.name.node.cmp(&b.name.node));
+    directives
+        .into_iter()
+        .map(|directive| {
+            let arguments = arguments(directive.arguments.iter().map(|(name, value)| {
+                let Ok(value) = value.node.clone().into_const_with(variable_sentinel);
+                (&name.node, value)
+            }));
+            format!("@{}{arguments}", directive.name.node)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn arguments<'a>(arguments: impl Iterator<Item = (&'a Name, Value)>) -> String {
+    let mut arguments: Vec<(&Name, String)> = arguments
+        .map(|(name, value)| (name, hidden(&value)))
+        .collect();
+    if arguments.is_empty() {
+        return String::new();
+    }
+    arguments.sort_by(|a, b| a.0.cmp(b.0));
+    let arguments: Vec<String> = arguments
+        .into_iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect();
+    format!("({})", arguments.join(", "))
+}
+
+// Variables in argument values become enum values named `$<variable>`, which print as the
+// variable itself.
+fn variable_sentinel(name: Name) -> Result<Value, Infallible> {
+    Ok(Value::Enum(Name::new(format!("${name}"))))
+}
+
+fn hidden(value: &Value) -> String {
+    match value {
+        Value::Number(_) => "0".to_owned(),
+        Value::String(_) | Value::Binary(_) => "\"\"".to_owned(),
+        Value::List(_) => "[]".to_owned(),
+        Value::Object(_) => "{}".to_owned(),
+        Value::Null => "null".to_owned(),
+        Value::Boolean(flag) => flag.to_string(),
+        Value::Enum(name) => name.to_string(),
+    }
+}
+
+// Python entry point of `operation_signature`, for correlating logs with the metrics keyed by
+// it.
+#[pyfunction(name = "operation_signature")]
+#[pyo3(signature = (query, operation_name=None))]
+pub(crate) fn py_operation_signature(
+    query: &str,
+    operation_name: Option<&str>,
+) -> PyResult<String> {
+    let doc = parse_query(query).map_err(|err| validation_failed(err.to_string()))?;
+    operation_signature(&doc, operation_name).ok_or_else(|| match operation_name {
+        Some(name) => validation_failed(format!("Unknown operation named \"{name}\".")),
+        None => {
+            validation_failed("Operation name is required for documents with several operations.")
+        }
+    })
+}
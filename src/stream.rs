@@ -0,0 +1,221 @@
+use std::mem;
+use std::sync::{Arc, PoisonError};
+use std::time::Instant;
+
+use async_graphql::Response;
+use async_graphql::futures_util::future::{Either, select};
+use async_graphql::futures_util::lock::Mutex;
+use async_graphql::futures_util::stream::{BoxStream, StreamExt};
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+
+use crate::errors::{describe_exceptions, not_picklable, stream_used_after_fork};
+use crate::ordering::KeyOrder;
+use crate::request::{StreamOptions, close_contexts, schedule_close};
+use crate::resolver::awaitable_into_future;
+use crate::subscriptions::SubscriptionHandle;
+use crate::types::{CoercionWarnings, PyObj, RequestStats, SubscriptionErrors};
+use crate::values::{
+    ResultFormat, chunked_value_to_py, converted_response_to_py, payload_to_py, response_to_payload,
+};
+
+#[pyclass(module = "grommet._core", name = "SubscriptionStream")]
+pub(crate) struct SubscriptionStream {
+    pub(crate) stream: Arc<Mutex<Option<BoxStream<'static, async_graphql::Response>>>>,
+    // The request's contexts when the schema manages them, closed once the stream ends.
+    pub(crate) contexts: std::sync::Mutex<Vec<Py<PyAny>>>,
+    pub(crate) handle: Arc<SubscriptionHandle>,
+    pub(crate) last_cursor: Arc<std::sync::Mutex<Option<String>>>,
+    pub(crate) warnings: Option<CoercionWarnings>,
+    pub(crate) errors: SubscriptionErrors,
+    // Present when the schema was in debug mode as the subscription started.
+    pub(crate) stats: Option<RequestStats>,
+    pub(crate) order: Option<KeyOrder>,
+    pub(crate) started: Instant,
+    pub(crate) options: StreamOptions,
+    pub(crate) format: ResultFormat,
+    // The event loop the stream started on, when it holds contexts to close.
+    pub(crate) event_loop: Option<PyObj>,
+}
+
+impl SubscriptionStream {
+    // Closes the request's managed contexts the first time the stream ends.
+    async fn close_contexts(&self) -> PyResult<()> {
+        let contexts =
+            mem::take(&mut *self.contexts.lock().unwrap_or_else(PoisonError::into_inner));
+        close_contexts(contexts).await
+    }
+
+    // The next event, or `None` once the stream ends or is closed for running out of time.
+    async fn next_event(
+        &self,
+        stream: &mut BoxStream<'static, Response>,
+    ) -> PyResult<Option<Response>> {
+        let Some((remaining, reason)) = self.options.remaining(self.started) else {
+            return Ok(stream.next().await);
+        };
+        if !remaining.is_zero() {
+            let timer = Python::attach(|py| {
+                let sleep = py
+                    .import("asyncio")?
+                    .call_method1("sleep", (remaining.as_secs_f64(),))?;
+                Ok::<_, PyErr>(awaitable_into_future(sleep))
+            })?;
+            match select(stream.next(), timer).await {
+                Either::Left((event, _)) => return Ok(event),
+                Either::Right((elapsed, _)) => elapsed.map(drop)?,
+            }
+        }
+        self.handle.close(reason);
+        Ok(None)
+    }
+}
+
+#[pymethods]
+impl SubscriptionStream {
+    // Cursor of the last delivered event, to pass as `resume_from` when reconnecting.
+    #[getter]
+    fn cursor(&self) -> Option<String> {
+        self.last_cursor
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    #[getter]
+    fn operation_name(&self) -> Option<String> {
+        self.handle.operation_name.clone()
+    }
+
+    #[getter]
+    fn field_name(&self) -> Option<String> {
+        self.handle.field_name.clone()
+    }
+
+    #[getter]
+    fn started_at(&self) -> f64 {
+        self.handle.started_at
+    }
+
+    // Why the stream stopped: "completed" when the subscription ran out of events, "closed"
+    // after `aclose`, "cancelled" by `cancel_all`, or "max_lifetime" / "idle_timeout" when a
+    // time limit ran out. `None` while the stream is open.
+    fn close_reason(&self) -> Option<&'static str> {
+        self.handle.close_reason()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.handle.is_closed()
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(slf: PyRef<'py, Self>) -> PyResult<Bound<'py, PyAny>> {
+        let py = slf.py();
+        let slf_obj: Py<Self> = slf.into();
+        slf_obj.bind(py).call_method0("_anext_impl")
+    }
+
+    #[pyo3(name = "_anext_impl")]
+    async fn anext_impl(&self) -> PyResult<Py<PyAny>> {
+        if self.handle.pid != std::process::id() {
+            return Err(stream_used_after_fork());
+        }
+        if self.handle.is_closed() {
+            self.close_contexts().await?;
+            return Err(PyErr::new::<PyStopAsyncIteration, _>(""));
+        }
+        let mut guard = self.stream.lock().await;
+        let Some(stream) = guard.as_mut() else {
+            return Err(PyErr::new::<PyStopAsyncIteration, _>(""));
+        };
+        match self.next_event(stream).await? {
+            Some(mut response) => {
+                if let Some(order) = &self.order {
+                    order.apply(&mut response);
+                }
+                self.errors.drain_into(&mut response);
+                if let Some(warnings) = &self.warnings {
+                    warnings.drain_into(&mut response);
+                }
+                if let Some(stats) = &self.stats {
+                    stats.drain_into(&mut response);
+                }
+                let data = match self.options.chunk_size {
+                    Some(chunk_size) => Some(
+                        chunked_value_to_py(&response.data, chunk_size, self.format.values).await?,
+                    ),
+                    None => None,
+                };
+                Python::attach(|py| {
+                    if self.stats.is_some() {
+                        describe_exceptions(py, &mut response)?;
+                    }
+                    let data = match data {
+                        Some(data) => data,
+                        None => payload_to_py(py, &response.data, self.format.values)?.unbind(),
+                    };
+                    if self.options.envelope {
+                        return response_to_payload(py, response, data, self.format, self.cursor());
+                    }
+                    converted_response_to_py(py, response, data, self.format)
+                })
+            }
+            None => {
+                self.handle.close("completed");
+                *guard = None;
+                self.close_contexts().await?;
+                Err(PyErr::new::<PyStopAsyncIteration, _>(""))
+            }
+        }
+    }
+
+    async fn aclose(&self) -> PyResult<()> {
+        self.handle.close("closed");
+        let mut guard = self.stream.lock().await;
+        *guard = None;
+        self.close_contexts().await
+    }
+
+    fn __reduce__(&self) -> PyResult<Py<PyAny>> {
+        Err(not_picklable("subscription stream"))
+    }
+
+    async fn __aenter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    // Closes the stream however the `async with` block exits, letting exceptions propagate.
+    async fn __aexit__(
+        &self,
+        _exc_type: Py<PyAny>,
+        _exc_value: Py<PyAny>,
+        _traceback: Py<PyAny>,
+    ) -> PyResult<bool> {
+        self.aclose().await?;
+        Ok(false)
+    }
+}
+
+// A stream dropped while open counts as closed; dropping its events lets the resolver's
+// iterator be closed on its event loop, where the contexts it still holds are closed too.
+impl Drop for SubscriptionStream {
+    fn drop(&mut self) {
+        self.handle.close("closed");
+        let contexts = mem::take(
+            self.contexts
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        let (false, Some(event_loop)) = (contexts.is_empty(), self.event_loop.take()) else {
+            return;
+        };
+        Python::try_attach(|py| {
+            if let Err(err) = schedule_close(py, contexts, &event_loop.bind(py)) {
+                err.write_unraisable(py, None);
+            }
+        });
+    }
+}
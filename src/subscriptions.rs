@@ -0,0 +1,108 @@
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_graphql::futures_util::stream::AbortHandle;
+
+// What is known about a subscription stream, shared between the stream and the schema's
+// registry of active subscriptions.
+pub(crate) struct SubscriptionHandle {
+    pub(crate) operation_name: Option<String>,
+    // Name of the subscription root field the stream delivers events from.
+    pub(crate) field_name: Option<String>,
+    // When the subscription started, in seconds since the Unix epoch.
+    pub(crate) started_at: f64,
+    pub(crate) labels: Vec<String>,
+    // The process the stream was started in; a forked child cannot drive the parent's streams.
+    pub(crate) pid: u32,
+    closed: AtomicBool,
+    close_reason: Mutex<Option<&'static str>>,
+    abort: AbortHandle,
+}
+
+impl SubscriptionHandle {
+    pub(crate) fn new(
+        operation_name: Option<String>,
+        field_name: Option<String>,
+        labels: Vec<String>,
+        abort: AbortHandle,
+    ) -> Self {
+        Self {
+            operation_name,
+            field_name,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0.0, |since| since.as_secs_f64()),
+            labels,
+            pid: process::id(),
+            closed: AtomicBool::new(false),
+            close_reason: Mutex::new(None),
+            abort,
+        }
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn close_reason(&self) -> Option<&'static str> {
+        *self
+            .close_reason
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Marks the stream closed; the first reason given sticks.
+    pub(crate) fn close(&self, reason: &'static str) {
+        self.close_reason
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert(reason);
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    // Closes the stream from outside, ending the wait of a consumer blocked on its next event.
+    pub(crate) fn cancel(&self) {
+        self.close("cancelled");
+        self.abort.abort();
+    }
+}
+
+// Weak handles to the subscription streams started through a schema and its variants, so
+// dropped streams do not linger. A child process forked from the one that registered them
+// starts with an empty registry.
+#[derive(Default)]
+pub(crate) struct ActiveSubscriptions {
+    handles: Mutex<Vec<Weak<SubscriptionHandle>>>,
+    pid: AtomicU32,
+}
+
+impl ActiveSubscriptions {
+    fn handles(&self) -> MutexGuard<'_, Vec<Weak<SubscriptionHandle>>> {
+        let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+        let pid = process::id();
+        if self.pid.swap(pid, Ordering::SeqCst) != pid {
+            handles.clear();
+        }
+        handles
+    }
+
+    pub(crate) fn register(&self, handle: &Arc<SubscriptionHandle>) {
+        let mut handles = self.handles();
+        handles.retain(|handle| handle.strong_count() > 0);
+        handles.push(Arc::downgrade(handle));
+    }
+
+    // Handles of the streams that are still open, oldest first.
+    pub(crate) fn live(&self) -> Vec<Arc<SubscriptionHandle>> {
+        let mut handles = self.handles();
+        let live: Vec<_> = handles
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|handle| !handle.is_closed())
+            .collect();
+        *handles = live.iter().map(Arc::downgrade).collect();
+        live
+    }
+}
@@ -1,8 +1,18 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 
-use async_graphql::dynamic::TypeRef;
+use async_graphql::dynamic::{ResolverContext, TypeRef};
+use async_graphql::futures_util::lock::Mutex as AsyncMutex;
+use async_graphql::{Name, QueryEnv, Response, ServerError, Value};
+use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
 
+use crate::constraints::ArgumentChecks;
+use crate::errors::{py_value_error, response_too_large};
+use crate::ids::TypedIds;
+use crate::values::ValueSettings;
+
 #[derive(Clone)]
 pub(crate) struct PyObj {
     inner: Arc<Py<PyAny>>,
@@ -27,15 +37,398 @@ impl PyObj {
 #[derive(Clone)]
 pub(crate) struct ContextValue(pub(crate) PyObj);
 
+// Schema-wide `on_result(type_name, value)` hook attached to each request when configured.
+#[derive(Clone)]
+pub(crate) struct ResultHook(pub(crate) PyObj);
+
+// The bundle the schema was built from, handed to resolvers as `Info.schema`.
+#[derive(Clone)]
+pub(crate) struct SchemaBundle(pub(crate) PyObj);
+
+// Schema-wide `resolver(parent, info)` callables that data fields resolve through instead of
+// reading the parent, when the schema was built with them. They are keyed by field coordinate
+// (`Type.field`), by type (`Type.*`), or `*` for any field, and the most specific key wins; a
+// lone `default_resolver` callable is keyed `*`, a list of them by their qualified names, and a
+// `ResolverRegistry` by the fields they were registered for.
+#[derive(Clone)]
+pub(crate) struct DefaultResolver(pub(crate) Arc<HashMap<String, PyObj>>);
+
+impl DefaultResolver {
+    pub(crate) fn lookup(&self, coordinate: &str) -> Option<&PyObj> {
+        let type_name = coordinate
+            .split_once('.')
+            .map_or("", |(type_name, _)| type_name);
+        self.0
+            .get(coordinate)
+            .or_else(|| self.0.get(&format!("{type_name}.*")))
+            .or_else(|| self.0.get("*"))
+    }
+}
+
+// Callables keyed by object type name that turn resolver results of that type into the value
+// the type's fields resolve from, e.g. calling `.to_dict()` on ORM models.
+#[derive(Clone)]
+pub(crate) struct Serializers(pub(crate) Arc<HashMap<String, PyObj>>);
+
+// Classes or runtime-checkable protocols standing for the implementors of interfaces, keyed by
+// interface name and tried in order: a result for an interface field that is not a grommet
+// object resolves as the first implementor it is an instance of.
+#[derive(Clone)]
+pub(crate) struct InterfaceBindings(pub(crate) Arc<HashMap<String, Vec<(String, PyObj)>>>);
+
+impl InterfaceBindings {
+    pub(crate) fn implementor(
+        &self,
+        interface: &str,
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<Option<String>> {
+        let Some(implementors) = self.0.get(interface) else {
+            return Ok(None);
+        };
+        for (type_name, class) in implementors {
+            if value.is_instance(&class.bind(value.py()))? {
+                return Ok(Some(type_name.clone()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// Locks taking turns between the calls of `serial` resolvers, one per field coordinate and
+// kept by the schema handle, so requests on its variants and on reloaded builds share them.
+#[derive(Clone, Default)]
+pub(crate) struct SerialLocks(Arc<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>>);
+
+impl SerialLocks {
+    pub(crate) fn get(&self, coordinate: &str) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match locks.get(coordinate) {
+            Some(lock) => lock.clone(),
+            None => locks.entry(coordinate.to_owned()).or_default().clone(),
+        }
+    }
+}
+
+// The most items a list returned by a resolver may hold, when the schema limits it.
+#[derive(Clone, Copy)]
+pub(crate) struct MaxListItems(pub(crate) usize);
+
+// How many bytes of JSON the fields of one response may add up to, when the schema limits the
+// response size. Fields count against it as they resolve and convert, so a response stops
+// growing once it passes the limit. Clones share the count.
+#[derive(Clone)]
+pub(crate) struct ResponseBudget {
+    limit: usize,
+    used: Arc<AtomicUsize>,
+}
+
+impl ResponseBudget {
+    pub(crate) fn new(limit: usize) -> Self {
+        ResponseBudget {
+            limit,
+            used: Arc::default(),
+        }
+    }
+
+    pub(crate) fn charge(&self, bytes: usize) -> PyResult<()> {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if used > self.limit {
+            return Err(response_too_large(self.limit));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn exceeded(&self) -> bool {
+        self.used.load(Ordering::Relaxed) > self.limit
+    }
+}
+
+// What converting a resolver's arguments and result depends on besides the values and their
+// declared types, borrowed once from the request: serializers by type name, interface bindings,
+// the node types of typed ids, the list limit, the response budget, where lossy conversions are
+// recorded, and the schema's value settings. New coercion rules take their settings from here
+// rather than from parameters of their own.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ConvertCtx<'a> {
+    pub(crate) serializers: Option<&'a Serializers>,
+    pub(crate) interfaces: Option<&'a InterfaceBindings>,
+    pub(crate) typed_ids: Option<&'a TypedIds>,
+    pub(crate) max_list_items: Option<usize>,
+    pub(crate) response: Option<&'a ResponseBudget>,
+    pub(crate) warnings: Option<&'a CoercionWarnings>,
+    pub(crate) values: ValueSettings,
+}
+
+impl<'a> ConvertCtx<'a> {
+    pub(crate) fn from_request(ctx: &ResolverContext<'a>) -> Self {
+        ConvertCtx {
+            serializers: ctx.data_opt::<Serializers>(),
+            interfaces: ctx.data_opt::<InterfaceBindings>(),
+            typed_ids: ctx.data_opt::<TypedIds>(),
+            max_list_items: ctx.data_opt::<MaxListItems>().map(|limit| limit.0),
+            response: ctx.data_opt::<ResponseBudget>(),
+            warnings: ctx.data_opt::<CoercionWarnings>(),
+            values: ctx.data_opt::<ValueSettings>().copied().unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn charge_response(&self, bytes: usize) -> PyResult<()> {
+        self.response.map_or(Ok(()), |budget| budget.charge(bytes))
+    }
+}
+
+// Relay node fetchers keyed by type name, attached to each request when configured.
+#[derive(Clone)]
+pub(crate) struct NodeResolvers(pub(crate) Arc<HashMap<String, PyObj>>);
+
+// Where data fields read their value from the parent object when the schema configures it: the
+// attribute and/or the item named like the field, tried in order. A parent offering neither
+// resolves to null, or fails the field in strict mode.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum SourceKind {
+    Attr,
+    Item,
+}
+
+impl SourceKind {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            SourceKind::Attr => "attr",
+            SourceKind::Item => "item",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SourceLookup {
+    pub(crate) order: Arc<[SourceKind]>,
+    pub(crate) strict: bool,
+}
+
+// Lossy conversions recorded while executing a request with `coercion_warnings=True`, as
+// `(field path, message)` pairs.
+#[derive(Clone, Default)]
+pub(crate) struct CoercionWarnings(Arc<Mutex<Vec<(String, String)>>>);
+
+impl CoercionWarnings {
+    pub(crate) fn record(&self, path: &str, messages: Vec<String>) {
+        let mut warnings = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        warnings.extend(
+            messages
+                .into_iter()
+                .map(|message| (path.to_string(), message)),
+        );
+    }
+
+    // Moves the warnings recorded so far into the response's `warnings` extension.
+    pub(crate) fn drain_into(&self, response: &mut Response) {
+        let warnings = std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner));
+        if warnings.is_empty() {
+            return;
+        }
+        let warnings = warnings
+            .into_iter()
+            .map(|(path, message)| {
+                Value::Object(
+                    [
+                        (Name::new("path"), Value::String(path)),
+                        (Name::new("message"), Value::String(message)),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )
+            })
+            .collect();
+        response
+            .extensions
+            .insert("warnings".to_string(), Value::List(warnings));
+    }
+}
+
+// Python work done while resolving a request, counted when the schema is in debug mode.
+#[derive(Clone, Copy)]
+pub(crate) enum Stat {
+    ResolverCalls,
+    SourceLookups,
+    GilAcquisitions,
+    Awaits,
+}
+
+impl Stat {
+    const ALL: [Stat; 4] = [
+        Stat::ResolverCalls,
+        Stat::SourceLookups,
+        Stat::GilAcquisitions,
+        Stat::Awaits,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Stat::ResolverCalls => "resolver_calls",
+            Stat::SourceLookups => "source_lookups",
+            Stat::GilAcquisitions => "gil_acquisitions",
+            Stat::Awaits => "awaits",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct RequestStats(Arc<[AtomicUsize; 4]>);
+
+impl RequestStats {
+    pub(crate) fn count(&self, stat: Stat) {
+        self.0[stat as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Moves the counts so far into the response's `stats` extension.
+    pub(crate) fn drain_into(&self, response: &mut Response) {
+        let stats = Stat::ALL
+            .into_iter()
+            .map(|stat| {
+                let count = self.0[stat as usize].swap(0, Ordering::Relaxed);
+                (Name::new(stat.name()), Value::from(count))
+            })
+            .collect();
+        response
+            .extensions
+            .insert("stats".to_string(), Value::Object(stats));
+    }
+}
+
+// What a subscription does with an event that fails: end the stream with the error, drop the
+// event, or deliver the error with a null value. The engine ends a stream at the first event
+// whose response has errors, so emitted errors bypass it through `SubscriptionErrors`.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum ErrorPolicy {
+    Stop,
+    Skip,
+    Emit,
+}
+
+impl ErrorPolicy {
+    pub(crate) fn parse(policy: &str) -> PyResult<Self> {
+        match policy {
+            "stop" => Ok(ErrorPolicy::Stop),
+            "skip" => Ok(ErrorPolicy::Skip),
+            "emit" => Ok(ErrorPolicy::Emit),
+            _ => Err(py_value_error(format!(
+                "Unknown subscription error policy '{policy}'."
+            ))),
+        }
+    }
+}
+
+// Errors raised while resolving subscription events. The engine collects nested field errors on
+// the request environment without moving them into the event's response, and without the field
+// path, so resolvers record a located copy and each event swaps those in for the bare ones.
+#[derive(Clone, Default)]
+pub(crate) struct SubscriptionErrors(Arc<Mutex<SubscriptionErrorState>>);
+
+#[derive(Default)]
+struct SubscriptionErrorState {
+    env: Option<QueryEnv>,
+    located: Vec<ServerError>,
+    emitted: Vec<ServerError>,
+}
+
+impl SubscriptionErrors {
+    pub(crate) fn attach(&self, env: &QueryEnv) {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.env.is_none() {
+            state.env = Some(env.clone());
+        }
+    }
+
+    pub(crate) fn record(&self, error: ServerError) {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        state.located.push(error);
+    }
+
+    // Keeps an event's error that the engine never sees, for `ErrorPolicy::Emit`.
+    pub(crate) fn emit(&self, error: ServerError) {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        state.emitted.push(error);
+    }
+
+    pub(crate) fn drain_into(&self, response: &mut Response) {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut located = std::mem::take(&mut state.located);
+        let emitted = std::mem::take(&mut state.emitted);
+        let mut errors = std::mem::take(&mut response.errors);
+        if let Some(env) = &state.env {
+            errors.append(&mut env.errors.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+        response.errors = errors
+            .into_iter()
+            .map(|error| {
+                let copy = located.iter().position(|candidate| {
+                    error.path.is_empty()
+                        && candidate.message == error.message
+                        && candidate.locations == error.locations
+                });
+                match copy {
+                    Some(index) => located.swap_remove(index),
+                    None => error,
+                }
+            })
+            .chain(emitted)
+            .collect();
+    }
+}
+
+// How often a failing async resolver is called again. Waits start at `backoff_ms` and double
+// with each retry. Only exceptions whose class or one of its bases is named in `on` are
+// retried, or any exception when `on` is empty.
+pub(crate) struct RetryPolicy {
+    pub(crate) retries: u32,
+    pub(crate) backoff_ms: u64,
+    pub(crate) on: Vec<String>,
+}
+
+impl RetryPolicy {
+    // Whether the call that failed with `err` as the given attempt, counted from 1, is retried.
+    pub(crate) fn retries(&self, py: Python<'_>, err: &PyErr, attempt: u32) -> PyResult<bool> {
+        if attempt > self.retries || !err.is_instance_of::<PyException>(py) {
+            return Ok(false);
+        }
+        if self.on.is_empty() {
+            return Ok(true);
+        }
+        for base in err.get_type(py).getattr("__mro__")?.try_iter()? {
+            let name: String = base?.getattr("__name__")?.extract()?;
+            if self.on.contains(&name) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Seconds to wait before retrying the given failed attempt.
+    pub(crate) fn backoff_seconds(&self, attempt: u32) -> f64 {
+        self.backoff_ms as f64 * 2f64.powi(attempt as i32 - 1) / 1000.0
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct ResolverEntry {
     pub(crate) func: PyObj,
     pub(crate) needs_context: bool,
     pub(crate) is_async_gen: bool,
+    pub(crate) batched: bool,
+    pub(crate) source_lookup: bool,
+    pub(crate) needs_info: bool,
+    pub(crate) retry: Option<Arc<RetryPolicy>>,
+    // Names of the arguments typed `ID` or lists of it.
+    pub(crate) id_arguments: Vec<String>,
+    // Whether the resolver's calls take turns, through the schema's `SerialLocks`.
+    pub(crate) serial: bool,
 }
 
 #[derive(Clone)]
 pub(crate) struct FieldContext {
+    // The field's coordinate, e.g. `Query.users`.
+    pub(crate) coordinate: String,
     pub(crate) resolver: Option<ResolverEntry>,
     pub(crate) output_type: TypeRef,
+    pub(crate) argument_checks: Option<Arc<ArgumentChecks>>,
 }
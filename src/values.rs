@@ -1,12 +1,22 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 use async_graphql::dynamic::{FieldValue, TypeRef};
-use async_graphql::{Name, Value};
+use async_graphql::{Name, ServerError, Value};
 use pyo3::IntoPyObject;
 use pyo3::prelude::*;
 use pyo3::sync::PyOnceLock;
-use pyo3::types::{PyAnyMethods, PyBytes, PyDict, PyList};
+use pyo3::types::{PyAnyMethods, PyBool, PyBytes, PyDict, PyIterator, PyList, PyString, PyTuple};
 
-use crate::errors::{expected_list_value, py_value_error, unsupported_value_type};
-use crate::types::PyObj;
+use crate::errors::{
+    assign_error_codes, cyclic_value, expected_list_value, invalid_string, lossy_conversion,
+    py_value_error, status_hint, too_many_list_items, unknown_string_policy,
+    unsupported_value_type, value_too_deep,
+};
+use crate::types::{ConvertCtx, PyObj};
 
 #[pyclass(module = "grommet._core", name = "OperationResult")]
 pub(crate) struct OperationResult {
@@ -16,6 +26,9 @@ pub(crate) struct OperationResult {
     errors: Py<PyAny>,
     #[pyo3(get)]
     extensions: Py<PyAny>,
+    // The HTTP status to answer the response with, see `status_hint` of the errors module.
+    #[pyo3(get)]
+    status_hint: u16,
 }
 
 #[pymethods]
@@ -37,15 +50,125 @@ impl OperationResult {
             "data" => Ok(self.data.clone_ref(py)),
             "errors" => Ok(self.errors.clone_ref(py)),
             "extensions" => Ok(self.extensions.clone_ref(py)),
+            "status_hint" => Ok(self.status_hint.into_pyobject(py)?.into_any().unbind()),
             _ => Err(pyo3::exceptions::PyKeyError::new_err(key.to_string())),
         }
     }
 }
 
+// An event of a subscription started with `envelope=True`, so transports can tell payload
+// kinds apart by attribute or `match` instead of inspecting dicts. `cursor` is the event's
+// replay cursor, if its resolver tags events. The core never emits keepalives itself;
+// `keepalive()` builds one for transports that interleave their own with the stream's events.
+#[pyclass(module = "grommet._core", name = "SubscriptionPayload", frozen)]
+pub(crate) struct SubscriptionPayload {
+    #[pyo3(get)]
+    data: Py<PyAny>,
+    #[pyo3(get)]
+    errors: Py<PyAny>,
+    #[pyo3(get)]
+    extensions: Py<PyAny>,
+    #[pyo3(get)]
+    is_keepalive: bool,
+    #[pyo3(get)]
+    cursor: Option<String>,
+}
+
+#[pymethods]
+impl SubscriptionPayload {
+    #[classattr]
+    fn __match_args__() -> (&'static str, &'static str, &'static str, &'static str) {
+        ("data", "errors", "is_keepalive", "cursor")
+    }
+
+    #[staticmethod]
+    fn keepalive(py: Python<'_>) -> Self {
+        SubscriptionPayload {
+            data: py.None(),
+            errors: py.None(),
+            extensions: py.None(),
+            is_keepalive: true,
+            cursor: None,
+        }
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        if self.is_keepalive {
+            return Ok("SubscriptionPayload(keepalive)".to_owned());
+        }
+        Ok(format!(
+            "SubscriptionPayload(data={}, errors={}, cursor={:?})",
+            self.data.bind(py).repr()?,
+            self.errors.bind(py).repr()?,
+            self.cursor,
+        ))
+    }
+}
+
+// The graphql-core shaped result schemas built with `execution_result=True` return, so view
+// code written against graphql-core ports unchanged: it unpacks as `(data, errors)`, and
+// `formatted` is the response as a dict, with the keys of empty parts left out.
+#[pyclass(module = "grommet._core", name = "ExecutionResult", frozen)]
+pub(crate) struct ExecutionResult {
+    #[pyo3(get)]
+    data: Py<PyAny>,
+    #[pyo3(get)]
+    errors: Py<PyAny>,
+    #[pyo3(get)]
+    extensions: Py<PyAny>,
+    // The HTTP status to answer the response with, see `status_hint` of the errors module.
+    #[pyo3(get)]
+    status_hint: u16,
+}
+
+#[pymethods]
+impl ExecutionResult {
+    // Whether the operation ran without errors.
+    #[getter]
+    fn ok(&self, py: Python<'_>) -> bool {
+        self.errors.is_none(py)
+    }
+
+    #[getter]
+    fn formatted<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let formatted = PyDict::new(py);
+        formatted.set_item("data", &self.data)?;
+        if !self.errors.is_none(py) {
+            formatted.set_item("errors", &self.errors)?;
+        }
+        if !self.extensions.is_none(py) {
+            formatted.set_item("extensions", &self.extensions)?;
+        }
+        Ok(formatted)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyTuple::new(py, [&self.data, &self.errors])?.try_iter()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "ExecutionResult(data={}, errors={})",
+            self.data.bind(py).repr()?,
+            self.errors.bind(py).repr()?,
+        ))
+    }
+}
+
+// How responses are handed to Python: errors as `GraphQLError`s rather than dicts, results as
+// `ExecutionResult`s rather than `OperationResult`s, and values converted with the schema's
+// settings.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ResultFormat {
+    pub(crate) error_objects: bool,
+    pub(crate) execution_result: bool,
+    pub(crate) values: ValueSettings,
+}
+
 pub(crate) fn pyobj_to_value(value: &PyObj) -> PyResult<Value> {
     Python::attach(|py| {
         let bound = value.bind(py);
-        py_to_value(py, &bound)
+        py_to_value(py, &bound, ValueSettings::default())
     })
 }
 
@@ -57,11 +180,32 @@ fn dataclasses_asdict(py: Python<'_>) -> PyResult<Py<PyAny>> {
     Ok(asdict.clone_ref(py))
 }
 
+fn enum_type(py: Python<'_>) -> PyResult<Py<PyAny>> {
+    static ENUM_TYPE: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+    let enum_type = ENUM_TYPE.get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+        Ok(py.import("enum")?.getattr("Enum")?.unbind())
+    })?;
+    Ok(enum_type.clone_ref(py))
+}
+
+// Values that convert as objects: grommet input instances, attrs instances (their declared
+// attributes, shallowly), and `NamedTuple` instances.
 fn input_object_as_dict<'py>(
     py: Python<'py>,
     value: &Bound<'py, PyAny>,
 ) -> PyResult<Option<Bound<'py, PyAny>>> {
     let ty = value.get_type();
+    if let Some(attributes) = ty.getattr_opt("__attrs_attrs__")? {
+        let dict = PyDict::new(py);
+        for attribute in attributes.try_iter()? {
+            let name = attribute?.getattr("name")?.cast_into::<PyString>()?;
+            dict.set_item(&name, value.getattr(&name)?)?;
+        }
+        return Ok(Some(dict.into_any()));
+    }
+    if value.is_instance_of::<PyTuple>() && ty.hasattr("_fields")? {
+        return Ok(Some(value.call_method0("_asdict")?));
+    }
     if !ty.hasattr("__grommet_meta__")? {
         return Ok(None);
     }
@@ -77,6 +221,30 @@ fn input_object_as_dict<'py>(
     Ok(Some(dict_obj))
 }
 
+// Pydantic models, v1 or v2, convert as the dict they dump to.
+fn pydantic_model_dump<'py>(value: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let mut is_model = false;
+    for base in value.get_type().mro().iter() {
+        if base.getattr("__name__")?.extract::<String>()? != "BaseModel" {
+            continue;
+        }
+        let module: String = base.getattr("__module__")?.extract()?;
+        if module.split('.').next() == Some("pydantic") {
+            is_model = true;
+            break;
+        }
+    }
+    if !is_model {
+        return Ok(None);
+    }
+    let dump = if value.hasattr("model_dump")? {
+        "model_dump"
+    } else {
+        "dict"
+    };
+    Ok(Some(value.call_method0(dump)?))
+}
+
 fn meta_kind_value(meta: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
     if !meta.hasattr("kind")? {
         return Ok(None);
@@ -110,46 +278,172 @@ fn is_builtin_scalar(type_name: &str) -> bool {
     matches!(type_name, "Boolean" | "Int" | "Float" | "String" | "ID")
 }
 
-fn extract_scalar_value(value: &Bound<'_, PyAny>) -> Option<Value> {
+fn extract_scalar_value(
+    value: &Bound<'_, PyAny>,
+    strings: StringPolicy,
+) -> PyResult<Option<Value>> {
     if value.is_none() {
-        return Some(Value::Null);
+        return Ok(Some(Value::Null));
     }
     if let Ok(boolean) = value.extract::<bool>() {
-        return Some(Value::Boolean(boolean));
+        return Ok(Some(Value::Boolean(boolean)));
     }
     if let Ok(integer) = value.extract::<i64>() {
-        return Some(Value::from(integer));
+        return Ok(Some(Value::from(integer)));
     }
     if let Ok(float) = value.extract::<f64>() {
-        return Some(Value::from(float));
+        return Ok(Some(Value::from(float)));
     }
-    if let Ok(string) = value.extract::<String>() {
-        return Some(Value::String(string));
+    if let Some(string) = strings.extract(value)? {
+        return Ok(Some(Value::String(string)));
     }
-    None
+    Ok(None)
 }
 
+// How strings with NUL characters or lone surrogates, which JSON consumers commonly reject,
+// convert: "keep" leaves NUL characters in place, "error" fails the conversion, and "replace"
+// substitutes U+FFFD for each offending character. Lone surrogates cannot be kept.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) enum StringPolicy {
+    #[default]
+    Keep,
+    Error,
+    Replace,
+}
+
+impl StringPolicy {
+    pub(crate) fn from_name(name: &str) -> PyResult<Self> {
+        match name {
+            "keep" => Ok(StringPolicy::Keep),
+            "error" => Ok(StringPolicy::Error),
+            "replace" => Ok(StringPolicy::Replace),
+            _ => Err(unknown_string_policy(name)),
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            StringPolicy::Keep => "keep",
+            StringPolicy::Error => "error",
+            StringPolicy::Replace => "replace",
+        }
+    }
+
+    // A Python string checked against the policy; `None` when the value is not a string.
+    fn extract(self, value: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+        let Ok(string) = value.cast::<PyString>() else {
+            return Ok(None);
+        };
+        let string = match string.to_str() {
+            Ok(string) => string.to_owned(),
+            Err(_) if self == StringPolicy::Replace => replace_surrogates(string)?,
+            Err(_) => return Err(invalid_string("a lone surrogate")),
+        };
+        self.check(string).map(Some)
+    }
+
+    fn check(self, string: String) -> PyResult<String> {
+        if self == StringPolicy::Keep || !string.contains('\0') {
+            return Ok(string);
+        }
+        if self == StringPolicy::Replace {
+            return Ok(string.replace('\0', "\u{fffd}"));
+        }
+        Err(invalid_string("a NUL character"))
+    }
+
+    // Applies the policy to the strings of a value converted without `extract`.
+    #[cfg(feature = "orjson")]
+    fn check_value(self, value: &mut Value) -> PyResult<()> {
+        match value {
+            Value::String(string) if string.contains('\0') => {
+                *string = self.check(std::mem::take(string))?;
+            }
+            Value::List(items) => {
+                for item in items {
+                    self.check_value(item)?;
+                }
+            }
+            Value::Object(map) => {
+                for item in map.values_mut() {
+                    self.check_value(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// One U+FFFD per lone surrogate, going through UTF-16 where Python keeps surrogates intact.
+fn replace_surrogates(string: &Bound<'_, PyString>) -> PyResult<String> {
+    let encoded = string.call_method1("encode", ("utf-16-le", "surrogatepass"))?;
+    let units = encoded
+        .cast::<PyBytes>()?
+        .as_bytes()
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+    Ok(char::decode_utf16(units)
+        .map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect())
+}
+
+// Lossy conversions that still succeed are described in `warnings`. Lists longer than the
+// list limit, or that take the response past its size limit, fail before any of their items
+// are converted.
 pub(crate) fn py_to_field_value_for_type(
     py: Python<'_>,
     value: &Bound<'_, PyAny>,
     output_type: &TypeRef,
+    convert: &ConvertCtx<'_>,
+    warnings: &mut Vec<String>,
 ) -> PyResult<FieldValue<'static>> {
     if value.is_none() {
+        convert.charge_response(payload_bytes(&Value::Null))?;
         return Ok(FieldValue::value(Value::Null));
     }
     match output_type {
-        TypeRef::NonNull(inner) => py_to_field_value_for_type(py, value, inner),
-        TypeRef::List(inner) => convert_sequence_to_field_values(py, value, inner),
+        TypeRef::NonNull(inner) => py_to_field_value_for_type(py, value, inner, convert, warnings),
+        TypeRef::List(inner) => {
+            convert_sequence_to_field_values(py, value, inner, convert, warnings)
+        }
         TypeRef::Named(name) => {
             let type_name: &str = name;
-            convert_named_field_value(value, type_name)
+            let field_value = match convert
+                .serializers
+                .and_then(|serializers| serializers.0.get(type_name))
+            {
+                Some(serializer) => {
+                    let serialized = serializer.bind(py).call1((value,))?;
+                    convert_named_field_value(&serialized, type_name, convert, warnings)
+                }
+                None => convert_named_field_value(value, type_name, convert, warnings),
+            }?;
+            // Objects count their braces here and their fields as those resolve.
+            convert.charge_response(field_value.as_value().map_or(2, payload_bytes))?;
+            Ok(field_value)
         }
     }
 }
 
+// Booleans pass as numbers, and integers beyond 2^53 lose precision as floats.
+fn is_lossy_number(value: &Bound<'_, PyAny>, type_name: &str) -> bool {
+    if value.is_instance_of::<PyBool>() {
+        return true;
+    }
+    type_name == "Float"
+        && value
+            .extract::<i64>()
+            .is_ok_and(|integer| integer.unsigned_abs() > MAX_SAFE_INTEGER)
+}
+
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
 fn convert_named_field_value(
     value: &Bound<'_, PyAny>,
     type_name: &str,
+    convert: &ConvertCtx<'_>,
+    warnings: &mut Vec<String>,
 ) -> PyResult<FieldValue<'static>> {
     if value.is_none() {
         return Ok(FieldValue::value(Value::Null));
@@ -164,36 +458,47 @@ fn convert_named_field_value(
         }
         return Ok(field_value.with_type(runtime_type_name));
     }
+    if let Some(interfaces) = convert.interfaces
+        && let Some(implementor) = interfaces.implementor(type_name, value)?
+    {
+        let field_value = FieldValue::owned_any(PyObj::new(value.clone().unbind()));
+        return Ok(field_value.with_type(implementor));
+    }
+
+    if matches!(type_name, "Int" | "Float") && is_lossy_number(value, type_name) {
+        warnings.push(lossy_conversion(value, type_name));
+    }
 
+    let strings = convert.values.strings;
     match type_name {
         "Boolean" => Ok(FieldValue::value(Value::Boolean(
             value
                 .extract::<bool>()
-                .map_err(|_| unsupported_value_type())?,
+                .map_err(|_| unsupported_value_type(value, Some(type_name)))?,
         ))),
         "Int" => Ok(FieldValue::value(Value::from(
             value
                 .extract::<i64>()
-                .map_err(|_| unsupported_value_type())?,
+                .map_err(|_| unsupported_value_type(value, Some(type_name)))?,
         ))),
         "Float" => Ok(FieldValue::value(Value::from(
             value
                 .extract::<f64>()
-                .map_err(|_| unsupported_value_type())?,
+                .map_err(|_| unsupported_value_type(value, Some(type_name)))?,
         ))),
         "String" => Ok(FieldValue::value(Value::String(
-            value
-                .extract::<String>()
-                .map_err(|_| unsupported_value_type())?,
+            strings
+                .extract(value)?
+                .ok_or_else(|| unsupported_value_type(value, Some(type_name)))?,
         ))),
         "ID" => {
-            if let Ok(string) = value.extract::<String>() {
+            if let Some(string) = strings.extract(value)? {
                 return Ok(FieldValue::value(Value::String(string)));
             }
             if let Ok(integer) = value.extract::<i64>() {
                 return Ok(FieldValue::value(Value::String(integer.to_string())));
             }
-            Err(unsupported_value_type())
+            Err(unsupported_value_type(value, Some(type_name)))
         }
         _ => Ok(FieldValue::owned_any(PyObj::new(value.clone().unbind()))),
     }
@@ -201,11 +506,13 @@ fn convert_named_field_value(
 
 fn try_collect_sequence<T>(
     value: &Bound<'_, PyAny>,
+    settings: ValueSettings,
     mut convert: impl FnMut(&Bound<'_, PyAny>) -> PyResult<T>,
 ) -> PyResult<Option<Vec<T>>> {
     if let Ok(seq) = value.cast::<PyList>() {
         let mut items = Vec::with_capacity(seq.len());
-        for item in seq.iter() {
+        for (index, item) in seq.iter().enumerate() {
+            settings.yield_gil(value.py(), index)?;
             items.push(convert(&item)?);
         }
         return Ok(Some(items));
@@ -215,54 +522,301 @@ fn try_collect_sequence<T>(
 
 fn collect_sequence<T>(
     value: &Bound<'_, PyAny>,
+    settings: ValueSettings,
     convert: impl FnMut(&Bound<'_, PyAny>) -> PyResult<T>,
 ) -> PyResult<Vec<T>> {
-    try_collect_sequence(value, convert)?.ok_or_else(expected_list_value)
+    try_collect_sequence(value, settings, convert)?.ok_or_else(expected_list_value)
 }
 
 fn convert_sequence_to_field_values(
     py: Python<'_>,
     value: &Bound<'_, PyAny>,
     inner_type: &TypeRef,
+    convert: &ConvertCtx<'_>,
+    warnings: &mut Vec<String>,
 ) -> PyResult<FieldValue<'static>> {
-    let items = collect_sequence(value, |item| {
-        py_to_field_value_for_type(py, item, inner_type)
+    if let Ok(list) = value.cast::<PyList>() {
+        if let Some(limit) = convert.max_list_items
+            && list.len() > limit
+        {
+            return Err(too_many_list_items(list.len(), limit));
+        }
+        convert.charge_response(2 + list.len().saturating_sub(1))?;
+    }
+    let items = collect_sequence(value, convert.values, |item| {
+        py_to_field_value_for_type(py, item, inner_type, convert, warnings)
     })?;
     Ok(FieldValue::list(items))
 }
 
-pub(crate) fn py_to_value(py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<Value> {
+// Approximate JSON-encoded size of a variables payload or response data.
+pub(crate) fn payload_bytes(value: &Value) -> usize {
+    match value {
+        Value::Null => 4,
+        Value::Boolean(flag) => {
+            if *flag {
+                4
+            } else {
+                5
+            }
+        }
+        Value::Number(number) => number.to_string().len(),
+        Value::String(text) => text.len() + 2,
+        Value::Enum(name) => name.len() + 2,
+        Value::Binary(bytes) => bytes.len(),
+        Value::List(items) => {
+            2 + items.len().saturating_sub(1) + items.iter().map(payload_bytes).sum::<usize>()
+        }
+        Value::Object(map) => {
+            2 + map.len().saturating_sub(1)
+                + map
+                    .iter()
+                    .map(|(key, value)| key.len() + 3 + payload_bytes(value))
+                    .sum::<usize>()
+        }
+    }
+}
+
+// How deeply lists and objects may nest in converted values, so hostile payloads fail cleanly
+// instead of exhausting the stack.
+const DEFAULT_MAX_VALUE_DEPTH: usize = 256;
+
+// Long conversions let other Python threads run every this many values, and stop early when a
+// signal such as Ctrl-C is pending.
+const DEFAULT_GIL_YIELD_INTERVAL: usize = 10_000;
+
+// How a schema converts values between Python and GraphQL, read once per request; see the
+// schema's `set_max_value_depth`, `set_gil_yield_interval` and `set_string_policy`. Values
+// converted outside of a schema use the defaults.
+#[derive(Clone, Copy)]
+pub(crate) struct ValueSettings {
+    pub(crate) max_depth: usize,
+    // How many values a conversion handles between releasing the GIL; 0 never releases it.
+    pub(crate) gil_yield_interval: usize,
+    pub(crate) strings: StringPolicy,
+}
+
+impl Default for ValueSettings {
+    fn default() -> Self {
+        ValueSettings {
+            max_depth: DEFAULT_MAX_VALUE_DEPTH,
+            gil_yield_interval: DEFAULT_GIL_YIELD_INTERVAL,
+            strings: StringPolicy::default(),
+        }
+    }
+}
+
+impl ValueSettings {
+    // Called with the number of values a conversion has handled so far.
+    fn yield_gil(self, py: Python<'_>, converted: usize) -> PyResult<()> {
+        let interval = self.gil_yield_interval;
+        if interval == 0 || converted == 0 || !converted.is_multiple_of(interval) {
+            return Ok(());
+        }
+        py.check_signals()?;
+        py.detach(|| ());
+        Ok(())
+    }
+}
+
+// A list or object whose items are still being converted.
+enum Container<'py> {
+    List {
+        source: Bound<'py, PyList>,
+        items: Vec<Value>,
+    },
+    Object {
+        source: std::vec::IntoIter<(Name, Bound<'py, PyAny>)>,
+        key: Option<Name>,
+        map: indexmap::IndexMap<Name, Value>,
+    },
+}
+
+impl<'py> Container<'py> {
+    fn next_item(&mut self) -> Option<Bound<'py, PyAny>> {
+        match self {
+            Container::List { source, items } => source.get_item(items.len()).ok(),
+            Container::Object { source, key, .. } => {
+                let (name, item) = source.next()?;
+                *key = Some(name);
+                Some(item)
+            }
+        }
+    }
+
+    // Path segment of the item being converted.
+    fn position(&self) -> String {
+        match self {
+            Container::List { items, .. } => items.len().to_string(),
+            Container::Object { key, .. } => key.as_ref().map(Name::to_string).unwrap_or_default(),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        match self {
+            Container::List { items, .. } => items.push(value),
+            Container::Object { key, map, .. } => {
+                map.insert(key.take().expect("object item without key"), value);
+            }
+        }
+    }
+
+    fn finish(self) -> Value {
+        match self {
+            Container::List { items, .. } => Value::List(items),
+            Container::Object { map, .. } => Value::Object(map),
+        }
+    }
+}
+
+enum Converted<'py> {
+    Value(Value),
+    Container(Container<'py>),
+}
+
+// Converts a value that holds no lists or objects, or opens the list or object it is.
+fn convert_one<'py>(
+    py: Python<'py>,
+    value: &Bound<'py, PyAny>,
+    strings: StringPolicy,
+) -> PyResult<Converted<'py>> {
     if let Some(dict_obj) = input_object_as_dict(py, value)? {
-        return py_to_value(py, &dict_obj);
+        return convert_one(py, &dict_obj, strings);
     }
 
-    if let Some(scalar) = extract_scalar_value(value) {
-        return Ok(scalar);
+    if let Some(scalar) = extract_scalar_value(value, strings)? {
+        return Ok(Converted::Value(scalar));
     }
 
     if let Ok(bytes) = value.cast::<PyBytes>() {
-        return Ok(Value::Binary(bytes.as_bytes().to_vec().into()));
+        return Ok(Converted::Value(Value::Binary(
+            bytes.as_bytes().to_vec().into(),
+        )));
     }
 
-    if let Some(items) = try_collect_sequence(value, |item| py_to_value(py, item))? {
-        return Ok(Value::List(items));
+    if let Ok(list) = value.cast::<PyList>() {
+        return Ok(Converted::Container(Container::List {
+            items: Vec::with_capacity(list.len()),
+            source: list.clone(),
+        }));
     }
 
     if let Ok(dict) = value.cast::<PyDict>() {
-        let mut map = indexmap::IndexMap::new();
+        let mut entries = Vec::with_capacity(dict.len());
         for (key, value) in dict.iter() {
-            let key: String = key.extract()?;
-            map.insert(Name::new(key), py_to_value(py, &value)?);
+            entries.push((Name::new(key.extract::<String>()?), value));
+        }
+        return Ok(Converted::Container(Container::Object {
+            map: indexmap::IndexMap::with_capacity(entries.len()),
+            source: entries.into_iter(),
+            key: None,
+        }));
+    }
+
+    if let Some(dumped) = pydantic_model_dump(value)? {
+        return convert_one(py, &dumped, strings);
+    }
+    // Enum members, such as argument defaults, convert as their values. Members of `str` and
+    // `int` enums already converted as scalars above.
+    if value.is_instance(enum_type(py)?.bind(py))? {
+        return convert_one(py, &value.getattr("value")?, strings);
+    }
+    Err(unsupported_value_type(value, None))
+}
+
+// Walks nested lists and objects with an explicit stack of open containers rather than by
+// recursion, failing once they nest deeper than the configured maximum or contain
+// themselves. Containers are keyed by the identity of the Python object they came from.
+pub(crate) fn py_to_value(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    settings: ValueSettings,
+) -> PyResult<Value> {
+    let max_depth = settings.max_depth;
+    let mut open: Vec<(usize, Container<'_>)> = Vec::new();
+    let mut item_id = value.as_ptr() as usize;
+    let mut count = 0;
+    let mut converted = convert_one(py, value, settings.strings)?;
+    loop {
+        match converted {
+            Converted::Container(container) => {
+                if open.iter().any(|(id, _)| *id == item_id) {
+                    let path: Vec<_> = open.iter().map(|(_, open)| open.position()).collect();
+                    return Err(cyclic_value(&path.join(".")));
+                }
+                if open.len() == max_depth {
+                    return Err(value_too_deep(max_depth));
+                }
+                open.push((item_id, container));
+            }
+            Converted::Value(value) => match open.last_mut() {
+                Some((_, parent)) => parent.push(value),
+                None => return Ok(value),
+            },
         }
-        return Ok(Value::Object(map));
+        converted = loop {
+            let (_, top) = open.last_mut().expect("an open container");
+            if let Some(item) = top.next_item() {
+                count += 1;
+                settings.yield_gil(py, count)?;
+                item_id = item.as_ptr() as usize;
+                break convert_one(py, &item, settings.strings)?;
+            }
+            let (_, finished) = open.pop().expect("an open container");
+            match open.last_mut() {
+                Some((_, parent)) => parent.push(finished.finish()),
+                None => return Ok(finished.finish()),
+            }
+        };
     }
-    Err(unsupported_value_type())
+}
+
+// Converts a whole payload (request variables), through orjson when built with the `orjson`
+// feature and the payload is large.
+pub(crate) fn payload_to_value(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    settings: ValueSettings,
+) -> PyResult<Value> {
+    #[cfg(feature = "orjson")]
+    if let Some(mut value) = crate::fastpath::py_to_value_fast(py, value, settings.max_depth) {
+        settings.strings.check_value(&mut value)?;
+        return Ok(value);
+    }
+    py_to_value(py, value, settings)
+}
+
+// Converts whole response data, through orjson when built with the `orjson` feature and the
+// data is large.
+pub(crate) fn payload_to_py<'py>(
+    py: Python<'py>,
+    value: &Value,
+    settings: ValueSettings,
+) -> PyResult<Bound<'py, PyAny>> {
+    #[cfg(feature = "orjson")]
+    if let Some(decoded) = crate::fastpath::value_to_py_fast(py, value, settings.max_depth)? {
+        return Ok(decoded);
+    }
+    value_to_py_bound(py, value, settings)
 }
 
 pub(crate) fn value_to_py_bound<'py>(
     py: Python<'py>,
     value: &Value,
+    settings: ValueSettings,
 ) -> PyResult<Bound<'py, PyAny>> {
+    value_to_py_at_depth(py, value, 0, settings)
+}
+
+fn value_to_py_at_depth<'py>(
+    py: Python<'py>,
+    value: &Value,
+    depth: usize,
+    settings: ValueSettings,
+) -> PyResult<Bound<'py, PyAny>> {
+    if matches!(value, Value::List(_) | Value::Object(_)) && depth == settings.max_depth {
+        return Err(value_too_deep(settings.max_depth));
+    }
     match value {
         Value::Null => Ok(py.None().into_bound(py)),
         Value::Boolean(b) => Ok(b.into_pyobject(py)?.to_owned().into_any()),
@@ -281,15 +835,19 @@ pub(crate) fn value_to_py_bound<'py>(
         Value::Enum(s) => Ok(s.as_str().into_pyobject(py)?.into_any()),
         Value::List(items) => {
             let list = PyList::empty(py);
-            for item in items {
-                list.append(value_to_py_bound(py, item)?)?;
+            for (index, item) in items.iter().enumerate() {
+                settings.yield_gil(py, index)?;
+                list.append(value_to_py_at_depth(py, item, depth + 1, settings)?)?;
             }
             Ok(list.into_any())
         }
         Value::Object(map) => {
             let dict = PyDict::new(py);
             for (key, value) in map {
-                dict.set_item(key.as_str(), value_to_py_bound(py, value)?)?;
+                dict.set_item(
+                    key.as_str(),
+                    value_to_py_at_depth(py, value, depth + 1, settings)?,
+                )?;
             }
             Ok(dict.into_any())
         }
@@ -297,76 +855,324 @@ pub(crate) fn value_to_py_bound<'py>(
     }
 }
 
-#[cfg(test)]
-pub(crate) fn value_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
-    Ok(value_to_py_bound(py, value)?.unbind())
+// A list or object already placed in the converted tree, with the items left to convert into
+// it.
+enum OpenContainer<'a> {
+    List(Py<PyList>, std::slice::Iter<'a, Value>),
+    Object(Py<PyDict>, indexmap::map::Iter<'a, Name, Value>),
 }
 
-pub(crate) fn response_to_py<'py>(
-    py: Python<'py>,
-    response: async_graphql::Response,
-) -> PyResult<Py<PyAny>> {
-    let data = value_to_py_bound(py, &response.data)?.unbind();
+// Converts a value tree to Python a bounded number of values at a time, resuming where the
+// previous step stopped, so that converting a huge value can be spread over several GIL
+// acquisitions.
+struct ChunkedConversion<'a> {
+    pending: Option<&'a Value>,
+    root: Option<Py<PyAny>>,
+    open: Vec<OpenContainer<'a>>,
+    settings: ValueSettings,
+}
 
-    let extensions_dict = PyDict::new(py);
-    for (key, value) in response.extensions {
-        extensions_dict.set_item(key, value_to_py_bound(py, &value)?)?;
+impl<'a> ChunkedConversion<'a> {
+    fn new(value: &'a Value, settings: ValueSettings) -> Self {
+        ChunkedConversion {
+            pending: Some(value),
+            root: None,
+            open: Vec::new(),
+            settings,
+        }
     }
-    let extensions = if extensions_dict.is_empty() {
-        py.None()
-    } else {
-        extensions_dict.into_any().unbind()
-    };
 
-    let errors = if response.errors.is_empty() {
-        py.None()
-    } else {
-        let errors_list = PyList::empty(py);
-        for err in response.errors {
-            let err_dict = PyDict::new(py);
-            err_dict.set_item("message", err.message)?;
-            if !err.locations.is_empty() {
-                let locs = PyList::empty(py);
-                for loc in err.locations {
-                    let loc_dict = PyDict::new(py);
-                    loc_dict.set_item("line", loc.line)?;
-                    loc_dict.set_item("column", loc.column)?;
-                    locs.append(loc_dict)?;
-                }
-                err_dict.set_item("locations", locs)?;
+    // Converts up to `budget` more values, returning the converted tree once it is complete.
+    fn step(&mut self, py: Python<'_>, budget: NonZeroUsize) -> PyResult<Option<Py<PyAny>>> {
+        if let Some(value) = self.pending.take() {
+            self.root = Some(self.place(py, value)?.unbind());
+        }
+        let mut converted = 0;
+        while let Some(container) = self.open.last_mut() {
+            if converted == budget.get() {
+                return Ok(None);
             }
-            let path_list = PyList::empty(py);
-            if !err.path.is_empty() {
-                for seg in err.path {
-                    match seg {
-                        async_graphql::PathSegment::Field(name) => {
-                            path_list.append(name)?;
-                        }
-                        async_graphql::PathSegment::Index(index) => {
-                            path_list.append(index)?;
-                        }
-                    }
+            match container {
+                OpenContainer::List(list, items) => {
+                    let Some(item) = items.next() else {
+                        self.open.pop();
+                        continue;
+                    };
+                    let list = list.clone_ref(py);
+                    list.bind(py).append(self.place(py, item)?)?;
+                }
+                OpenContainer::Object(dict, entries) => {
+                    let Some((key, item)) = entries.next() else {
+                        self.open.pop();
+                        continue;
+                    };
+                    let dict = dict.clone_ref(py);
+                    dict.bind(py)
+                        .set_item(key.as_str(), self.place(py, item)?)?;
                 }
             }
-            if path_list.len() > 0 {
-                err_dict.set_item("path", path_list)?;
+            converted += 1;
+        }
+        Ok(self.root.take())
+    }
+
+    // The Python value for `value`: leaves converted, and lists and objects created empty and
+    // left open for their items.
+    fn place<'py>(&mut self, py: Python<'py>, value: &'a Value) -> PyResult<Bound<'py, PyAny>> {
+        let max_depth = self.settings.max_depth;
+        if matches!(value, Value::List(_) | Value::Object(_)) && self.open.len() == max_depth {
+            return Err(value_too_deep(max_depth));
+        }
+        match value {
+            Value::List(items) => {
+                let list = PyList::empty(py);
+                self.open
+                    .push(OpenContainer::List(list.clone().unbind(), items.iter()));
+                Ok(list.into_any())
             }
-            if let Some(extensions) = err.extensions {
-                let ext_value = async_graphql::to_value(extensions)
-                    .map_err(|err| py_value_error(err.to_string()))?;
-                if !matches!(ext_value, Value::Object(ref map) if map.is_empty()) {
-                    err_dict.set_item("extensions", value_to_py_bound(py, &ext_value)?)?;
-                }
+            Value::Object(map) => {
+                let dict = PyDict::new(py);
+                self.open
+                    .push(OpenContainer::Object(dict.clone().unbind(), map.iter()));
+                Ok(dict.into_any())
             }
-            errors_list.append(err_dict)?;
+            value => value_to_py_bound(py, value, self.settings),
         }
-        errors_list.into_any().unbind()
+    }
+}
+
+// Completes on its second poll, letting the event loop run its other tasks in between like
+// `asyncio.sleep(0)`.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+// Converts a value tree `chunk_size` values per GIL acquisition, yielding to the event loop
+// between chunks, so that a single huge subscription event cannot block it for long.
+pub(crate) async fn chunked_value_to_py(
+    value: &Value,
+    chunk_size: NonZeroUsize,
+    settings: ValueSettings,
+) -> PyResult<Py<PyAny>> {
+    let mut conversion = ChunkedConversion::new(value, settings);
+    loop {
+        if let Some(converted) = Python::attach(|py| conversion.step(py, chunk_size))? {
+            return Ok(converted);
+        }
+        YieldNow(false).await;
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn value_to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+    Ok(value_to_py_bound(py, value, ValueSettings::default())?.unbind())
+}
+
+pub(crate) fn extensions_to_py(
+    py: Python<'_>,
+    extensions: &BTreeMap<String, Value>,
+    settings: ValueSettings,
+) -> PyResult<Py<PyAny>> {
+    if extensions.is_empty() {
+        return Ok(py.None());
+    }
+    let extensions_dict = PyDict::new(py);
+    for (key, value) in extensions {
+        extensions_dict.set_item(key, value_to_py_bound(py, value, settings)?)?;
+    }
+    Ok(extensions_dict.into_any().unbind())
+}
+
+// A response error as an object rather than a dict: `path` is a tuple, `locations` a list of
+// `{"line", "column"}` dicts, and `extensions` always a dict. `original_exception` is the
+// exception a resolver raised, when the error came from one.
+#[pyclass(module = "grommet._core", name = "GraphQLError", frozen)]
+pub(crate) struct GraphQLError {
+    #[pyo3(get)]
+    message: String,
+    #[pyo3(get)]
+    path: Py<PyTuple>,
+    #[pyo3(get)]
+    locations: Py<PyList>,
+    #[pyo3(get)]
+    extensions: Py<PyDict>,
+    #[pyo3(get)]
+    original_exception: Option<Py<PyAny>>,
+}
+
+#[pymethods]
+impl GraphQLError {
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "GraphQLError(message={}, path={})",
+            PyString::new(py, &self.message).repr()?,
+            self.path.bind(py).repr()?,
+        ))
+    }
+
+    fn __str__(&self) -> String {
+        self.message.clone()
+    }
+}
+
+fn error_locations<'py>(py: Python<'py>, err: &ServerError) -> PyResult<Bound<'py, PyList>> {
+    let locations = PyList::empty(py);
+    for loc in &err.locations {
+        let loc_dict = PyDict::new(py);
+        loc_dict.set_item("line", loc.line)?;
+        loc_dict.set_item("column", loc.column)?;
+        locations.append(loc_dict)?;
+    }
+    Ok(locations)
+}
+
+fn error_path<'py>(py: Python<'py>, err: &ServerError) -> PyResult<Bound<'py, PyList>> {
+    let path_list = PyList::empty(py);
+    for seg in &err.path {
+        match seg {
+            async_graphql::PathSegment::Field(name) => {
+                path_list.append(name)?;
+            }
+            async_graphql::PathSegment::Index(index) => {
+                path_list.append(index)?;
+            }
+        }
+    }
+    Ok(path_list)
+}
+
+// The error's extensions, or `None` when it has none.
+fn error_extensions<'py>(
+    py: Python<'py>,
+    err: &ServerError,
+    settings: ValueSettings,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Some(extensions) = &err.extensions else {
+        return Ok(None);
     };
+    let ext_value =
+        async_graphql::to_value(extensions).map_err(|err| py_value_error(err.to_string()))?;
+    if matches!(ext_value, Value::Object(ref map) if map.is_empty()) {
+        return Ok(None);
+    }
+    value_to_py_bound(py, &ext_value, settings).map(Some)
+}
+
+fn error_object(
+    py: Python<'_>,
+    err: &ServerError,
+    settings: ValueSettings,
+) -> PyResult<GraphQLError> {
+    let extensions = match error_extensions(py, err, settings)? {
+        Some(extensions) => extensions.cast_into::<PyDict>()?,
+        None => PyDict::new(py),
+    };
+    Ok(GraphQLError {
+        message: err.message.clone(),
+        path: error_path(py, err)?.to_tuple().unbind(),
+        locations: error_locations(py, err)?.unbind(),
+        extensions: extensions.unbind(),
+        original_exception: err
+            .source::<PyErr>()
+            .map(|source| source.value(py).clone().into_any().unbind()),
+    })
+}
+
+// The response errors as a list of dicts, or of `GraphQLError`s with `error_objects`. `None`
+// when there are none.
+pub(crate) fn errors_to_py(
+    py: Python<'_>,
+    errors: &[ServerError],
+    format: ResultFormat,
+) -> PyResult<Py<PyAny>> {
+    if errors.is_empty() {
+        return Ok(py.None());
+    }
+    let errors_list = PyList::empty(py);
+    for err in errors {
+        if format.error_objects {
+            errors_list.append(error_object(py, err, format.values)?)?;
+            continue;
+        }
+        let err_dict = PyDict::new(py);
+        err_dict.set_item("message", &err.message)?;
+        if !err.locations.is_empty() {
+            err_dict.set_item("locations", error_locations(py, err)?)?;
+        }
+        if !err.path.is_empty() {
+            err_dict.set_item("path", error_path(py, err)?)?;
+        }
+        if let Some(extensions) = error_extensions(py, err, format.values)? {
+            err_dict.set_item("extensions", extensions)?;
+        }
+        errors_list.append(err_dict)?;
+    }
+    Ok(errors_list.into_any().unbind())
+}
 
+pub(crate) fn response_to_py<'py>(
+    py: Python<'py>,
+    response: async_graphql::Response,
+    format: ResultFormat,
+) -> PyResult<Py<PyAny>> {
+    let data = payload_to_py(py, &response.data, format.values)?.unbind();
+    converted_response_to_py(py, response, data, format)
+}
+
+// Like `response_to_py`, with the response's data already converted.
+pub(crate) fn converted_response_to_py(
+    py: Python<'_>,
+    mut response: async_graphql::Response,
+    data: Py<PyAny>,
+    format: ResultFormat,
+) -> PyResult<Py<PyAny>> {
+    assign_error_codes(py, &mut response.errors);
+    let errors = errors_to_py(py, &response.errors, format)?;
+    let extensions = extensions_to_py(py, &response.extensions, format.values)?;
+    if format.execution_result {
+        let result = ExecutionResult {
+            data,
+            errors,
+            extensions,
+            status_hint: status_hint(&response),
+        };
+        return Ok(result.into_pyobject(py)?.into_any().unbind());
+    }
     let result = OperationResult {
         data,
         errors,
         extensions,
+        status_hint: status_hint(&response),
     };
     Ok(result.into_pyobject(py)?.into_any().unbind())
 }
+
+// A subscription event as a `SubscriptionPayload`, with its data already converted.
+pub(crate) fn response_to_payload(
+    py: Python<'_>,
+    mut response: async_graphql::Response,
+    data: Py<PyAny>,
+    format: ResultFormat,
+    cursor: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    assign_error_codes(py, &mut response.errors);
+    let payload = SubscriptionPayload {
+        data,
+        errors: errors_to_py(py, &response.errors, format)?,
+        extensions: extensions_to_py(py, &response.extensions, format.values)?,
+        is_keepalive: false,
+        cursor,
+    };
+    Ok(payload.into_pyobject(py)?.into_any().unbind())
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use async_graphql::ServerError;
+use async_graphql::parser::parse_query;
+use async_graphql::registry::Registry;
+use pyo3::prelude::*;
+
+use crate::document::collect_field_coordinates;
+
+// Evaluate a per-request visibility callable against every field selected by the query. Hidden
+// fields produce the same error validation reports for fields that do not exist.
+pub(crate) fn hidden_field_errors(
+    registry: &Registry,
+    query: &str,
+    visibility: &Bound<'_, PyAny>,
+) -> PyResult<Vec<ServerError>> {
+    // Unparseable queries are reported by regular execution.
+    let Ok(doc) = parse_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut decisions: HashMap<(String, String), bool> = HashMap::new();
+    let mut errors = Vec::new();
+    for coordinate in collect_field_coordinates(registry, &doc) {
+        let key = (coordinate.type_name, coordinate.field_name);
+        let visible = match decisions.get(&key) {
+            Some(visible) => *visible,
+            None => {
+                let visible = visibility
+                    .call1((key.0.as_str(), key.1.as_str()))?
+                    .is_truthy()?;
+                decisions.insert(key.clone(), visible);
+                visible
+            }
+        };
+        if !visible {
+            errors.push(ServerError::new(
+                format!("Unknown field \"{}\" on type \"{}\".", key.1, key.0),
+                Some(coordinate.pos),
+            ));
+        }
+    }
+    Ok(errors)
+}
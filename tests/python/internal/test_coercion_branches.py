@@ -83,3 +83,30 @@ def test_coerce_input_accepts_instances_dicts_and_rejects_other_values():
 
     with pytest.raises(TypeError, match="Expected mapping"):
         _coerce_input("bad", ChildInput)
+
+
+def test_pydantic_models_dump_for_defaults_and_coercion():
+    """Dumps v1 and v2 Pydantic models wherever input mappings are accepted."""
+
+    def init(self: object, **values: int) -> None:
+        self.__dict__.update(values)
+
+    def dump(self: object) -> dict[str, int]:
+        return dict(self.__dict__)
+
+    v2_model = type(
+        "BaseModel",
+        (),
+        {"__module__": "pydantic.main", "__init__": init, "model_dump": dump},
+    )
+    v1_model = type(
+        "BaseModel",
+        (),
+        {"__module__": "pydantic.v1.main", "__init__": init, "dict": dump},
+    )
+    lookalike = type("BaseModel", (), {"__module__": "models", "__init__": init})
+
+    assert _default_value_for_annotation(ChildInput, v2_model(value=5)) == {"value": 5}
+    assert _coerce_input(v1_model(value=6), ChildInput) == ChildInput(value=6)
+    with pytest.raises(TypeError, match="Expected mapping for input type ChildInput"):
+        _coerce_input(lookalike(value=7), ChildInput)
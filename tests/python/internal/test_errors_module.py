@@ -6,20 +6,42 @@ from grommet.errors import (
     GrommetError,
     GrommetTypeError,
     async_iterable_requires_parameter,
+    batch_resolver_info_not_supported,
+    batch_resolver_requires_list,
+    batch_resolver_retry_not_supported,
+    batch_resolver_serial_not_supported,
+    constraint_not_applicable,
+    default_literal_conflict,
     dataclass_required,
     decorator_requires_callable,
+    extension_data_fields_not_allowed,
+    extension_field_conflict,
+    extension_resolver_kind_mismatch,
+    extension_target_invalid,
+    input_field_default_literal_not_allowed,
     input_field_resolver_not_allowed,
     input_mapping_expected,
     input_type_expected,
+    interface_not_implemented,
+    interface_type_expected,
+    lazy_input_not_supported,
+    lazy_type_unresolved,
     list_type_requires_parameter,
+    mutation_root_not_object,
     not_grommet_type,
     output_type_expected,
+    relay_node_requires_id,
+    required_input_deprecated,
     resolver_context_annotation_requires_annotated,
     resolver_missing_annotation,
     resolver_requires_async,
+    retry_policy_invalid,
+    subscription_emit_requires_nullable,
+    subscription_error_policy_invalid,
     union_definition_conflict,
     union_input_not_supported,
     union_member_must_be_object,
+    union_member_not_registered,
     unsupported_annotation,
 )
 
@@ -46,6 +68,27 @@ from grommet.errors import (
             lambda: resolver_requires_async("resolver", "field"),
             "Resolver resolver for field 'field' must be async.",
         ),
+        (
+            lambda: batch_resolver_requires_list("resolver", "field"),
+            "Batch resolver resolver for field 'field' must return a list.",
+        ),
+        (
+            lambda: batch_resolver_info_not_supported("resolver", "field"),
+            "Batch resolver resolver for field 'field' cannot take Info.",
+        ),
+        (
+            lambda: batch_resolver_retry_not_supported("resolver", "field"),
+            "Batch resolver resolver for field 'field' cannot retry.",
+        ),
+        (
+            lambda: batch_resolver_serial_not_supported("resolver", "field"),
+            "Batch resolver resolver for field 'field' cannot be serial.",
+        ),
+        (
+            lambda: retry_policy_invalid("resolver", -1, 0),
+            "Resolver resolver has retries=-1 and backoff_ms=0; "
+            "both must be non-negative.",
+        ),
         (lambda: input_type_expected("Thing"), "Thing is not an input type"),
         (lambda: output_type_expected("Thing"), "Thing cannot be used as output"),
         (lambda: unsupported_annotation("bad"), "Unsupported annotation: bad"),
@@ -78,6 +121,80 @@ from grommet.errors import (
             lambda: union_definition_conflict("Named"),
             "Union 'Named' has conflicting definitions across the schema graph.",
         ),
+        (
+            lambda: union_member_not_registered("Named", "X"),
+            "Union 'Named' member 'X' is not a registered object type.",
+        ),
+        (
+            lambda: interface_not_implemented("A", "Node", ["missing field 'id'"]),
+            "Type 'A' does not correctly implement interface 'Node': "
+            "missing field 'id'.",
+        ),
+        (
+            lambda: constraint_not_applicable("field 'age'", "pattern", "Int"),
+            "Constraint 'pattern' on field 'age' does not apply to type 'Int'.",
+        ),
+        (
+            lambda: default_literal_conflict("argument 'size'"),
+            "Argument 'size' has both a default and a default_literal; "
+            "give it only one.",
+        ),
+        (
+            lambda: input_field_default_literal_not_allowed("size"),
+            "Input field 'size' cannot have a default_literal; "
+            "give it a dataclass default instead.",
+        ),
+        (
+            lambda: required_input_deprecated("argument 'term'"),
+            "Required argument 'term' cannot be deprecated; make it nullable or give "
+            "it a default.",
+        ),
+        (
+            lambda: relay_node_requires_id("User"),
+            "Relay node type 'User' must declare a non-null scalar 'id' field.",
+        ),
+        (lambda: interface_type_expected("Thing"), "Thing is not an interface type"),
+        (
+            lambda: extension_target_invalid("Filter"),
+            "Only object and subscription types can be extended; 'Filter' is neither.",
+        ),
+        (
+            lambda: extension_data_fields_not_allowed("Extra"),
+            "Type extension Extra can only declare resolver fields.",
+        ),
+        (
+            lambda: extension_resolver_kind_mismatch(
+                "Extra", "Query", "@grommet.field"
+            ),
+            "Type extension Extra for 'Query' must use @grommet.field resolvers.",
+        ),
+        (
+            lambda: extension_field_conflict("Book", "title"),
+            "Type 'Book' already defines field 'title'.",
+        ),
+        (
+            lambda: lazy_input_not_supported("Book"),
+            "Lazy reference 'Book' cannot be used as an input type.",
+        ),
+        (
+            lambda: lazy_type_unresolved("Book", "pkg.books"),
+            "Lazy reference 'Book' does not resolve to a grommet type named 'Book' "
+            "in module 'pkg.books'.",
+        ),
+        (
+            lambda: subscription_error_policy_invalid("resolver", "retry"),
+            "Subscription resolver resolver has on_error='retry'; "
+            "expected 'stop', 'skip', or 'emit'.",
+        ),
+        (
+            lambda: subscription_emit_requires_nullable("resolver", "ticks"),
+            "Subscription resolver resolver for field 'ticks' must return a nullable "
+            "type to use on_error='emit'.",
+        ),
+        (
+            lambda: mutation_root_not_object("Mutation", "input"),
+            "Mutation root 'Mutation' must be an object type, not input type.",
+        ),
     ],
 )
 def test_error_factories_emit_expected_type_and_message(factory, expected_message: str):
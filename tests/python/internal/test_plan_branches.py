@@ -8,8 +8,11 @@ import grommet
 from grommet._compiled import (
     COMPILED_TYPE_ATTR,
     REFS_ATTR,
+    CompiledArg,
     CompiledDataField,
+    CompiledResolverField,
     CompiledType,
+    CompiledUnion,
 )
 from grommet.metadata import TypeKind, TypeMeta, TypeSpec
 from grommet.plan import (
@@ -17,8 +20,13 @@ from grommet.plan import (
     _collect_compiled_unions,
     _get_compiled_type,
     _iter_interface_implementers,
+    _is_type_spec_subtype,
+    _iter_interface_problems,
     _iter_union_type_specs,
+    _render_type_spec,
+    _validate_interface_implementations,
     _validate_root_defaults,
+    _validate_union_members,
     _walk_and_collect,
     build_schema_graph,
 )
@@ -210,6 +218,30 @@ def test_validate_root_defaults_rejects_missing_defaults():
         _validate_root_defaults(Root)
 
 
+def test_build_schema_graph_validates_the_mutation_root():
+    """Rejects non-object mutation roots and accepts object ones."""
+
+    @grommet.type
+    @dataclass
+    class Query:
+        greeting: str = "hi"
+
+    @grommet.input
+    @dataclass
+    class MutationInput:
+        count: int = 0
+
+    @grommet.type
+    @dataclass
+    class Mutation:
+        count: int = 0
+
+    with pytest.raises(TypeError, match="must be an object type, not input type"):
+        build_schema_graph(query=Query, mutation=MutationInput)
+    bundle = build_schema_graph(query=Query, mutation=Mutation)
+    assert bundle.mutation == "Mutation"
+
+
 def test_build_schema_graph_rejects_roots_missing_compiled_type_metadata():
     """Raises when build_schema_graph receives undecorated root classes."""
 
@@ -224,3 +256,103 @@ def test_class_sort_key_uses_module_and_qualname():
     """Builds deterministic sort keys from module and qualname."""
     key = _class_sort_key(TypeMeta)
     assert key.endswith("grommet.metadata.TypeMeta")
+
+
+def _named(name: str, *, nullable: bool = False) -> TypeSpec:
+    return TypeSpec(kind="named", name=name, nullable=nullable)
+
+
+def _resolver_field(name: str, args: tuple[CompiledArg, ...]) -> CompiledResolverField:
+    return CompiledResolverField(
+        kind="field",
+        name=name,
+        func=lambda self, _context, _kwargs: None,
+        needs_context=False,
+        is_async=False,
+        type_spec=_named("String"),
+        description=None,
+        args=args,
+        refs=frozenset(),
+    )
+
+
+def test_validate_union_members_rejects_unregistered_and_non_object_members():
+    """Rejects union members that are missing from the graph or are not objects."""
+    union = CompiledUnion(
+        meta=TypeMeta(kind=TypeKind.UNION, name="Named"), possible_types=("A",)
+    )
+    with pytest.raises(TypeError, match="member 'A' is not a registered"):
+        _validate_union_members([union], {})
+
+    interface = CompiledType(meta=TypeMeta(kind=TypeKind.INTERFACE, name="A"))
+    with pytest.raises(TypeError, match="member 'A' is not a registered"):
+        _validate_union_members([union], {"A": interface})
+
+
+def test_validate_interface_implementations_rejects_unregistered_interfaces():
+    """Rejects implements entries that do not name a registered interface."""
+    compiled = CompiledType(
+        meta=TypeMeta(kind=TypeKind.OBJECT, name="A"), implements=("Missing",)
+    )
+    with pytest.raises(TypeError, match="'Missing' is not a registered interface"):
+        _validate_interface_implementations({"A": compiled})
+
+
+def test_iter_interface_problems_reports_all_argument_mismatches():
+    """Lists missing and mistyped arguments for implemented interface fields."""
+    interface = CompiledType(
+        meta=TypeMeta(kind=TypeKind.INTERFACE, name="Node"),
+        object_fields=(
+            _resolver_field(
+                "greet",
+                (
+                    CompiledArg(name="name", type_spec=_named("String")),
+                    CompiledArg(name="times", type_spec=_named("Int")),
+                ),
+            ),
+        ),
+    )
+    compiled = CompiledType(
+        meta=TypeMeta(kind=TypeKind.OBJECT, name="A"),
+        object_fields=(
+            _resolver_field(
+                "greet", (CompiledArg(name="name", type_spec=_named("Int")),)
+            ),
+        ),
+        implements=("Node",),
+    )
+
+    problems = list(_iter_interface_problems(compiled, interface, {}))
+    assert problems == [
+        "field 'greet' argument 'name' has type Int!, expected String!",
+        "field 'greet' is missing argument 'times'",
+    ]
+
+
+def test_is_type_spec_subtype_follows_output_covariance_rules():
+    """Accepts covariant implementations and rejects incompatible output types."""
+    types_by_name = {
+        "A": CompiledType(
+            meta=TypeMeta(kind=TypeKind.OBJECT, name="A"), implements=("Node",)
+        )
+    }
+    union = TypeSpec(kind="union", name="AB", union_members=("A", "B"))
+    list_of = TypeSpec(kind="list", of_type=_named("A"))
+
+    assert _is_type_spec_subtype(_named("A"), _named("A", nullable=True), {})
+    assert not _is_type_spec_subtype(_named("A", nullable=True), _named("A"), {})
+    assert _is_type_spec_subtype(_named("A"), _named("Node"), types_by_name)
+    assert not _is_type_spec_subtype(_named("B"), _named("Node"), types_by_name)
+    assert _is_type_spec_subtype(_named("A"), union, {})
+    assert not _is_type_spec_subtype(_named("C"), union, {})
+    assert _is_type_spec_subtype(
+        list_of, TypeSpec(kind="list", of_type=_named("Node")), types_by_name
+    )
+    assert not _is_type_spec_subtype(list_of, _named("A"), {})
+
+
+def test_render_type_spec_uses_graphql_type_notation():
+    """Renders nested list and nullability wrappers using SDL notation."""
+    spec = TypeSpec(kind="list", of_type=_named("Int", nullable=True), nullable=True)
+    assert _render_type_spec(spec) == "[Int]"
+    assert _render_type_spec(_named("String")) == "String!"
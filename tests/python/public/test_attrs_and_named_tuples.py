@@ -0,0 +1,56 @@
+"""Public contract tests for attrs and NamedTuple values in conversions."""
+
+from dataclasses import dataclass
+from types import SimpleNamespace
+from typing import NamedTuple
+
+import grommet
+
+
+class Point(NamedTuple):
+    x: int
+    y: int
+
+
+class Size:
+    """Shaped like an attrs class: attributes declared in `__attrs_attrs__`."""
+
+    __attrs_attrs__ = (SimpleNamespace(name="width"), SimpleNamespace(name="height"))
+
+    def __init__(self, width: int, height: int) -> None:
+        self.width = width
+        self.height = height
+
+
+@grommet.input
+@dataclass
+class PointInput:
+    x: int
+    y: int
+
+
+@grommet.input
+@dataclass
+class SizeInput:
+    width: int
+    height: int
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def area(self, origin: PointInput, size: SizeInput) -> str:
+        return f"{origin.x},{origin.y} {size.width}x{size.height}"
+
+
+async def test_named_tuples_and_attrs_instances_convert_as_objects(assert_success):
+    """Passes NamedTuple and attrs instances as input object variables."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "query ($origin: PointInput!, $size: SizeInput!) "
+        "{ area(origin: $origin, size: $size) }",
+        {"origin": Point(x=1, y=2), "size": Size(width=3, height=4)},
+    )
+
+    assert_success(result, {"area": "1,2 3x4"})
@@ -0,0 +1,94 @@
+"""Public contract tests for batched resolver fields."""
+
+from dataclasses import dataclass
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+CALLS: list[tuple[list[int], str]] = []
+
+
+@grommet.type
+@dataclass
+class Author:
+    id: int
+
+    @grommet.field
+    async def books(self) -> list[Annotated["Book", grommet.Lazy(__name__)]]:
+        return [Book(id=self.id * 10), Book(id=self.id * 10 + 1)]
+
+
+@grommet.type
+@dataclass
+class Book:
+    id: int
+
+    @grommet.field(batch=True)
+    async def author(self: list["Book"], style: str = "plain") -> list[Author]:
+        CALLS.append(([book.id for book in self], style))
+        return [Author(id=book.id // 10) for book in self]
+
+    @grommet.field(batch=True)
+    async def broken(self: list["Book"]) -> list[int]:
+        return []
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def books(self) -> list[Book]:
+        return [Book(id=10), Book(id=20), Book(id=30)]
+
+
+async def test_batched_fields_resolve_sibling_parents_in_one_call(assert_success):
+    """Calls the batch resolver once per depth and argument set, in parent order."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "{ books { id author { books { author { id } } }"
+        ' fancy: author(style: "fancy") { id } } }'
+    )
+
+    assert result.errors is None
+    assert result.data is not None
+    assert [book["fancy"]["id"] for book in result.data["books"]] == [1, 2, 3]
+    assert result.data["books"][0]["author"]["books"] == [
+        {"author": {"id": 1}},
+        {"author": {"id": 1}},
+    ]
+    assert sorted(CALLS) == [
+        ([10, 11, 20, 21, 30, 31], "plain"),
+        ([10, 20, 30], "fancy"),
+        ([10, 20, 30], "plain"),
+    ]
+
+
+async def test_batched_fields_report_mismatched_results():
+    """Fails every member of a batch when the resolver returns the wrong count."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute("{ books { broken } }")
+
+    assert result.data == {"books": [None, None, None]}
+    assert result.errors is not None
+    assert [error["message"] for error in result.errors] == [
+        "ValueError: Batch resolver returned 0 values for 3 parents"
+    ] * 3
+
+
+def test_batched_fields_require_async_list_resolvers():
+    """Rejects batch resolvers that are synchronous or do not return a list."""
+    with pytest.raises(GrommetTypeError, match="must be async"):
+
+        @grommet.field(batch=True)
+        def sync_batch(self: list[Book]) -> list[int]:
+            return []
+
+    with pytest.raises(GrommetTypeError, match="must return a list"):
+
+        @grommet.field(batch=True)
+        async def scalar_batch(self: list[Book]) -> int:
+            return 0
@@ -0,0 +1,91 @@
+"""Public contract tests for argument defaults given as GraphQL literals."""
+
+from dataclasses import dataclass
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet._core import SchemaBuildError
+from grommet.errors import GrommetTypeError
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def pick(
+        self,
+        ids: Annotated[list[int], grommet.Field(default_literal="[1, 2]")],
+        label: Annotated[str, grommet.Field(default_literal='"first"')],
+    ) -> str:
+        return f"{label}:{ids}"
+
+
+def test_default_literals_render_as_written(schema_sdl):
+    """Renders literal defaults in SDL as the values they parse to."""
+    sdl = schema_sdl(grommet.Schema(query=Query))
+    assert 'pick(ids: [Int!]! = [1, 2], label: String! = "first"): String!' in sdl
+
+
+async def test_default_literals_reach_resolvers(assert_success):
+    """Passes literal defaults of omitted arguments to resolvers."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute('{ pick other: pick(ids: [3], label: "x") }')
+    assert_success(result, {"pick": "first:[1, 2]", "other": "x:[3]"})
+
+
+def test_default_literals_are_exclusive_to_arguments():
+    """Rejects literals beside Python defaults, on input fields, or unparsable."""
+    with pytest.raises(GrommetTypeError, match="both a default and a default_literal"):
+
+        @grommet.field
+        async def both(
+            self, size: Annotated[int, grommet.Field(default_literal="1")] = 2
+        ) -> int:
+            return size
+
+    with pytest.raises(GrommetTypeError, match="cannot have a default_literal"):
+
+        @grommet.input
+        @dataclass
+        class Page:
+            size: Annotated[int, grommet.Field(default_literal="20")]
+
+    @grommet.type(name="Query")
+    @dataclass
+    class BrokenQuery:
+        @grommet.field
+        async def broken(
+            self, size: Annotated[int, grommet.Field(default_literal="[1,")]
+        ) -> int:
+            return size
+
+    with pytest.raises(SchemaBuildError, match="is not a GraphQL value"):
+        grommet.Schema(query=BrokenQuery)
+
+
+def _schema_with_default(literal: str) -> grommet.Schema:
+    @grommet.type(name="Query")
+    @dataclass
+    class LiteralQuery:
+        @grommet.field
+        async def echo(
+            self, text: Annotated[str, grommet.Field(default_literal=literal)]
+        ) -> str:
+            return text
+
+    return grommet.Schema(query=LiteralQuery)
+
+
+async def test_default_literals_are_a_single_value(assert_success):
+    """Rejects literals that go on past their value and keeps strings as written."""
+    schema = _schema_with_default('"(a) #b @c $d"')
+
+    assert_success(await schema.execute("{ echo }"), {"echo": "(a) #b @c $d"})
+    with pytest.raises(SchemaBuildError, match='unexpected "\\)" outside a string'):
+        _schema_with_default('"a") { __typename } query Other($b: String = "b"')
+    with pytest.raises(SchemaBuildError, match='unexpected "#" outside a string'):
+        _schema_with_default('"a" #')
+    with pytest.raises(SchemaBuildError, match="is not a GraphQL value"):
+        _schema_with_default('"a" "b"')
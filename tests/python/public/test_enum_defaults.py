@@ -0,0 +1,45 @@
+"""Public contract tests for Python enum members used as defaults."""
+
+import enum
+from dataclasses import dataclass
+from typing import cast
+
+import grommet
+
+
+class Size(enum.Enum):
+    SMALL = "small"
+    LARGE = "large"
+
+
+class Level(enum.IntEnum):
+    LOW = 1
+    HIGH = 2
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def order(
+        self, size: str = cast("str", Size.SMALL), level: int = Level.HIGH
+    ) -> str:
+        return f"{size}:{level}"
+
+
+def test_enum_defaults_render_as_their_values(schema_sdl):
+    """Renders enum member defaults in SDL as literals of their values."""
+    sdl = schema_sdl(grommet.Schema(query=Query))
+    assert 'order(size: String = "small", level: Int = 2): String!' in sdl
+
+
+async def test_enum_defaults_reach_resolvers_as_values(assert_success):
+    """Introspects enum member defaults and passes their values to resolvers."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        '{ order __type(name: "Query") { fields { args { defaultValue } } } }'
+    )
+    args = [{"defaultValue": '"small"'}, {"defaultValue": "2"}]
+    assert_success(
+        result, {"order": "small:2", "__type": {"fields": [{"args": args}]}}
+    )
@@ -0,0 +1,205 @@
+"""Public contract tests for declarative input constraints."""
+
+from dataclasses import dataclass, field
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+CALLS: list[str] = []
+
+
+@grommet.input
+@dataclass
+class TagInput:
+    label: Annotated[str, grommet.Field(min_length=2)]
+
+
+@grommet.input
+@dataclass
+class SearchInput:
+    term: Annotated[str, grommet.Field(max_length=5, pattern="^[a-z]+$")]
+    tags: Annotated[list[TagInput], grommet.Field(max_length=2)] = field(
+        default_factory=list
+    )
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def search(
+        self,
+        input: SearchInput,
+        count: Annotated[int, grommet.Field(minimum=1, maximum=10)] = 5,
+    ) -> str:
+        CALLS.append(input.term)
+        return f"{input.term}:{count}"
+
+
+async def test_constraints_accept_values_within_bounds(assert_success):
+    """Passes constrained arguments through to the resolver unchanged."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        '{ search(input: { term: "abc", tags: [{ label: "ok" }] }, count: 10) }'
+    )
+    assert_success(result, {"search": "abc:10"})
+
+
+@pytest.mark.parametrize(
+    ("arguments", "path", "message"),
+    [
+        ('input: { term: "abcdef" }', "input.term", "at most 5"),
+        ('input: { term: "ABC" }', "input.term", 'must match pattern "^[a-z]+$"'),
+        ('input: { term: "abc" }, count: 0', "count", "greater than or equal to 1"),
+        ('input: { term: "abc" }, count: 11', "count", "less than or equal to 10"),
+        (
+            'input: { term: "abc", tags: [{ label: "ok" }, { label: "x" }] }',
+            "input.tags[1].label",
+            "at least 2",
+        ),
+        (
+            'input: { term: "abc", tags: [{ label: "a1" }, { label: "b2" }, '
+            '{ label: "c3" }] }',
+            "input.tags",
+            "at most 2",
+        ),
+    ],
+)
+async def test_constraints_reject_arguments_before_resolvers_run(
+    arguments: str, path: str, message: str
+):
+    """Reports the violated constraint with the argument path and skips the resolver."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(f"{{ search({arguments}) }}")
+
+    assert result.data is None
+    assert result.errors is not None
+    assert result.errors[0]["message"].startswith(
+        f'Invalid value for argument "{path}": '
+    )
+    assert message in result.errors[0]["message"]
+    assert CALLS == []
+
+
+@grommet.type
+@dataclass
+class TagsQuery:
+    @grommet.field
+    async def tags(
+        self,
+        tags: Annotated[list[str], grommet.Field(min_length=2, pattern="^[a-z]$")],
+    ) -> int:
+        return len(tags)
+
+
+async def test_list_length_limits_do_not_apply_to_items(assert_success):
+    """Checks lengths against the list itself and patterns against each item."""
+    schema = grommet.Schema(query=TagsQuery)
+    result = await schema.execute('{ tags(tags: ["a", "b", "c"]) }')
+    short = await schema.execute('{ tags(tags: ["a"]) }')
+    invalid = await schema.execute('{ tags(tags: ["a", "B"]) }')
+
+    assert_success(result, {"tags": 3})
+    assert short.errors is not None
+    assert "at least 2" in short.errors[0]["message"]
+    assert invalid.errors is not None
+    assert "must match pattern" in invalid.errors[0]["message"]
+
+
+def test_constraints_must_match_the_value_type():
+    """Rejects constraints that cannot apply to the annotated GraphQL type."""
+    with pytest.raises(GrommetTypeError, match="Constraint 'pattern' on argument"):
+
+        @grommet.field
+        async def lookup(
+            self, id: Annotated[int, grommet.Field(pattern="[0-9]+")]
+        ) -> str:
+            return str(id)
+
+    with pytest.raises(GrommetTypeError, match="Constraint 'minimum' on field"):
+
+        @grommet.input
+        @dataclass
+        class BadInput:
+            name: Annotated[str, grommet.Field(minimum=1)]
+
+
+def test_invalid_constraint_patterns_fail_schema_build():
+    """Surfaces invalid patterns when the schema is built."""
+
+    @grommet.input
+    @dataclass
+    class PatternInput:
+        code: Annotated[str, grommet.Field(pattern="(")]
+
+    @grommet.type
+    @dataclass
+    class PatternQuery:
+        @grommet.field
+        async def check(self, input: PatternInput) -> bool:
+            return True
+
+    with pytest.raises(ValueError, match="Invalid constraint pattern"):
+        grommet.Schema(query=PatternQuery)
+
+
+def _require_even(value: int) -> None:
+    if value % 2:
+        raise ValueError("must be even")
+
+
+def _require_known_term(value: SearchInput | None) -> None:
+    if value is not None and value.term not in {"abc", "xyz"}:
+        raise ValueError(f"unknown term {value.term!r}")
+
+
+@grommet.type
+@dataclass
+class ValidatedQuery:
+    @grommet.field
+    async def page(
+        self,
+        size: Annotated[int, grommet.Field(minimum=0, validate=_require_even)],
+        input: Annotated[
+            SearchInput | None, grommet.Field(validate=_require_known_term)
+        ] = None,
+    ) -> int:
+        CALLS.append(str(size))
+        return size
+
+
+async def test_validate_callables_receive_coerced_values(assert_success):
+    """Runs validators with coerced inputs and passes accepted values through."""
+    schema = grommet.Schema(query=ValidatedQuery)
+    result = await schema.execute('{ page(size: 4, input: { term: "xyz" }) }')
+    assert_success(result, {"page": 4})
+
+
+@pytest.mark.parametrize(
+    ("arguments", "message"),
+    [
+        ("size: 3", 'Invalid value for argument "size": must be even'),
+        ("size: -2", 'Invalid value for argument "size": must be greater than'),
+        (
+            'size: 2, input: { term: "nope" }',
+            "Invalid value for argument \"input\": unknown term 'nope'",
+        ),
+    ],
+)
+async def test_validate_callable_errors_become_argument_errors(
+    arguments: str, message: str
+):
+    """Reports validator exceptions as argument errors located at the field."""
+    CALLS.clear()
+    schema = grommet.Schema(query=ValidatedQuery)
+    result = await schema.execute(f"{{ page({arguments}) }}")
+
+    assert result.data is None
+    assert result.errors is not None
+    assert result.errors[0]["message"].startswith(message)
+    assert result.errors[0]["locations"] == [{"line": 1, "column": 3}]
+    assert CALLS == []
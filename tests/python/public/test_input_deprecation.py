@@ -0,0 +1,151 @@
+"""Public contract tests for deprecated arguments, input fields, and output fields."""
+
+from dataclasses import dataclass
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+
+@grommet.input
+@dataclass
+class FilterInput:
+    name: str
+    legacy: Annotated[str | None, grommet.Field(deprecated="Use name.")] = None
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def search(
+        self,
+        filter: FilterInput,
+        limit: Annotated[int, grommet.Field(deprecated=True)] = 10,
+    ) -> str:
+        return f"{filter.name}:{limit}"
+
+
+def test_deprecated_inputs_are_rendered_in_sdl(schema_sdl):
+    """Renders @deprecated on arguments and input fields, with the default reason."""
+    sdl = schema_sdl(grommet.Schema(query=Query))
+    assert 'legacy: String = null @deprecated(reason: "Use name.")' in sdl
+    assert 'limit: Int = 10 @deprecated(reason: "No longer supported")' in sdl
+
+
+async def test_deprecated_inputs_are_introspected_and_still_accepted(assert_success):
+    """Reports deprecations through introspection while the inputs keep working."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        """
+        {
+          query: __type(name: "Query") {
+            fields {
+              args(includeDeprecated: true) {
+                name
+                isDeprecated
+                deprecationReason
+              }
+            }
+          }
+          input: __type(name: "FilterInput") {
+            inputFields(includeDeprecated: true) { name isDeprecated }
+          }
+          search(filter: { name: "a", legacy: "b" }, limit: 2)
+        }
+        """
+    )
+    assert_success(
+        result,
+        {
+            "query": {
+                "fields": [
+                    {
+                        "args": [
+                            {
+                                "name": "filter",
+                                "isDeprecated": False,
+                                "deprecationReason": None,
+                            },
+                            {
+                                "name": "limit",
+                                "isDeprecated": True,
+                                "deprecationReason": "No longer supported",
+                            },
+                        ]
+                    }
+                ]
+            },
+            "input": {
+                "inputFields": [
+                    {"name": "name", "isDeprecated": False},
+                    {"name": "legacy", "isDeprecated": True},
+                ]
+            },
+            "search": "a:2",
+        },
+    )
+
+
+def test_required_inputs_cannot_be_deprecated():
+    """Rejects deprecating an argument that is non-null without a default."""
+    with pytest.raises(GrommetTypeError, match="Required argument 'term'"):
+
+        @grommet.field
+        async def search(
+            self: object, term: Annotated[str, grommet.Field(deprecated=True)]
+        ) -> str:
+            return term
+
+
+@grommet.interface
+@dataclass
+class Named:
+    title: Annotated[str, grommet.Field(deprecated="Use name.")]
+
+
+@grommet.type
+@dataclass
+class Item(Named):
+    name: str
+    code: Annotated[str, grommet.Field(deprecated=True)]
+
+
+@grommet.type
+@dataclass
+class ItemQuery:
+    @grommet.field
+    async def item(self) -> Named:
+        return Item(title="a", name="a", code="b")
+
+
+async def test_deprecated_output_fields_are_rendered_and_resolved(
+    schema_sdl, assert_success
+):
+    """Renders @deprecated on object and interface fields, which keep resolving."""
+    schema = grommet.Schema(query=ItemQuery)
+    sdl = schema_sdl(schema)
+    assert 'title: String! @deprecated(reason: "Use name.")' in sdl
+    assert 'code: String! @deprecated(reason: "No longer supported")' in sdl
+
+    result = await schema.execute(
+        """
+        {
+          named: __type(name: "Named") {
+            fields(includeDeprecated: true) { name deprecationReason }
+          }
+          item { title ... on Item { code } }
+        }
+        """
+    )
+    assert_success(
+        result,
+        {
+            "named": {
+                "fields": [{"name": "title", "deprecationReason": "Use name."}]
+            },
+            "item": {"title": "a", "code": "b"},
+        },
+    )
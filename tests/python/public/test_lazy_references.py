@@ -0,0 +1,89 @@
+"""Public contract tests for lazy type references."""
+
+from dataclasses import dataclass, field
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+
+@grommet.type
+@dataclass
+class Author:
+    name: str
+    books: list[Annotated["Book", grommet.Lazy(__name__)]] = field(
+        default_factory=list
+    )
+    favorite: Annotated["Book", grommet.Lazy(__name__)] | None = None
+
+
+@grommet.type
+@dataclass
+class Book:
+    title: str
+    author: Author
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def author(self) -> Author:
+        author = Author(name="Ada")
+        book = Book(title="Notes", author=author)
+        author.books.append(book)
+        author.favorite = book
+        return author
+
+
+async def test_lazy_references_resolve_when_the_schema_is_built(assert_success):
+    """Resolves string references to types declared after the referencing type."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "{ author { books { title author { name } } favorite { title } } }"
+    )
+
+    assert_success(
+        result,
+        {
+            "author": {
+                "books": [{"title": "Notes", "author": {"name": "Ada"}}],
+                "favorite": {"title": "Notes"},
+            }
+        },
+    )
+    sdl = schema._schema.as_sdl()
+    assert "\tbooks: [Book!]!\n\tfavorite: Book\n" in sdl
+
+
+def test_unresolved_lazy_references_fail_schema_build():
+    """Reports lazy references that do not name a grommet type in their module."""
+
+    @grommet.type(name="Query")
+    @dataclass
+    class MissingQuery:
+        missing: Annotated["Missing", grommet.Lazy(__name__)] | None = None
+
+    with pytest.raises(GrommetTypeError, match="Lazy reference 'Missing' does not"):
+        grommet.Schema(query=MissingQuery)
+
+
+def test_lazy_references_cannot_be_inputs():
+    """Rejects lazy references in input positions."""
+    with pytest.raises(GrommetTypeError, match="cannot be used as an input type"):
+
+        @grommet.field
+        async def lookup(self, book: Annotated["Book", grommet.Lazy(__name__)]) -> str:
+            return ""
+
+
+def test_forward_references_need_lazy_metadata():
+    """Rejects string references whose Annotated metadata has no Lazy marker."""
+    with pytest.raises(GrommetTypeError, match="Unsupported annotation"):
+
+        @grommet.type
+        @dataclass
+        class Shelf:
+            book: Annotated["Book", grommet.Field(description="A book")]
@@ -0,0 +1,97 @@
+"""Public contract tests for async resolvers run without a running event loop."""
+
+import asyncio
+import threading
+import time
+from collections.abc import Coroutine
+from dataclasses import dataclass
+from typing import Any
+
+import grommet
+
+THREADS: list[str] = []
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def greeting(self) -> str:
+        await asyncio.sleep(0)
+        THREADS.append(threading.current_thread().name)
+        return "hello"
+
+
+def _drive(coroutine: "Coroutine[Any, Any, Any]") -> Any:
+    while True:
+        try:
+            yielded = coroutine.send(None)
+        except StopIteration as stop:
+            return stop.value
+        assert yielded is None, "the operation waited on an event loop"
+        time.sleep(0.001)
+
+
+def test_awaitables_run_on_the_helper_loop_without_a_running_loop():
+    """Finishes async resolvers on the helper loop for operations driven by hand."""
+    THREADS.clear()
+    schema = grommet.Schema(query=Query)
+
+    result = _drive(schema.execute("{ greeting }"))
+
+    assert result.errors is None
+    assert result.data == {"greeting": "hello"}
+    assert THREADS == ["grommet-helper-loop"]
+
+
+@grommet.type
+@dataclass
+class SlowQuery:
+    @grommet.field
+    async def greeting(self) -> str:
+        await asyncio.sleep(0.05)
+        return "hello"
+
+
+def test_operations_yield_while_the_helper_loop_runs_their_awaitables():
+    """Yields to the caller instead of blocking while a helper-loop awaitable runs."""
+    coroutine = grommet.Schema(query=SlowQuery).execute("{ greeting }")
+
+    started = time.monotonic()
+    assert coroutine.send(None) is None
+    assert time.monotonic() - started < 0.05
+
+    result = _drive(coroutine)
+    assert result.data == {"greeting": "hello"}
+
+
+def test_awaitables_run_on_the_helper_loop_from_worker_threads():
+    """Serves operations driven from several threads through the shared helper loop."""
+    THREADS.clear()
+    schema = grommet.Schema(query=Query)
+    results: list[Any] = []
+
+    workers = [
+        threading.Thread(
+            target=lambda: results.append(_drive(schema.execute("{ greeting }")))
+        )
+        for _ in range(3)
+    ]
+    for worker in workers:
+        worker.start()
+    for worker in workers:
+        worker.join()
+
+    assert [result.data for result in results] == [{"greeting": "hello"}] * 3
+    assert THREADS == ["grommet-helper-loop"] * 3
+
+
+async def test_awaitables_run_on_the_running_loop_when_there_is_one():
+    """Keeps scheduling async resolvers on the loop that runs the operation."""
+    THREADS.clear()
+    schema = grommet.Schema(query=Query)
+
+    result = await schema.execute("{ greeting }")
+
+    assert result.errors is None
+    assert THREADS == [threading.current_thread().name]
@@ -0,0 +1,89 @@
+"""Public contract tests for connection-style pagination arguments."""
+
+from dataclasses import dataclass
+from typing import Annotated
+
+import pytest
+
+import grommet
+
+CALLS: list[dict[str, int | None]] = []
+
+PageSize = Annotated[int | None, grommet.Field(maximum=50)]
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def users(
+        self,
+        first: PageSize = None,
+        last: PageSize = None,
+        offset: Annotated[int, grommet.Field(maximum=1000)] = 0,
+    ) -> str:
+        CALLS.append({"first": first, "last": last, "offset": offset})
+        return f"{first}:{last}:{offset}"
+
+    @grommet.field
+    def items(self, limit: Annotated[int, grommet.Field(maximum=20)] = 10) -> int:
+        return limit
+
+    @grommet.field
+    def plain(self, first: int = -1, last: int = -1) -> str:
+        return f"{first}:{last}"
+
+
+async def test_page_sizes_above_maximum_are_clamped(assert_success):
+    """Passes page sizes above the declared maximum to resolvers as the maximum."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "query ($limit: Int!) { users(first: 500, offset: 10) items(limit: $limit) }",
+        {"limit": 21},
+    )
+
+    assert_success(result, {"users": "50:None:10", "items": 20})
+    assert CALLS == [{"first": 50, "last": None, "offset": 10}]
+
+
+@pytest.mark.parametrize(
+    ("arguments", "message"),
+    [
+        (
+            "first: 5, last: 5",
+            'Arguments "first" and "last" cannot be used together.',
+        ),
+        (
+            "first: -1",
+            'Invalid value for argument "first": must be greater than or equal to 0',
+        ),
+        (
+            "offset: -3",
+            'Invalid value for argument "offset": must be greater than or equal to 0',
+        ),
+        (
+            "offset: 1001",
+            'Invalid value for argument "offset": must be less than or equal to 1000',
+        ),
+    ],
+)
+async def test_pagination_rules_reject_arguments_before_resolvers_run(
+    arguments: str, message: str
+):
+    """Reports standardized pagination errors without calling the resolver."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(f"{{ users({arguments}) }}")
+
+    assert result.errors is not None
+    assert [error["message"] for error in result.errors] == [message]
+    assert CALLS == []
+
+
+async def test_pagination_rules_require_a_declared_maximum(assert_success):
+    """Leaves pagination-named arguments without a maximum untouched."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute("{ plain(first: -2, last: 3) }")
+
+    assert_success(result, {"plain": "-2:3"})
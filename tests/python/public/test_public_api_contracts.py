@@ -19,14 +19,27 @@ class RootWithoutDefault:
     greeting: str
 
 
+@grommet.type(name="RootQuery")
+@dataclass
+class RenamedQuery:
+    greeting: str = "Hello world!"
+
+
 def test_public_exports_match_the_supported_surface():
     """Ensures __all__ exposes the documented public entry points."""
     expected = {
+        "Complete",
         "Context",
+        "Event",
         "Field",
+        "GlobalID",
         "Hidden",
+        "ID",
+        "Info",
+        "Lazy",
         "Schema",
         "Union",
+        "extend",
         "field",
         "input",
         "interface",
@@ -61,3 +74,17 @@ def test_root_type_fields_require_defaults_or_resolvers():
     """Rejects root data fields without defaults during schema construction."""
     with pytest.raises(TypeError, match="must declare a default value"):
         grommet.Schema(query=RootWithoutDefault)
+
+
+def test_sdl_omits_schema_definition_for_conventional_root_names(schema_sdl):
+    """Leaves the schema definition implicit when roots use the default names."""
+    sdl = schema_sdl(grommet.Schema(query=Query))
+    assert "type Query" in sdl
+    assert "schema {" not in sdl
+
+
+def test_sdl_emits_schema_definition_for_renamed_roots(schema_sdl):
+    """Emits an explicit schema definition when a root type is renamed."""
+    sdl = schema_sdl(grommet.Schema(query=RenamedQuery))
+    assert "type RootQuery" in sdl
+    assert "schema {\n\tquery: RootQuery\n}" in sdl
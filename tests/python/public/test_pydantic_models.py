@@ -0,0 +1,73 @@
+"""Public contract tests for passing Pydantic models through the conversion layer."""
+
+from dataclasses import dataclass
+from typing import Any, cast
+
+import grommet
+
+
+class BaseModel:
+    """Stand-in for `pydantic.BaseModel`, recognized by its name and module."""
+
+    __module__ = "pydantic.main"
+
+    def __init__(self, **values: Any) -> None:
+        self.__dict__.update(values)
+
+    def model_dump(self) -> dict[str, Any]:
+        return dict(self.__dict__)
+
+
+class Address(BaseModel):
+    """A Pydantic model returned where a grommet type is expected."""
+
+
+YORK = Address(city="York", country="GB")
+
+
+@grommet.input
+@dataclass
+class AddressFilter:
+    city: str
+    country: str = "GB"
+
+
+@grommet.type(name="Address")
+@dataclass
+class AddressType:
+    city: str
+    country: str
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def addresses(self) -> list[AddressType]:
+        return cast("list[AddressType]", [Address(city="Leeds", country="GB")])
+
+    @grommet.field
+    async def describe(self, where: AddressFilter = cast("AddressFilter", YORK)) -> str:
+        return f"{where.city}, {where.country}"
+
+
+async def test_models_resolve_output_fields_from_attributes(assert_success):
+    """Reads the fields of output types from models returned by resolvers."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute("{ addresses { city country } }")
+
+    assert_success(result, {"addresses": [{"city": "Leeds", "country": "GB"}]})
+
+
+async def test_models_convert_as_inputs_and_defaults(assert_success):
+    """Dumps models passed as variables or declared as argument defaults."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "query ($where: AddressFilter!) { given: describe(where: $where) describe }",
+        {"where": Address(city="Bath", country="GB")},
+    )
+
+    assert_success(result, {"given": "Bath, GB", "describe": "York, GB"})
+    assert 'where: AddressFilter = {city: "York", country: "GB"}' in (
+        schema._schema.as_sdl()
+    )
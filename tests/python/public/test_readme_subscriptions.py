@@ -1,12 +1,16 @@
 """Public contract tests for README subscription examples."""
 
+import asyncio
+import gc
+import itertools
 from collections.abc import AsyncIterator
 from dataclasses import dataclass
-from typing import Annotated
+from typing import Annotated, Literal, cast
 
 import pytest
 
 import grommet
+from grommet._core import SubscriptionPayload
 
 
 @grommet.type
@@ -64,3 +68,249 @@ async def test_readme_subscription_context_is_injected(collect_stream):
     )
     rows = await collect_stream(stream)
     assert rows == [{"counter": "123:0"}, {"counter": "123:1"}, {"counter": "123:2"}]
+
+
+@grommet.type
+@dataclass
+class TickerSubscription:
+    @grommet.subscription
+    async def ticks(self, count: int) -> AsyncIterator[int]:
+        for _ in range(count):
+            tick = next(TICKS)
+            yield grommet.Event(tick, cursor=f"tick-{tick}")
+
+
+TICKS = itertools.count()
+
+
+async def test_subscription_resume_replays_missed_events(collect_stream):
+    """Replays buffered events after the resume cursor before live events."""
+    schema = grommet.Schema(query=Query, subscription=TickerSubscription)
+    core = schema._schema
+    core.set_replay_buffer(10)
+
+    stream = await core.execute("subscription { ticks(count: 2) }")
+    first = await anext(stream)
+    start = first.data["ticks"]
+    assert stream.cursor == f"tick-{start}"
+    await stream.aclose()
+
+    # Another subscriber with the same arguments receives the events the first missed.
+    other = await core.execute("subscription { ticks(count: 2) }")
+    assert len(await collect_stream(other)) == 2
+
+    resumed = await core.execute(
+        "subscription { ticks(count: 2) }", options={"resume_from": f"tick-{start}"}
+    )
+    rows = await collect_stream(resumed)
+    assert [row["ticks"] for row in rows] == [start + offset for offset in range(1, 5)]
+    assert resumed.cursor == f"tick-{start + 4}"
+
+
+async def test_subscription_resume_without_buffer_streams_live_events(collect_stream):
+    """Streams only live events when no replay buffer is configured."""
+    schema = grommet.Schema(query=Query, subscription=TickerSubscription)
+
+    stream = await schema._schema.execute(
+        "subscription { ticks(count: 2) }", options={"resume_from": "tick-0"}
+    )
+    assert stream.cursor is None
+    assert len(await collect_stream(stream)) == 2
+
+
+async def test_subscription_envelopes_carry_each_event_cursor():
+    """Delivers SubscriptionPayload envelopes that pattern-match by kind."""
+    schema = grommet.Schema(query=Query, subscription=TickerSubscription)
+
+    stream = await schema._schema.execute(
+        "subscription { ticks(count: 2) }", options={"envelope": True}
+    )
+    payloads = [payload async for payload in stream]
+    payloads.append(SubscriptionPayload.keepalive())
+
+    kinds = []
+    for payload in payloads:
+        match payload:
+            case SubscriptionPayload(is_keepalive=True):
+                kinds.append("keepalive")
+            case SubscriptionPayload({"ticks": tick}, None, False, cursor):
+                assert cursor == f"tick-{tick}"
+                kinds.append("event")
+    assert kinds == ["event", "event", "keepalive"]
+    assert payloads[-1].data is None
+    assert payloads[-1].cursor is None
+
+
+@grommet.type
+@dataclass
+class Job:
+    state: str
+    progress: int
+
+
+@grommet.type
+@dataclass
+class JobSubscription:
+    @grommet.subscription
+    async def job(self) -> AsyncIterator[Job]:
+        try:
+            yield Job(state="running", progress=50)
+            yield cast("Job", grommet.Complete(Job(state="done", progress=100)))
+            yield Job(state="unreachable", progress=0)
+        finally:
+            CLOSED.append("job")
+
+
+CLOSED: list[str] = []
+
+
+async def test_subscription_complete_delivers_a_final_payload(collect_stream):
+    """Ends the stream after a Complete value and closes the generator."""
+    schema = grommet.Schema(query=Query, subscription=JobSubscription)
+
+    stream = await schema.execute("subscription { job { state progress } }")
+    rows = await collect_stream(stream)
+
+    assert rows == [
+        {"job": {"state": "running", "progress": 50}},
+        {"job": {"state": "done", "progress": 100}},
+    ]
+    assert CLOSED == ["job"]
+
+
+def _reading_subscription(on_error: "Literal['stop', 'skip', 'emit']") -> type:
+    @grommet.type(name="Subscription")
+    @dataclass
+    class ReadingSubscription:
+        @grommet.subscription(on_error=on_error)
+        async def readings(self) -> AsyncIterator[int | None]:
+            for reading in (1, "garbled", 3):
+                yield cast("int", reading)
+
+    return ReadingSubscription
+
+
+@pytest.mark.parametrize(
+    ("on_error", "expected"),
+    [
+        ("stop", [1, None]),
+        ("skip", [1, 3]),
+        ("emit", [1, None, 3]),
+    ],
+)
+async def test_subscription_error_policy_decides_whether_streams_continue(
+    on_error: "Literal['stop', 'skip', 'emit']", expected: list[int | None]
+):
+    """Stops at, drops, or delivers a failing event per the on_error policy."""
+    schema = grommet.Schema(query=Query, subscription=_reading_subscription(on_error))
+
+    stream = await schema.execute("subscription { readings }")
+    results = [result async for result in stream]
+
+    readings = [(result.data or {}).get("readings") for result in results]
+    assert readings == expected
+    failed = [result for result in results if result.errors]
+    assert len(failed) == (0 if on_error == "skip" else 1)
+    for result in failed:
+        assert result.errors[0]["path"] == ["readings"]
+
+
+def test_subscription_emit_policy_requires_a_nullable_type():
+    """Rejects on_error='emit' for fields that cannot deliver null."""
+    with pytest.raises(TypeError, match="must return a nullable type"):
+
+        @grommet.subscription(on_error="emit")
+        async def readings(self: object) -> AsyncIterator[int]:
+            yield 1
+
+
+def test_subscription_error_policy_must_be_known():
+    """Rejects on_error values other than 'stop', 'skip', and 'emit'."""
+    policy = cast("Literal['stop', 'skip', 'emit']", "retry")
+    with pytest.raises(TypeError, match="on_error='retry'"):
+
+        @grommet.subscription(on_error=policy)
+        async def readings(self: object) -> AsyncIterator[int]:
+            yield 1
+
+
+CLOSED_TICKERS: list[str] = []
+
+
+@grommet.type
+@dataclass
+class ClosingSubscription:
+    @grommet.subscription
+    async def ticks(self, name: str) -> AsyncIterator[int]:
+        try:
+            for tick in itertools.count(1):
+                yield tick
+        finally:
+            CLOSED_TICKERS.append(name)
+
+
+async def _run_scheduled_closes() -> None:
+    # One loop iteration creates the closing task and the next one runs it.
+    for _ in range(2):
+        await asyncio.sleep(0)
+
+
+async def test_subscription_stream_closes_when_its_async_with_block_exits():
+    """Closes the stream and its iterator on exit, even when the block raises."""
+    CLOSED_TICKERS.clear()
+    schema = grommet.Schema(query=Query, subscription=ClosingSubscription)
+    query = 'subscription { ticks(name: "ok") }'
+
+    async with await schema.execute(query) as stream:
+        first = await anext(stream)
+    with pytest.raises(RuntimeError, match="boom"):
+        async with await schema.execute(query.replace("ok", "raised")) as failed:
+            await anext(failed)
+            raise RuntimeError("boom")
+    await _run_scheduled_closes()
+
+    assert first.data == {"ticks": 1}
+    assert stream.close_reason() == "closed"
+    assert failed.is_closed()
+    assert CLOSED_TICKERS == ["ok", "raised"]
+    with pytest.raises(StopAsyncIteration):
+        await anext(stream)
+
+
+async def test_subscription_stream_dropped_while_open_closes_its_iterator():
+    """Schedules the iterator's aclose on the event loop when the stream is dropped."""
+    CLOSED_TICKERS.clear()
+    schema = grommet.Schema(query=Query, subscription=ClosingSubscription)
+    stream = await schema.execute('subscription { ticks(name: "dropped") }')
+    await anext(stream)
+
+    del stream
+    gc.collect()
+    await _run_scheduled_closes()
+
+    assert CLOSED_TICKERS == ["dropped"]
+
+
+async def test_subscription_selecting_several_root_fields_is_rejected():
+    """Rejects subscriptions with more than one root field, citing the spec rule."""
+    schema = grommet.Schema(query=Query, subscription=Subscription)
+    query = (
+        "subscription Twice { first: counter(limit: 1) ...More }"
+        " fragment More on Subscription { second: counter(limit: 1) }"
+    )
+
+    result = await schema.execute(query)
+    anonymous = await schema.execute("subscription { counter(limit: 1) __typename }")
+    single = await schema.execute(
+        "subscription { counter(limit: 1) ... on Subscription { counter(limit: 1) } }"
+    )
+
+    assert result.data is None
+    assert result.errors[0]["message"] == (
+        'Subscription "Twice" must select only one top level field, but selects '
+        '"first", "second" '
+        "(https://spec.graphql.org/October2021/#sec-Single-root-field)."
+    )
+    assert result.errors[0]["extensions"] == {"code": "VALIDATION_ERROR"}
+    assert anonymous.errors[0]["message"].startswith("Anonymous Subscription must")
+    assert [row.data async for row in single] == [{"counter": 0}]
@@ -169,3 +169,34 @@ def test_union_name_conflicts_fail_schema_build():
 
     with pytest.raises(TypeError, match="conflicting definitions"):
         grommet.Schema(query=Query)
+
+
+def test_interface_implementations_are_validated_at_schema_build():
+    """Lists every interface field an implementer drops or redefines incompatibly."""
+
+    @grommet.interface
+    @dataclass
+    class Node:
+        id: str
+        label: str
+
+    @grommet.type
+    @dataclass
+    class Broken(Node):
+        id: int
+        label: Annotated[str, grommet.Hidden]
+
+    @grommet.type
+    @dataclass
+    class Query:
+        @grommet.field
+        async def node(self) -> Node:
+            return Broken(id=1, label="x")
+
+    with pytest.raises(TypeError) as exc_info:
+        grommet.Schema(query=Query)
+
+    message = str(exc_info.value)
+    assert "Type 'Broken' does not correctly implement interface 'Node'" in message
+    assert "field 'id' has type Int!, expected String!" in message
+    assert "missing field 'label'" in message
@@ -0,0 +1,229 @@
+"""Public contract tests for Relay global object identification."""
+
+import base64
+from dataclasses import dataclass
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+from grommet.plan import build_schema_graph
+
+
+def _global_id(type_name: str, id: object) -> str:
+    return base64.b64encode(f"{type_name}:{id}".encode()).decode()
+
+
+@grommet.type(relay_node=True)
+@dataclass
+class User:
+    id: int
+    name: str
+
+
+@grommet.type(relay_node=True)
+@dataclass
+class Post:
+    title: str
+
+    @grommet.field
+    async def id(self) -> str:
+        return self.title.lower()
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def viewer(self) -> User:
+        return User(id=7, name="Ada")
+
+    @grommet.field
+    async def latest(self) -> Post:
+        return Post(title="Hello")
+
+
+async def _fetch_post(id: str, context: object) -> Post:
+    return Post(title=id.title())
+
+
+def _schema() -> grommet.Schema:
+    schema = grommet.Schema(query=Query)
+    schema._schema.set_node_resolvers(
+        {
+            "User": lambda id, context: User(id=int(id), name=f"{context}-{id}"),
+            "Post": _fetch_post,
+        }
+    )
+    return schema
+
+
+def test_relay_node_types_implement_node_interface():
+    """Adds the Node interface, the node root field, and ID-typed id fields."""
+    sdl = _schema()._schema.as_sdl()
+
+    assert "interface Node {" in sdl
+    assert "type User implements Node {" in sdl
+    assert "type Post implements Node {" in sdl
+    assert "node(id: ID!): Node" in sdl
+    assert "\tid: ID!\n\tname: String!" in sdl
+
+
+async def test_relay_node_ids_are_encoded_on_output(assert_success):
+    """Encodes data-field and resolver ids as base64 global ids."""
+    result = await _schema().execute("{ viewer { id } latest { id } }")
+
+    assert_success(
+        result,
+        {
+            "viewer": {"id": _global_id("User", 7)},
+            "latest": {"id": _global_id("Post", "hello")},
+        },
+    )
+
+
+async def test_node_field_dispatches_to_type_fetchers(assert_success):
+    """Decodes global ids and resolves them with sync and async fetchers."""
+    result = await _schema().execute(
+        "query ($user: ID!, $post: ID!) {"
+        " user: node(id: $user) { id ... on User { name } }"
+        " post: node(id: $post) { __typename ... on Post { title } } }",
+        {"user": _global_id("User", 3), "post": _global_id("Post", "news")},
+        "ctx",
+    )
+
+    assert_success(
+        result,
+        {
+            "user": {"id": _global_id("User", 3), "name": "ctx-3"},
+            "post": {"__typename": "Post", "title": "News"},
+        },
+    )
+
+
+@pytest.mark.parametrize(
+    ("global_id", "message"),
+    [
+        ("not-base64!", 'Invalid global ID "not-base64!".'),
+        (_global_id("Query", 1), f'Invalid global ID "{_global_id("Query", 1)}".'),
+    ],
+)
+async def test_node_field_rejects_unknown_global_ids(global_id, message):
+    """Reports ids that do not decode to a registered Relay node type."""
+    result = await _schema().execute(
+        "query ($id: ID!) { node(id: $id) { id } }", {"id": global_id}
+    )
+
+    assert [error["message"] for error in result.errors] == [message]
+
+
+async def test_node_field_requires_registered_fetcher():
+    """Reports node types that have no fetcher registered."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute(
+        "query ($id: ID!) { node(id: $id) { id } }", {"id": _global_id("User", 1)}
+    )
+
+    assert [error["message"] for error in result.errors] == [
+        'No node resolver is registered for type "User".'
+    ]
+
+
+@grommet.type(name="Query")
+@dataclass
+class LookupQuery:
+    @grommet.field
+    async def viewer(self) -> User:
+        return User(id=7, name="Ada")
+
+    @grommet.field
+    async def lookup(self, id: grommet.ID, related: list[grommet.ID]) -> str:
+        return repr((id, related))
+
+
+async def test_id_arguments_reach_resolvers_as_strings(assert_success):
+    """Delivers ID arguments given as integers or strings to resolvers as str."""
+    schema = grommet.Schema(query=LookupQuery)
+
+    result = await schema.execute('{ lookup(id: 5, related: [6, "x"]) }')
+
+    assert_success(result, {"lookup": "('5', ['6', 'x'])"})
+
+
+async def test_typed_ids_decode_node_global_ids(assert_success):
+    """Wraps ID arguments naming Relay nodes in GlobalIDs with typed_ids."""
+    core = grommet._core.Schema(build_schema_graph(query=LookupQuery), typed_ids=True)
+    user_id = _global_id("User", 7)
+
+    result = await core.execute(
+        "query ($id: ID!, $other: ID!) { lookup(id: $id, related: [$other, 3]) }",
+        {"id": user_id, "other": _global_id("Comment", 1)},
+    )
+
+    expected = (grommet.GlobalID("User", "7"), [_global_id("Comment", 1), "3"])
+    assert_success(result, {"lookup": repr(expected)})
+    assert str(grommet.GlobalID("User", "7")) == user_id
+    assert grommet.GlobalID("User", "7").type == "User"
+
+
+@grommet.input
+@dataclass
+class Mention:
+    target: grommet.ID
+    others: list[grommet.ID]
+
+
+@grommet.type(name="Query")
+@dataclass
+class MentionQuery:
+    @grommet.field
+    async def viewer(self) -> User:
+        return User(id=7, name="Ada")
+
+    @grommet.field
+    async def mention(self, mentions: list[Mention]) -> str:
+        return repr([(mention.target, mention.others) for mention in mentions])
+
+
+async def test_typed_ids_decode_ids_inside_input_objects(assert_success):
+    """Converts ID fields of input object arguments as ID arguments."""
+    core = grommet._core.Schema(build_schema_graph(query=MentionQuery), typed_ids=True)
+
+    result = await core.execute(
+        'query ($id: ID!) { mention(mentions: [{target: $id, others: [4, "x"]}]) }',
+        {"id": _global_id("User", 7)},
+    )
+
+    expected = [(grommet.GlobalID("User", "7"), ["4", "x"])]
+    assert_success(result, {"mention": repr(expected)})
+
+
+def test_relay_node_requires_scalar_id_field():
+    """Rejects Relay node types without a non-null scalar id field."""
+    message = "must declare a non-null scalar 'id' field"
+    with pytest.raises(GrommetTypeError, match=message):
+
+        @grommet.type(relay_node=True)
+        @dataclass
+        class Anonymous:
+            name: str
+
+    with pytest.raises(GrommetTypeError, match=message):
+
+        @grommet.type(relay_node=True)
+        @dataclass
+        class Optional:
+            id: int | None = None
+
+
+def test_relay_node_rejects_existing_node_root_field():
+    """Refuses to replace a node field the query type already declares."""
+
+    @grommet.type(name="Query")
+    @dataclass
+    class NodeQuery:
+        node: str = "taken"
+        user: User | None = None
+
+    with pytest.raises(ValueError, match='already defines a "node" field'):
+        grommet.Schema(query=NodeQuery)
@@ -0,0 +1,74 @@
+"""Public contract tests for the Info resolver parameter."""
+
+from dataclasses import dataclass
+from typing import Annotated
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+SEEN: list[grommet.Info] = []
+
+
+@dataclass
+class State:
+    user: str
+
+
+@grommet.type
+@dataclass
+class Book:
+    title: str
+
+    @grommet.field
+    def label(self, info: grommet.Info, upper: bool = False) -> str:
+        SEEN.append(info)
+        return self.title.upper() if upper else self.title
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field
+    async def books(
+        self, info: grommet.Info, context: Annotated[State, grommet.Context]
+    ) -> list[Book]:
+        SEEN.append(info)
+        assert info.context is context
+        return [Book(title="Notes"), Book(title="Letters")]
+
+
+async def test_info_describes_the_field_being_resolved(assert_success):
+    """Passes field name, path, context, variables, schema, and operation to Info."""
+    SEEN.clear()
+    schema = grommet.Schema(query=Query)
+    state = State(user="ada")
+    result = await schema.execute(
+        "query Shelf($upper: Boolean!) { books { label(upper: $upper) } }",
+        {"upper": True},
+        state,
+    )
+
+    assert_success(result, {"books": [{"label": "NOTES"}, {"label": "LETTERS"}]})
+    books, *labels = SEEN
+    assert books.field_name == "books"
+    assert books.path == ["books"]
+    assert books.root_value is None
+    assert books.variable_values == {"upper": True}
+    assert books.schema.query == "Query"
+    assert books.operation_name == "Shelf"
+    assert sorted(info.path for info in labels) == [
+        ["books", 0, "label"],
+        ["books", 1, "label"],
+    ]
+    assert all(info.context is state for info in labels)
+
+
+def test_batch_resolvers_cannot_take_info():
+    """Rejects Info parameters on batch resolvers, which run once per batch."""
+    with pytest.raises(GrommetTypeError, match="cannot take Info"):
+
+        @grommet.field(batch=True)
+        async def labels(self, info: grommet.Info) -> list[str]:
+            return []
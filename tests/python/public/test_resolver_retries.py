@@ -0,0 +1,87 @@
+"""Public contract tests for retrying failing resolvers."""
+
+import time
+from dataclasses import dataclass
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+CALLS: dict[str, int] = {}
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field(retries=2, backoff_ms=20)
+    async def flaky(self, failures: int) -> str:
+        CALLS["flaky"] = CALLS.get("flaky", 0) + 1
+        if CALLS["flaky"] <= failures:
+            raise TimeoutError(f"attempt {CALLS['flaky']} timed out")
+        return f"ok after {CALLS['flaky']}"
+
+    @grommet.field(retries=3, retry_on=["OSError"])
+    async def selective(self, error: str) -> str:
+        CALLS["selective"] = CALLS.get("selective", 0) + 1
+        raise {"timeout": TimeoutError, "value": ValueError}[error](error)
+
+
+async def test_retries_failing_resolvers_with_backoff(assert_success):
+    """Calls a failing resolver again after waiting, until it succeeds."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+
+    started = time.monotonic()
+    result = await schema.execute("{ flaky(failures: 2) }")
+
+    assert_success(result, {"flaky": "ok after 3"})
+    assert time.monotonic() - started >= 0.06
+
+
+async def test_retried_errors_report_their_attempts():
+    """Surfaces the last error with the number of calls once retries run out."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+
+    result = await schema.execute("{ flaky(failures: 5) }")
+
+    assert result.data is None
+    assert result.errors[0]["message"] == "TimeoutError: attempt 3 timed out"
+    assert result.errors[0]["extensions"] == {"code": "TIMEOUT", "attempts": 3}
+
+
+@pytest.mark.parametrize(
+    ("error", "code", "calls"),
+    [("timeout", "TIMEOUT", 4), ("value", "RESOLVER_ERROR", 1)],
+)
+async def test_retries_only_matching_exceptions(error, code, calls):
+    """Retries exceptions whose class or a base is named in retry_on."""
+    CALLS.clear()
+    schema = grommet.Schema(query=Query)
+
+    result = await schema.execute(f'{{ selective(error: "{error}") }}')
+
+    assert result.errors[0]["extensions"] == {"code": code, "attempts": calls}
+    assert CALLS["selective"] == calls
+
+
+def test_retries_require_a_plain_async_resolver():
+    """Rejects retry policies on sync or batch resolvers and negative settings."""
+    with pytest.raises(GrommetTypeError, match="must be async"):
+
+        @grommet.field(retries=1)
+        def sync_field(self) -> str:
+            return "sync"
+
+    with pytest.raises(GrommetTypeError, match="cannot retry"):
+
+        @grommet.field(batch=True, retries=1)
+        async def batch_field(self) -> list[str]:
+            return ["batch"]
+
+    with pytest.raises(GrommetTypeError, match="must be non-negative"):
+
+        @grommet.field(backoff_ms=-1)
+        async def negative_field(self) -> str:
+            return "negative"
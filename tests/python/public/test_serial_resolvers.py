@@ -0,0 +1,59 @@
+"""Public contract tests for resolvers whose calls run one at a time."""
+
+import asyncio
+from dataclasses import dataclass
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+RUNNING: dict[str, int] = {}
+PEAKS: dict[str, int] = {}
+
+
+async def _tracked(name: str) -> int:
+    RUNNING[name] = RUNNING.get(name, 0) + 1
+    PEAKS[name] = max(PEAKS.get(name, 0), RUNNING[name])
+    await asyncio.sleep(0.01)
+    RUNNING[name] -= 1
+    return PEAKS[name]
+
+
+@grommet.type
+@dataclass
+class Query:
+    @grommet.field(serial=True)
+    async def ledger(self) -> int:
+        return await _tracked("ledger")
+
+    @grommet.field
+    async def feed(self) -> int:
+        return await _tracked("feed")
+
+
+async def test_serial_resolvers_run_one_call_at_a_time():
+    """Runs calls of a serial field in turn while other fields stay concurrent."""
+    RUNNING.clear()
+    PEAKS.clear()
+    schema = grommet.Schema(query=Query)
+
+    result = await schema.execute("{ a: ledger b: ledger c: ledger x: feed y: feed }")
+
+    assert result.errors is None
+    assert PEAKS == {"ledger": 1, "feed": 2}
+
+
+def test_serial_resolvers_require_a_plain_async_resolver():
+    """Rejects serial sync or batch resolvers."""
+    with pytest.raises(GrommetTypeError, match="must be async"):
+
+        @grommet.field(serial=True)
+        def sync_field(self) -> str:
+            return "sync"
+
+    with pytest.raises(GrommetTypeError, match="cannot be serial"):
+
+        @grommet.field(batch=True, serial=True)
+        async def batch_field(self) -> list[str]:
+            return ["batch"]
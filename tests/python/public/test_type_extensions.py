@@ -0,0 +1,151 @@
+"""Public contract tests for extending types declared elsewhere."""
+
+from collections.abc import AsyncIterator
+from dataclasses import dataclass
+
+import pytest
+
+import grommet
+from grommet.errors import GrommetTypeError
+
+
+@grommet.interface
+@dataclass
+class Named:
+    @grommet.field
+    async def name(self) -> str:
+        return ""
+
+
+@grommet.type
+@dataclass
+class Author:
+    first: str
+    last: str
+
+
+@grommet.extend(Author, implements=(Named,))
+class AuthorNames:
+    @grommet.field
+    async def name(self: Author) -> str:
+        return f"{self.first} {self.last}"
+
+
+@grommet.type
+@dataclass
+class Query:
+    greeting: str = "hello"
+
+
+@grommet.extend(Query)
+class AuthorQueries:
+    @grommet.field
+    async def author(self) -> Author:
+        return Author(first="Ada", last="Lovelace")
+
+
+@grommet.extend(Query)
+class CountQueries:
+    @grommet.field
+    def count(self, times: int = 2) -> str:
+        return "hi" * times
+
+
+@grommet.type
+@dataclass
+class Subscription:
+    @grommet.subscription
+    async def ticks(self) -> AsyncIterator[int]:
+        yield 1
+
+
+@grommet.extend(Subscription)
+class NameSubscriptions:
+    @grommet.subscription
+    async def names(self) -> AsyncIterator[Named]:
+        yield Author(first="Grace", last="Hopper")
+
+
+def test_extensions_contribute_fields_and_interfaces():
+    """Merges extension fields and interfaces into the extended types."""
+    sdl = grommet.Schema(query=Query, subscription=Subscription)._schema.as_sdl()
+
+    assert "type Author implements Named {" in sdl
+    assert "\tauthor: Author!\n" in sdl
+    assert "\tcount(times: Int = 2): String!\n" in sdl
+    assert "\tnames: Named!\n" in sdl
+
+
+async def test_extension_resolvers_receive_the_extended_parent(assert_success):
+    """Calls extension resolvers with the parent value of the extended type."""
+    schema = grommet.Schema(query=Query)
+    result = await schema.execute("{ greeting count author { name } }")
+
+    assert_success(
+        result,
+        {"greeting": "hello", "count": "hihi", "author": {"name": "Ada Lovelace"}},
+    )
+
+
+async def test_subscription_extensions_stream_events():
+    """Runs subscription fields contributed by an extension."""
+    schema = grommet.Schema(query=Query, subscription=Subscription)
+    stream = await schema.execute("subscription { names { name } }")
+
+    result = await anext(stream)
+
+    assert result.data == {"names": {"name": "Grace Hopper"}}
+    await stream.aclose()
+
+
+def test_extension_fields_cannot_replace_existing_fields():
+    """Rejects extensions that redeclare a field of the extended type."""
+
+    @grommet.type
+    @dataclass
+    class Book:
+        title: str
+
+    @grommet.extend(Book)
+    class BookTitles:
+        @grommet.field
+        async def title(self) -> str:
+            return ""
+
+    @grommet.type(name="Query")
+    @dataclass
+    class BookQuery:
+        book: Book | None = None
+
+    with pytest.raises(GrommetTypeError, match="already defines field 'title'"):
+        grommet.Schema(query=BookQuery)
+
+
+def test_extensions_reject_invalid_declarations():
+    """Rejects invalid targets, data fields, resolver kinds, and interfaces."""
+
+    @grommet.input
+    @dataclass
+    class Filter:
+        term: str
+
+    with pytest.raises(GrommetTypeError, match="'Filter' is neither"):
+        grommet.extend(Filter)(type("FilterExtension", (), {}))
+
+    with pytest.raises(GrommetTypeError, match="can only declare resolver fields"):
+
+        @grommet.extend(Query)
+        @dataclass
+        class DataExtension:
+            extra: str = ""
+
+    with pytest.raises(GrommetTypeError, match="must use @grommet.field resolvers"):
+
+        @grommet.extend(Query)
+        class StreamExtension:
+            @grommet.subscription
+            async def events(self) -> AsyncIterator[int]:
+                yield 1
+
+    with pytest.raises(GrommetTypeError, match="Author is not an interface type"):
+        grommet.extend(Query, implements=(Author,))(type("BadInterface", (), {}))
@@ -50,18 +50,144 @@ for root in search_roots:
     Python::attach(f)
 }
 
+mod batch {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/batch.rs"));
+}
+
+mod capture {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/capture.rs"));
+}
+
+mod mock {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/mock.rs"));
+}
+
+mod remote {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/remote.rs"));
+}
+
 mod errors {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/errors.rs"));
 }
 
+mod constraints {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/constraints.rs"));
+}
+
 mod types {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/types.rs"));
 }
 
+mod ids {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/ids.rs"));
+}
+
 mod values {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/values.rs"));
 }
 
+#[cfg(feature = "orjson")]
+mod fastpath {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/fastpath.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::values::{ValueSettings, payload_to_value, py_to_value};
+
+        use pyo3::types::PyDict;
+
+        const SETUP: &std::ffi::CStr = pyo3::ffi::c_str!(
+            r#"
+import enum
+import uuid
+
+class Color(enum.Enum):
+    RED = "red"
+
+class Text(str):
+    pass
+
+cyclic = [1]
+cyclic.append(cyclic)
+deep = 1
+for _ in range(300):
+    deep = [deep]
+"#
+        );
+
+        fn eval<'py>(py: Python<'py>, expression: &str) -> Bound<'py, PyAny> {
+            let locals = PyDict::new(py);
+            py.run(SETUP, None, Some(&locals)).unwrap();
+            let code = std::ffi::CString::new(expression).unwrap();
+            py.eval(&code, None, Some(&locals)).unwrap()
+        }
+
+        /// Ensures only payloads orjson converts like the recursive converter are counted.
+        #[test]
+        fn plain_values_reject_what_orjson_converts_differently() {
+            crate::with_py(|py| {
+                let plain = eval(py, r#"{"a": [1, 2.5, "x", None, True], "b": {}}"#);
+                assert_eq!(plain_values(&plain, 256), Some(8));
+                for expression in [
+                    "(1, 2)",
+                    "2**63",
+                    "float('nan')",
+                    "{1: 2}",
+                    "Color.RED",
+                    "uuid.UUID(int=1)",
+                    "Text('x')",
+                    "b'x'",
+                    "cyclic",
+                    "deep",
+                ] {
+                    let value = eval(py, &format!("[{expression}]"));
+                    assert_eq!(plain_values(&value, 256), None, "{expression}");
+                }
+                assert_eq!(plain_values(&eval(py, "[[[1]]]"), 2), None);
+            });
+        }
+
+        /// Ensures large payloads convert, or fail, the same with and without orjson.
+        #[test]
+        fn payloads_convert_the_same_on_both_paths() {
+            crate::with_py(|py| {
+                for expression in [
+                    "[1, 2.5, 'x', None, True, {'k': [1]}]",
+                    "'a\\x00b'",
+                    "(1, 2)",
+                    "2**63",
+                    "2**70",
+                    "{1: 2}",
+                    "Color.RED",
+                    "uuid.UUID(int=1)",
+                    "Text('x')",
+                    "b'x'",
+                    "'\\ud800'",
+                    "cyclic",
+                    "deep",
+                ] {
+                    let payload = eval(
+                        py,
+                        &format!("{{'pad': list(range(1100)), 'value': {expression}}}"),
+                    );
+                    let settings = ValueSettings::default();
+                    let message = |err: PyErr| err.value(py).to_string();
+                    assert_eq!(
+                        payload_to_value(py, &payload, settings).map_err(message),
+                        py_to_value(py, &payload, settings).map_err(message),
+                        "{expression}"
+                    );
+                }
+            });
+        }
+    }
+}
+
+mod info {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/info.rs"));
+}
+
 mod resolver {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/resolver.rs"));
 
@@ -76,9 +202,9 @@ mod resolver {
             Waker::noop().clone()
         }
 
-        /// Ensures custom awaitable bridge reports missing event loop errors.
+        /// Ensures awaitables polled without a running loop finish on the helper loop.
         #[test]
-        fn awaitable_bridge_requires_running_loop() {
+        fn awaitable_bridge_runs_on_helper_loop_without_running_loop() {
             let mut future = crate::with_py(|py| {
                 let locals = PyDict::new(py);
                 py.run(
@@ -100,20 +226,18 @@ async def coro():
 
             let waker = noop_waker();
             let mut cx = Context::from_waker(&waker);
-            assert!(matches!(future.as_mut().poll(&mut cx), Poll::Pending));
-
-            match future.as_mut().poll(&mut cx) {
-                Poll::Ready(Err(err)) => {
-                    let message = crate::with_py(|py| {
-                        err.value(py).str().unwrap().to_str().unwrap().to_string()
-                    });
-                    assert!(
-                        message.to_ascii_lowercase().contains("event loop"),
-                        "unexpected error: {message}"
-                    );
+            for _ in 0..1000 {
+                match future.as_mut().poll(&mut cx) {
+                    Poll::Ready(Ok(value)) => {
+                        let value = crate::with_py(|py| value.bind(py).extract::<i64>().unwrap());
+                        assert_eq!(value, 7);
+                        return;
+                    }
+                    Poll::Ready(Err(err)) => panic!("awaitable failed: {err}"),
+                    Poll::Pending => std::thread::sleep(std::time::Duration::from_millis(1)),
                 }
-                _ => panic!("expected missing running event loop error"),
             }
+            panic!("expected the awaitable to finish on the helper loop");
         }
 
         /// Ensures async-iterator detection accepts objects with __anext__.
@@ -156,3 +280,430 @@ class IterOnly:
         }
     }
 }
+
+mod replay {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/replay.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn cursors(events: &[(String, PyObj)]) -> Vec<&str> {
+            events.iter().map(|(cursor, _)| cursor.as_str()).collect()
+        }
+
+        /// Ensures the buffer deduplicates cursors, evicts the oldest, and replays the gap.
+        #[test]
+        fn replay_buffer_records_and_returns_events_after_cursor() {
+            crate::with_py(|py| {
+                let value = PyObj::new(py.None());
+                let mut buffer = ReplayBuffer::default();
+                buffer.record("ticks", "1", &value);
+                assert!(buffer.events_after("ticks", "0").is_empty());
+
+                buffer.set_capacity(3);
+                for cursor in ["1", "2", "2", "3", "4"] {
+                    buffer.record("ticks", cursor, &value);
+                }
+                assert_eq!(cursors(&buffer.events_after("ticks", "2")), ["3", "4"]);
+                assert_eq!(cursors(&buffer.events_after("ticks", "1")), ["2", "3", "4"]);
+                assert!(buffer.events_after("ticks", "4").is_empty());
+                assert!(buffer.events_after("other", "1").is_empty());
+
+                buffer.set_capacity(1);
+                assert_eq!(cursors(&buffer.events_after("ticks", "0")), ["4"]);
+            });
+        }
+    }
+}
+
+mod document {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/document.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use async_graphql::dynamic::{
+            Field, FieldFuture, FieldValue, InputObject, InputValue, Object, Schema, TypeRef,
+        };
+        use async_graphql::parser::parse_query;
+
+        fn field(name: &str, ty: TypeRef) -> Field {
+            Field::new(name, ty, |_| {
+                FieldFuture::new(async { Ok(None::<FieldValue<'_>>) })
+            })
+        }
+
+        fn schema() -> Schema {
+            let user = Object::new("User")
+                .field(field("name", TypeRef::named_nn(TypeRef::STRING)))
+                .field(field("friends", TypeRef::named_nn_list_nn("User")));
+            let query = Object::new("Query").field(field("user", TypeRef::named("User")));
+            Schema::build("Query", None, None)
+                .register(user)
+                .register(query)
+                .finish()
+                .unwrap()
+        }
+
+        /// Ensures fragments, inline fragments, and nested lists resolve parent types.
+        #[test]
+        fn collect_field_coordinates_follows_fragments_once() {
+            let schema = schema();
+            let doc = parse_query(
+                "{ user { ...F ... on User { missing } __typename } } \
+                 fragment F on User { name friends { ...F } }",
+            )
+            .unwrap();
+
+            let coordinates: Vec<(String, String)> =
+                collect_field_coordinates(schema.registry(), &doc)
+                    .into_iter()
+                    .map(|coordinate| (coordinate.type_name, coordinate.field_name))
+                    .collect();
+            let expected = [
+                ("Query", "user"),
+                ("User", "name"),
+                ("User", "friends"),
+                ("User", "missing"),
+            ];
+            assert_eq!(
+                coordinates,
+                expected.map(|(ty, name)| (ty.to_string(), name.to_string()))
+            );
+        }
+
+        /// Ensures root fields are named once, through fragments, for the operation that runs.
+        #[test]
+        fn summarize_operation_names_root_fields_of_the_operation_that_runs() {
+            let doc = parse_query(
+                "query Profile { me: user { name } ...Roots ... { user } __typename } \
+                 fragment Roots on Query { viewer ...Roots }",
+            )
+            .unwrap();
+            let summary = summarize_operation(&doc, None).unwrap();
+            let several = parse_query("query A { user } query B { viewer }").unwrap();
+            let named = summarize_operation(&several, Some("B")).unwrap();
+
+            assert_eq!(summary.name.as_deref(), Some("Profile"));
+            assert_eq!(summary.root_fields, ["user", "viewer"]);
+            assert!(summarize_operation(&several, None).is_none());
+            assert_eq!(named.name.as_deref(), Some("B"));
+            assert_eq!(named.root_fields, ["viewer"]);
+        }
+
+        /// Ensures coercion errors name the argument, the variable, and the paths below them.
+        #[test]
+        fn describe_input_errors_traces_paths_into_variables() {
+            let filter = InputObject::new("Filter").field(InputValue::new(
+                "tags",
+                TypeRef::named_nn_list(TypeRef::STRING),
+            ));
+            let find = field("find", TypeRef::named(TypeRef::STRING))
+                .argument(InputValue::new("filter", TypeRef::named("Filter")))
+                .argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)));
+            let schema = Schema::build("Query", None, None)
+                .register(filter)
+                .register(Object::new("Query").field(find))
+                .finish()
+                .unwrap();
+            let doc = parse_query(
+                "query ($tag: String, $limit: Int!) { ...F find(limit: $limit) } \
+                 fragment F on Query { find(filter: {tags: [\"a\", $tag]}) }",
+            )
+            .unwrap();
+            let at = |column| Some(Pos { line: 1, column });
+            let mut errors = [
+                ServerError::new("Invalid value for argument", at(92)),
+                ServerError::new("Variable limit is not defined.", at(55)),
+            ];
+            let variables = Value::from_json(serde_json::json!({"tag": 1})).unwrap();
+
+            describe_input_errors(schema.registry(), &doc, Some(&variables), &mut errors);
+
+            let extensions: Vec<_> = errors
+                .iter()
+                .map(|error| serde_json::to_value(&error.extensions).unwrap())
+                .collect();
+            assert_eq!(
+                extensions,
+                [
+                    serde_json::json!({
+                        "argument": "filter",
+                        "input_path": ["tags", 1],
+                        "variable": "tag",
+                        "variable_path": [],
+                    }),
+                    serde_json::json!({"variable": "limit"}),
+                ]
+            );
+        }
+    }
+}
+
+mod rate_limit {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/rate_limit.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use async_graphql::dynamic::{Field, FieldFuture, FieldValue, Object, Schema, TypeRef};
+        use async_graphql::parser::parse_query;
+        use pyo3::types::PyString;
+
+        fn schema() -> Schema {
+            let field = |name: &str| {
+                Field::new(name, TypeRef::named(TypeRef::STRING), |_| {
+                    FieldFuture::new(async { Ok(None::<FieldValue<'_>>) })
+                })
+            };
+            let query = Object::new("Query")
+                .field(field("greeting"))
+                .field(field("secret"));
+            Schema::build("Query", None, None)
+                .register(query)
+                .finish()
+                .unwrap()
+        }
+
+        fn limits(py: Python<'_>, per_second: f64) -> RateLimits {
+            let rule = PyDict::new(py);
+            rule.set_item("field", "Query.secret").unwrap();
+            rule.set_item("capacity", 1).unwrap();
+            rule.set_item("per_second", per_second).unwrap();
+            let key = py.eval(pyo3::ffi::c_str!("lambda context: context"), None, None);
+            RateLimits::new(&[rule], Some(PyObj::new(key.unwrap().unbind()))).unwrap()
+        }
+
+        /// Ensures only the operation a document runs is charged.
+        #[test]
+        fn check_charges_only_the_operation_that_runs() {
+            crate::with_py(|py| {
+                let schema = schema();
+                let limits = limits(py, 0.001);
+                let several = parse_query("query A { greeting } query B { secret }").unwrap();
+                let secret = parse_query("query B { secret }").unwrap();
+                let check = |doc, name| {
+                    let checked = limits.check(py, schema.registry(), doc, name, None);
+                    checked.unwrap()
+                };
+
+                assert!(check(&several, None).is_ok());
+                assert!(check(&several, Some("A")).is_ok());
+                assert!(check(&several, Some("A")).is_ok());
+                assert!(check(&several, Some("B")).is_ok());
+                assert!(check(&several, Some("B")).is_err());
+                assert!(check(&secret, None).is_err());
+            });
+        }
+
+        /// Ensures buckets that refilled are swept instead of kept for every key seen.
+        #[test]
+        fn check_sweeps_refilled_buckets() {
+            crate::with_py(|py| {
+                let schema = schema();
+                let limits = limits(py, 1e9);
+                let doc = parse_query("{ secret }").unwrap();
+                for user in 0..(MIN_SWEEP * 4) {
+                    let key = PyString::new(py, &user.to_string()).into_any();
+                    let checked = limits.check(py, schema.registry(), &doc, None, Some(&key));
+                    assert!(checked.unwrap().is_ok());
+                }
+
+                let buckets = limits.buckets.lock().unwrap();
+                assert!(buckets.entries.len() <= MIN_SWEEP);
+            });
+        }
+    }
+}
+
+mod msgpack {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/msgpack.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Ensures scalars pick the smallest MessagePack representation.
+        #[test]
+        fn encode_uses_compact_scalar_markers() {
+            let cases = [
+                (serde_json::json!(null), vec![0xc0]),
+                (serde_json::json!(true), vec![0xc3]),
+                (serde_json::json!(7), vec![0x07]),
+                (serde_json::json!(-3), vec![0xfd]),
+                (serde_json::json!(200), vec![0xcc, 0xc8]),
+                (serde_json::json!(-200), vec![0xd1, 0xff, 0x38]),
+                (serde_json::json!(70000), vec![0xce, 0x00, 0x01, 0x11, 0x70]),
+                (
+                    serde_json::json!(1.5),
+                    vec![0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0],
+                ),
+            ];
+            for (value, expected) in cases {
+                assert_eq!(encode(&value), expected, "{value}");
+            }
+        }
+
+        /// Ensures containers and long strings switch to sized headers.
+        #[test]
+        fn encode_uses_sized_headers_for_large_containers() {
+            let text = "x".repeat(40);
+            let encoded = encode(&serde_json::json!(text));
+            assert_eq!(&encoded[..2], &[0xd9, 40]);
+
+            let items = serde_json::Value::Array(vec![serde_json::json!(1); 20]);
+            assert_eq!(&encode(&items)[..3], &[0xdc, 0, 20]);
+
+            let encoded = encode(&serde_json::json!({"b": 1, "a": [true]}));
+            assert_eq!(
+                encoded,
+                vec![0x82, 0xa1, b'b', 0x01, 0xa1, b'a', 0x91, 0xc3]
+            );
+        }
+    }
+}
+
+mod check {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/check.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use async_graphql::parser::parse_query;
+
+        /// Ensures caller variable types are checked for nullability, lists, and coverage.
+        #[test]
+        fn variable_errors_compare_declared_and_provided_types() {
+            let doc =
+                parse_query("query ($id: Int!, $tags: [String!], $limit: Int = 10) { value }")
+                    .unwrap();
+            let operation = single_operation(&doc).unwrap();
+            let types = |pairs: &[(&str, &str)]| {
+                pairs
+                    .iter()
+                    .map(|(name, ty)| (name.to_string(), ty.to_string()))
+                    .collect::<HashMap<_, _>>()
+            };
+
+            assert!(
+                variable_errors(operation, &types(&[("id", "Int!"), ("tags", "String!")]))
+                    .is_empty()
+            );
+            assert_eq!(
+                variable_errors(
+                    operation,
+                    &types(&[("tags", "[String]"), ("limit", "[Int"), ("extra", "Int")])
+                ),
+                [
+                    "Variable \"$id\" of required type \"Int!\" was not provided.",
+                    "Variable \"$tags\" of type \"[String]\" cannot be used as \"[String!]\".",
+                    "Variable \"$limit\" has an invalid type \"[Int\".",
+                    "Variable \"$extra\" is not defined by the operation.",
+                ]
+            );
+        }
+    }
+}
+
+mod response {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/response.rs"));
+}
+
+mod diff {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/diff.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn value(json: &str) -> Value {
+            serde_json::from_str(json).unwrap()
+        }
+
+        /// Ensures differences are found by key and index, skipping ignored paths.
+        #[test]
+        fn response_differences_pair_values_by_path() {
+            let left = value(
+                r#"{"users": [{"name": "ada", "seen": 1}, {"name": "bob", "seen": 2}], "total": 2}"#,
+            );
+            let right = value(
+                r#"{"total": 3, "users": [{"seen": 5, "name": "ada"}, {"name": "eve", "seen": 6}, null], "page": null}"#,
+            );
+            let ignore = IgnorePaths::new(&["users.*.seen".to_string()]);
+
+            let differences = response_differences(&left, &right, &ignore);
+
+            let key = |key: &str| Segment::Key(key.to_string());
+            assert_eq!(
+                differences,
+                vec![
+                    Difference {
+                        path: vec![key("users"), Segment::Index(1), key("name")],
+                        left: Some(&Value::from("bob")),
+                        right: Some(&Value::from("eve")),
+                    },
+                    Difference {
+                        path: vec![key("users"), Segment::Index(2)],
+                        left: None,
+                        right: Some(&Value::Null),
+                    },
+                    Difference {
+                        path: vec![key("total")],
+                        left: Some(&Value::from(2)),
+                        right: Some(&Value::from(3)),
+                    },
+                    Difference {
+                        path: vec![key("page")],
+                        left: None,
+                        right: Some(&Value::Null),
+                    },
+                ]
+            );
+        }
+    }
+}
+
+mod signature {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/signature.rs"));
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Ensures signatures hide literals, drop aliases and unused fragments, and sort.
+        #[test]
+        fn operation_signature_normalizes_like_apollo() {
+            let doc = parse_query(
+                r#"
+                query Foo($b: Int, $a: Boolean) {
+                  user(name: "hello", age: 5) {
+                    ...Bar
+                    ... on User { hello bee }
+                    tz
+                    aliased: name
+                  }
+                }
+                fragment Baz on User { asd }
+                fragment Bar on User { age @skip(if: $a) ...Nested }
+                fragment Nested on User { blah }
+                "#,
+            )
+            .unwrap();
+
+            assert_eq!(
+                operation_signature(&doc, None).as_deref(),
+                Some(
+                    "fragment Bar on User{age@skip(if:$a)...Nested}\
+                     fragment Nested on User{blah}\
+                     query Foo($a:Boolean,$b:Int)\
+                     {user(age:0,name:\"\"){name tz...Bar...on User{bee hello}}}"
+                )
+            );
+            assert_eq!(operation_signature(&doc, Some("Bar")), None);
+        }
+    }
+}